@@ -0,0 +1,91 @@
+//! Benchmarks comparing the event queue backends on the classic "hold" workload, in which a queue holding a fixed
+//! number of events repeatedly pops its next event and schedules a replacement a random delay later. Filling the queue
+//! happens outside the measurement, so that only the steady state is compared.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+use desque::queue::{BinaryHeapQueue, CalendarQueue, EventQueueBackend};
+use desque::serial::{OkEvent, Simulation};
+use desque::SimState;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+/// Number of events executed in each benchmark iteration, regardless of the number held on the queue.
+const EVENTS_EXECUTED: usize = 100_000;
+
+/// Simulation state for the hold workload
+struct Hold {
+    executed: usize,
+    rng: Pcg64,
+}
+
+impl SimState<u64> for Hold {
+    fn is_complete(&self, _: &u64) -> bool {
+        self.executed >= EVENTS_EXECUTED
+    }
+}
+
+/// An event that reschedules itself on execution
+#[derive(Debug)]
+struct Tick;
+
+impl Tick {
+    fn schedule<Backend>(sim: &mut Simulation<Hold, u64, Backend>)
+    where
+        Backend: EventQueueBackend<u64>,
+    {
+        let delay = sim.state_mut().rng.random_range(0..1_000_000);
+        sim.schedule_with_delay(Self, delay)
+            .expect("delay should not be negative");
+    }
+}
+
+impl<Backend> OkEvent<Hold, u64, Backend> for Tick
+where
+    Backend: EventQueueBackend<u64>,
+{
+    fn execute(&mut self, sim: &mut Simulation<Hold, u64, Backend>) {
+        sim.state_mut().executed += 1;
+        Self::schedule(sim);
+    }
+}
+
+fn fill<Backend>(backend: Backend, queue_size: usize) -> Simulation<Hold, u64, Backend>
+where
+    Backend: EventQueueBackend<u64>,
+{
+    let state = Hold {
+        executed: 0,
+        rng: Pcg64::seed_from_u64(42),
+    };
+    let mut sim = Simulation::with_backend(state, 0, backend);
+    for _ in 0..queue_size {
+        Tick::schedule(&mut sim);
+    }
+    sim
+}
+
+/// Run the filled simulation, handing it back so that it is dropped outside the measurement.
+fn hold<Backend>(mut sim: Simulation<Hold, u64, Backend>) -> Simulation<Hold, u64, Backend>
+where
+    Backend: EventQueueBackend<u64>,
+{
+    sim.run().expect("simulation should complete normally");
+    sim
+}
+
+fn compare_backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("hold");
+    group.sample_size(20);
+    for queue_size in [10, 1_000, 100_000] {
+        group.bench_with_input(BenchmarkId::new("binary_heap", queue_size), &queue_size, |b, size| {
+            b.iter_batched(|| fill(BinaryHeapQueue::new(), *size), hold, BatchSize::LargeInput)
+        });
+        group.bench_with_input(BenchmarkId::new("calendar", queue_size), &queue_size, |b, size| {
+            b.iter_batched(|| fill(CalendarQueue::new(), *size), hold, BatchSize::LargeInput)
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, compare_backends);
+criterion_main!(benches);
@@ -0,0 +1,224 @@
+//! Benchmarks comparing how events are stored on the queue, using the workload of the `mm1_queue` example: customers
+//! arrive at a single server with a mean spacing of thirty minutes and are served with a mean duration of twenty. Each
+//! event carries a little data about its customer, as events in a fuller model would.
+//!
+//! The serial flavour is measured both scheduling events by value, which moves them into the queue's arena, and
+//! scheduling them through `schedule_from_boxed()`, which allocates each event separately. The inline flavour, which
+//! stores a single event type without boxing, is included as a baseline.
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use desque::{inline, serial, SimState, SimTime};
+use rand::SeedableRng;
+use rand_distr::{Distribution, Exp};
+use rand_pcg::Pcg64;
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+use std::ops::Add;
+
+/// Length of each simulated run, in minutes, covering roughly 33,000 customers.
+const END_TIME: f64 = 1_000_000.0;
+
+/// Wrap f64 with a new type so we can implement the Ord trait.
+#[derive(Copy, Clone, Debug, PartialEq)]
+struct Time(f64);
+
+impl Eq for Time {}
+
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Time {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap()
+    }
+}
+
+impl SimTime for Time {}
+
+impl Add<f64> for Time {
+    type Output = Self;
+
+    fn add(self, rhs: f64) -> Self::Output {
+        Self(self.0 + rhs)
+    }
+}
+
+/// Tracks the arrival times of waiting customers, whether the server is busy, and the statistics gathered so far.
+struct Store {
+    line: VecDeque<f64>,
+    server_busy: bool,
+    arrivals: usize,
+    total_time_in_system: f64,
+    rng: Pcg64,
+    /// Whether the serial flavour should schedule events through `schedule_from_boxed()`.
+    boxed: bool,
+}
+
+impl Store {
+    fn new(boxed: bool) -> Self {
+        Self {
+            line: VecDeque::new(),
+            server_busy: false,
+            arrivals: 0,
+            total_time_in_system: 0.0,
+            rng: Pcg64::seed_from_u64(7),
+            boxed,
+        }
+    }
+
+    fn interarrival_time(&mut self) -> f64 {
+        Exp::new(1.0 / 30.0).unwrap().sample(&mut self.rng)
+    }
+
+    fn service_time(&mut self) -> f64 {
+        Exp::new(1.0 / 20.0).unwrap().sample(&mut self.rng)
+    }
+
+    /// Record an arrival, returning whether the customer goes straight to the server.
+    fn arrive(&mut self, customer: usize, now: f64) -> bool {
+        self.arrivals = customer + 1;
+        if self.server_busy {
+            self.line.push_back(now);
+            false
+        } else {
+            self.server_busy = true;
+            true
+        }
+    }
+
+    /// Record a departure, returning the arrival time of the next customer to serve, if any.
+    fn depart(&mut self, arrived: f64, now: f64) -> Option<f64> {
+        self.total_time_in_system += now - arrived;
+        let next = self.line.pop_front();
+        self.server_busy = next.is_some();
+        next
+    }
+}
+
+impl SimState<Time> for Store {
+    fn is_complete(&self, current_time: &Time) -> bool {
+        current_time.0 >= END_TIME
+    }
+}
+
+/// Schedule an event on a serial simulation, either by value or boxed as the store dictates.
+fn schedule_serial<EventType>(sim: &mut serial::Simulation<Store, Time>, event: EventType, delay: f64)
+where
+    EventType: serial::Event<Store, Time> + 'static,
+{
+    let time = *sim.current_time() + delay;
+    let result = if sim.state().boxed {
+        sim.schedule_from_boxed(Box::new(event), time)
+    } else {
+        sim.schedule(event, time)
+    };
+    result.expect("delay should not be negative");
+}
+
+#[derive(Debug)]
+struct ArrivalEvent {
+    customer: usize,
+}
+
+impl serial::OkEvent<Store, Time> for ArrivalEvent {
+    fn execute(&mut self, sim: &mut serial::Simulation<Store, Time>) {
+        let now = sim.current_time().0;
+        if sim.state_mut().arrive(self.customer, now) {
+            let delay = sim.state_mut().service_time();
+            schedule_serial(sim, ServiceEvent { arrived: now }, delay);
+        }
+
+        let delay = sim.state_mut().interarrival_time();
+        schedule_serial(
+            sim,
+            ArrivalEvent {
+                customer: self.customer + 1,
+            },
+            delay,
+        );
+    }
+}
+
+#[derive(Debug)]
+struct ServiceEvent {
+    arrived: f64,
+}
+
+impl serial::OkEvent<Store, Time> for ServiceEvent {
+    fn execute(&mut self, sim: &mut serial::Simulation<Store, Time>) {
+        let now = sim.current_time().0;
+        if let Some(arrived) = sim.state_mut().depart(self.arrived, now) {
+            let delay = sim.state_mut().service_time();
+            schedule_serial(sim, ServiceEvent { arrived }, delay);
+        }
+    }
+}
+
+/// The same events as above, as a single type for the inline flavour.
+#[derive(Debug)]
+enum CustomerEvent {
+    Arrival { customer: usize },
+    Service { arrived: f64 },
+}
+
+impl inline::OkEvent<Store, Time> for CustomerEvent {
+    fn execute(&mut self, sim: &mut inline::Simulation<Store, Time, Self>) {
+        let now = sim.current_time().0;
+        match *self {
+            CustomerEvent::Arrival { customer } => {
+                if sim.state_mut().arrive(customer, now) {
+                    let delay = sim.state_mut().service_time();
+                    sim.schedule(CustomerEvent::Service { arrived: now }, *sim.current_time() + delay)
+                        .expect("delay should not be negative");
+                }
+
+                let delay = sim.state_mut().interarrival_time();
+                sim.schedule(
+                    CustomerEvent::Arrival { customer: customer + 1 },
+                    *sim.current_time() + delay,
+                )
+                .expect("delay should not be negative");
+            },
+            CustomerEvent::Service { arrived } => {
+                if let Some(arrived) = sim.state_mut().depart(arrived, now) {
+                    let delay = sim.state_mut().service_time();
+                    sim.schedule(CustomerEvent::Service { arrived }, *sim.current_time() + delay)
+                        .expect("delay should not be negative");
+                }
+            },
+        }
+    }
+}
+
+/// Run a simulation to completion, handing it back so that it is dropped outside the measurement.
+fn run_serial(boxed: bool) -> serial::Simulation<Store, Time> {
+    let mut sim = serial::Simulation::new(Store::new(boxed), Time(0.0));
+    let delay = sim.state_mut().interarrival_time();
+    schedule_serial(&mut sim, ArrivalEvent { customer: 0 }, delay);
+    sim.run().expect("simulation should complete normally");
+    sim
+}
+
+fn run_inline() -> inline::Simulation<Store, Time, CustomerEvent> {
+    let mut sim = inline::Simulation::new(Store::new(false), Time(0.0));
+    let delay = sim.state_mut().interarrival_time();
+    sim.schedule(CustomerEvent::Arrival { customer: 0 }, *sim.current_time() + delay)
+        .expect("delay should not be negative");
+    sim.run().expect("simulation should complete normally");
+    sim
+}
+
+fn compare_storage(c: &mut Criterion) {
+    let mut group = c.benchmark_group("mm1_queue");
+    group.sample_size(20);
+    group.bench_function("boxed", |b| b.iter_batched(|| true, run_serial, BatchSize::SmallInput));
+    group.bench_function("arena", |b| b.iter_batched(|| false, run_serial, BatchSize::SmallInput));
+    group.bench_function("inline", |b| b.iter(run_inline));
+    group.finish();
+}
+
+criterion_group!(benches, compare_storage);
+criterion_main!(benches);
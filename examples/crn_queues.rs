@@ -17,24 +17,31 @@
 //! customer to also clear the queue, which then ensures the same random numbers are used for the same purposes in each
 //! server configuration.
 
+use desque::replications::Replications;
 use desque::serial::*;
+use desque::stats::Tally;
 use desque::{SimState, SimTime};
 use rand::{Rng, SeedableRng};
 use rand_distr::{Distribution, Exp};
 use rand_pcg::Pcg64;
 use std::cmp::Ordering;
 use std::collections::VecDeque;
-use std::thread;
 
 /// Wrap f64 in a struct to implement Ord and SimTime
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 struct F64Time(f64);
 
 impl Eq for F64Time {}
 
+impl PartialOrd for F64Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for F64Time {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.0.partial_cmp(&other.0).unwrap()
     }
 }
 
@@ -54,7 +61,7 @@ struct Store {
     arrival_distr: Exp<f64>,            // rate of 4.0
     customer_queue: VecDeque<Customer>, // FIFO queue
     customers_served: usize,            // output stat
-    total_time_in_queue: f64,           // output stat
+    time_in_queue: Tally,               // output stat
     complete: bool,                     // terminate event loop when true
     rng: Pcg64,                         // random number generator
 }
@@ -68,7 +75,7 @@ impl Store {
             arrival_distr: Exp::new(4.0).unwrap(),
             customer_queue: VecDeque::new(),
             customers_served: 0,
-            total_time_in_queue: 0.0,
+            time_in_queue: Tally::new(),
             complete: false,
             rng,
         }
@@ -131,7 +138,8 @@ struct ServiceEvent {}
 
 impl ServiceEvent {
     fn schedule(customer: Customer, sim: &mut Simulation<Store, F64Time>) {
-        sim.state_mut().total_time_in_queue += sim.current_time().0 - customer.arrival_time.0;
+        let time_in_queue = sim.current_time().0 - customer.arrival_time.0;
+        sim.state_mut().time_in_queue.record(time_in_queue);
 
         let service_delay = customer.service_time_random_draw.ln() / -sim.state().service_rate;
         let service_time = sim.current_time().0 + service_delay;
@@ -179,43 +187,52 @@ impl OkEvent<Store, F64Time> for EndEvent {
         store.complete = true;
 
         for customer in store.customer_queue.iter() {
-            store.total_time_in_queue += now - customer.arrival_time.0;
+            store.time_in_queue.record(now - customer.arrival_time.0);
         }
     }
 }
 
-fn run_sim(seed: u64, num_servers: u32, service_rate: f64) -> (usize, f64) {
+fn build_sim(seed: u64, num_servers: u32, service_rate: f64) -> Simulation<Store, F64Time> {
     let rng = Pcg64::seed_from_u64(seed);
     let store = Store::new(num_servers, service_rate, rng);
     let mut sim = Simulation::new(store, F64Time(0.0));
     EndEvent::schedule(F64Time(540.0), &mut sim);
     ArrivalEvent::schedule_first(&mut sim);
-
-    sim.run().expect("simulation should complete normally");
-    (sim.state().customers_served, sim.state().total_time_in_queue)
+    sim
 }
 
 fn main() {
+    // every configuration runs a batch from the same base seed, so each replication index sees the same customers
     let seed: u64 = rand::random();
-    let mm1 = thread::spawn(move || run_sim(seed, 1, 6.0));
-    let mm2 = thread::spawn(move || run_sim(seed, 2, 3.0));
-    let mm3 = thread::spawn(move || run_sim(seed, 3, 2.0));
-
-    let mm1_results = mm1.join().expect("thread should return normally");
-    let mm2_results = mm2.join().expect("thread should return normally");
-    let mm3_results = mm3.join().expect("thread should return normally");
+    let replications = Replications::new(10, seed);
 
-    println!("Comparing queue configurations using the seed {seed}:");
-    println!(
-        "M/M/1 with rate 6.0 served {} customers with {} total time in queue",
-        mm1_results.0, mm1_results.1
-    );
-    println!(
-        "M/M/2 with rate 3.0 served {} customers with {} total time in queue",
-        mm2_results.0, mm2_results.1
-    );
     println!(
-        "M/M/3 with rate 2.0 served {} customers with {} total time in queue",
-        mm3_results.0, mm3_results.1
+        "Comparing queue configurations over {} replications using the seed {seed}:",
+        replications.count()
     );
+    for (name, num_servers, service_rate) in [("M/M/1", 1, 6.0), ("M/M/2", 2, 3.0), ("M/M/3", 3, 2.0)] {
+        let results = replications
+            .run(
+                |_, seed| build_sim(seed, num_servers, service_rate),
+                |sim| (sim.state().customers_served, sim.state().time_in_queue.sum()),
+            )
+            .expect("simulation should complete normally");
+
+        let mut customers_served = Tally::new();
+        let mut total_time_in_queue = Tally::new();
+        for (served, time_in_queue) in results {
+            customers_served.record(served as f64);
+            total_time_in_queue.record(time_in_queue);
+        }
+
+        println!(
+            "{name} with rate {service_rate:.1} served {:.1} customers with {:.3} total time in queue on average",
+            customers_served
+                .mean()
+                .expect("at least one replication should have run"),
+            total_time_in_queue
+                .mean()
+                .expect("at least one replication should have run"),
+        );
+    }
 }
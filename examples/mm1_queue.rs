@@ -4,13 +4,13 @@
 //! The simulation runs for nine hours before terminating, and so could represent a small, service-oriented business's
 //! typical workday.
 //!
-//! Arrival events check whether the server is currently busy. If so, the arriving customer gets in line. If not, the
-//! arriving customer goes directly to the server and a Service event is scheduled. Either way, a new Arrival event is
-//! also scheduled.
+//! The server is modelled as a single-unit `Resource`. Arrival events request the server on behalf of the arriving
+//! customer, who gets in line if it is busy, and schedule a new Arrival event. Once the customer reaches the server,
+//! a StartService event schedules a Service event for the end of their service time.
 //!
-//! Service events check the current size of the queue. If nonzero, then the queue size is decremented and a new Service
-//! event scheduled for the next customer.
+//! Service events release the server, which passes it to the next customer in line, if any.
 
+use desque::resource::Resource;
 use desque::serial::*;
 use desque::Result;
 use desque::{SimState, SimTime};
@@ -21,11 +21,17 @@ use std::cmp::Ordering;
 use std::ops::Add;
 
 /// Wrap f64 with a new type so we can implement the Ord trait.
-#[derive(Copy, Clone, Debug, PartialEq, PartialOrd)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 struct Time(f64);
 
 impl Eq for Time {}
 
+impl PartialOrd for Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for Time {
     fn cmp(&self, other: &Self) -> Ordering {
         self.0.partial_cmp(&other.0).unwrap()
@@ -42,11 +48,10 @@ impl Add<f64> for Time {
     }
 }
 
-/// Tracks the current length of the queue, whether the server is busy or idle, the desired end time of the simulation,
-/// and the random number generator from which arrival and service times are drawn.
+/// Tracks the server and its line of customers, the desired end time of the simulation, and the random number
+/// generator from which arrival and service times are drawn.
 struct Store {
-    queue_length: usize,
-    server_busy: bool,
+    server: Resource<StartServiceEvent>,
     end_time: f64,
     rng: Pcg64,
 }
@@ -55,8 +60,7 @@ impl Store {
     /// Creates an empty store with idle server, logs the desired end time, and seeds a random-number generator.
     fn new(end_time: f64) -> Self {
         Self {
-            queue_length: 0,
-            server_busy: false,
+            server: Resource::new(1),
             end_time,
             rng: Pcg64::from_rng(&mut rand::rng()),
         }
@@ -81,41 +85,52 @@ impl ArrivalEvent {
         let distribution = Exp::new(1.0 / 30.0).unwrap();
         let next_arrival_delay = distribution.sample(&mut sim.state_mut().rng);
         let next_arrival_time = *sim.current_time() + next_arrival_delay;
-        sim.schedule(ArrivalEvent {}, next_arrival_time)
+        sim.schedule(ArrivalEvent {}, next_arrival_time)?;
+        Ok(())
     }
 
     fn schedule_first(sim: &mut Simulation<Store, Time>) -> Result {
         let distribution = Exp::new(1.0 / 30.0).unwrap();
         let next_arrival_delay = distribution.sample(&mut sim.state_mut().rng);
         let next_arrival_time = *sim.current_time() + next_arrival_delay;
-        sim.schedule(Self {}, next_arrival_time)
+        sim.schedule(Self {}, next_arrival_time)?;
+        Ok(())
     }
 }
 
 impl Event<Store, Time> for ArrivalEvent {
-    /// If server is idle, mark it busy and schedule a service event. Otherwise, increment the queue length.
+    /// Request the server, which starts service immediately if it is idle and otherwise puts the customer in line.
     ///
     /// Regardless, schedule a new ArrivalEvent.
     fn execute(&mut self, sim: &mut Simulation<Store, Time>) -> Result {
         println!("Handling customer arrival at time {:.3}...", sim.current_time().0);
 
-        if sim.state().server_busy {
+        if sim.state().server.available() == 0 {
             println!(
                 "Server is occupied with prior customer. Getting in line behind {} other customers.",
-                sim.state().queue_length,
+                sim.state().server.queue_length(),
             );
-            sim.state_mut().queue_length += 1;
         } else {
             println!("Server is idle; moving to counter.");
-            sim.state_mut().server_busy = true;
-            ServiceEvent::schedule(sim)?;
         }
+        sim.request_resource(|store| &mut store.server, StartServiceEvent {})?;
 
         ArrivalEvent::schedule(sim)?;
         Ok(())
     }
 }
 
+/// Handle a customer reaching the counter.
+#[derive(Debug)]
+struct StartServiceEvent {}
+
+impl Event<Store, Time> for StartServiceEvent {
+    /// Schedule the end of the customer's service.
+    fn execute(&mut self, sim: &mut Simulation<Store, Time>) -> Result {
+        ServiceEvent::schedule(sim)
+    }
+}
+
 /// Handle the completion of a customer's service time at the counter.
 #[derive(Debug)]
 struct ServiceEvent {}
@@ -127,29 +142,26 @@ impl ServiceEvent {
         let distribution = Exp::new(1.0 / 20.0).unwrap();
         let service_length = distribution.sample(&mut sim.state_mut().rng);
         let service_completion_time = *sim.current_time() + service_length;
-        sim.schedule(ServiceEvent {}, service_completion_time)
+        sim.schedule(ServiceEvent {}, service_completion_time)?;
+        Ok(())
     }
 }
 
 impl Event<Store, Time> for ServiceEvent {
-    /// If at least one other customer is in line, decrement the length of the line and schedule a new ServiceEvent.
-    /// Otherwise, mark the server as idle.
+    /// Release the server, which passes it to the next customer in line if there is one.
     fn execute(&mut self, sim: &mut Simulation<Store, Time>) -> Result {
         println!(
             "Completed service for customer. Checking queue at time {:.3}...",
             sim.current_time().0,
         );
 
-        if sim.state().queue_length == 0 {
-            println!("Queue empty! Waiting for next arrival.");
-            sim.state_mut().server_busy = false;
-        } else {
-            sim.state_mut().queue_length -= 1;
+        if sim.release_resource(|store| &mut store.server)?.is_some() {
             println!(
                 "Beginning service for next customer. {} remain in the queue.",
-                sim.state().queue_length,
+                sim.state().server.queue_length(),
             );
-            ServiceEvent::schedule(sim)?;
+        } else {
+            println!("Queue empty! Waiting for next arrival.");
         }
 
         Ok(())
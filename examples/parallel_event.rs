@@ -3,13 +3,14 @@
 //! it dies. Thus, the population will increase by 3 for every member who is able to eat during a generation, and
 //! decrease by 1 for every member who is unable to find food. Sim time here is measured in generations and so fits with
 //! `usize`, and the simulation is rigged to terminate only "between" generations - i.e., immediately following the
-//! `StatusUpdateEvent`, and only if no food remains in the ecosystem.
+//! `StatusUpdateEvent`, and only if no food remains in the ecosystem. The `StatusUpdateEvent` recurs once per
+//! generation through `schedule_periodic()` rather than rescheduling itself.
 //!
 //! This example showcases how the elements of desque's `threadsafe` module support multithreaded execution of a single
 //! simulation by having each "birth" take place on a new thread.
 
 use desque::threadsafe::*;
-use desque::SimState;
+use desque::{Recurrence, SimState};
 use std::sync::atomic;
 use std::thread;
 
@@ -60,8 +61,6 @@ impl OkEvent<Ecosystem, usize> for StatusUpdateEvent {
             sim.state().population.load(atomic::Ordering::Relaxed),
             sim.state().remaining_food
         );
-        sim.schedule_with_delay(Self {}, 1)
-            .expect("positive delay should not result in error");
         sim.state_mut().between_generations = true;
     }
 }
@@ -75,7 +74,7 @@ fn main() {
     let mut sim = Simulation::new(ecosystem, 0);
     sim.schedule(SpawnEvent {}, 1)
         .expect("event should be scheduled with no errors");
-    sim.schedule(StatusUpdateEvent {}, 1)
+    sim.schedule_periodic(|| StatusUpdateEvent {}, 1, 1, Recurrence::Forever)
         .expect("event should be scheduled with no errors");
     sim.run().expect("simulation should complete with no errors");
 }
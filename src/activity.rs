@@ -0,0 +1,29 @@
+/// Identifies an activity started by [`serial::Simulation::start_activity()`].
+///
+/// Presenting this handle to [`serial::Simulation::interrupt()`] cancels the activity's pending completion and
+/// schedules its interrupt handler. As with an [`EventHandle`], a handle is only meaningful to the simulation that
+/// produced it.
+///
+/// [`serial::Simulation::start_activity()`]: crate::serial::Simulation::start_activity
+/// [`serial::Simulation::interrupt()`]: crate::serial::Simulation::interrupt
+/// [`EventHandle`]: crate::EventHandle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActivityHandle {
+    id: usize,
+}
+
+impl ActivityHandle {
+    /// Wrap the sequence number assigned to an activity by the simulation that started it.
+    pub(crate) fn new(id: usize) -> Self {
+        Self { id }
+    }
+}
+
+/// Describes an interrupted activity to the function that builds its interrupt handler.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub struct Interrupted<Time> {
+    /// The time by which the activity fell short of completing, i.e. its completion time less the time of the
+    /// interrupt.
+    pub remaining: Time,
+}
@@ -0,0 +1,298 @@
+use std::alloc::{self, Layout};
+use std::fmt::{Debug, Formatter};
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::ptr::NonNull;
+
+/// Granularity of the arena's size classes, and also the strictest alignment the arena serves.
+const SLOT_ALIGN: usize = 16;
+
+/// Number of size classes, such that the largest slots hold 256 bytes.
+const CLASS_COUNT: usize = 16;
+
+/// Number of slots carved from each chunk of memory requested from the global allocator.
+const SLOTS_PER_CHUNK: usize = 64;
+
+/// A pool of reusable memory for storing events of differing types, so that scheduling an event need not allocate.
+///
+/// Memory is handed out in slots whose sizes are multiples of 16 bytes, up to 256 bytes. Each size class keeps a free
+/// list of the slots released by executed or cancelled events, and only requests more memory from the global allocator,
+/// in chunks of many slots, once its free list runs dry. Once a simulation's queue has grown to the largest size its
+/// run requires, scheduling events therefore performs no further allocations. Values that are too large or too strictly
+/// aligned for any size class are boxed as usual, as are zero-sized values, for which boxing never allocates.
+pub(crate) struct EventArena {
+    /// Free slots of each size class.
+    free: Vec<Vec<NonNull<u8>>>,
+    /// Every chunk requested from the global allocator, to be returned when the arena is dropped.
+    chunks: Vec<(NonNull<u8>, Layout)>,
+}
+
+// SAFETY: the arena only holds pointers to memory that it owns exclusively, rather than references to shared data
+unsafe impl Send for EventArena {}
+
+impl EventArena {
+    /// Construct an empty arena, which does not allocate until the first value is inserted.
+    pub fn new() -> Self {
+        Self {
+            free: (0..CLASS_COUNT).map(|_| Vec::new()).collect(),
+            chunks: Vec::new(),
+        }
+    }
+
+    /// Move a value into a slot of the arena, or into a box if no size class suits it.
+    pub fn insert<T>(&mut self, value: T) -> ArenaBox<T> {
+        match Self::class_of(Layout::new::<T>()) {
+            Some(class) => {
+                let slot = self.take_slot(class).cast::<T>();
+                // SAFETY: the slot is unoccupied, at least as large as a T, and aligned to SLOT_ALIGN, which is a
+                // multiple of the alignment of a T
+                unsafe { slot.as_ptr().write(value) };
+                ArenaBox {
+                    ptr: slot,
+                    class: Some(class),
+                }
+            },
+            None => ArenaBox::from_box(Box::new(value)),
+        }
+    }
+
+    /// Drop a value held by an [`ArenaBox`] and reclaim its memory.
+    ///
+    /// # Safety
+    ///
+    /// The value must have been inserted into this arena, or else boxed through [`ArenaBox::from_box()`].
+    pub unsafe fn release<T>(&mut self, value: ArenaBox<T>)
+    where
+        T: ?Sized,
+    {
+        let slot = value.class.map(|class| (class, value.ptr.cast()));
+        drop(value);
+        if let Some((class, slot)) = slot {
+            self.free[class].push(slot);
+        }
+    }
+
+    /// Get the size class that serves values of the given layout, if any.
+    fn class_of(layout: Layout) -> Option<usize> {
+        if layout.size() == 0 || layout.align() > SLOT_ALIGN {
+            return None;
+        }
+
+        let class = (layout.size() - 1) / SLOT_ALIGN;
+        (class < CLASS_COUNT).then_some(class)
+    }
+
+    /// Take an unoccupied slot of the given size class, requesting a new chunk of slots if none are free.
+    fn take_slot(&mut self, class: usize) -> NonNull<u8> {
+        if let Some(slot) = self.free[class].pop() {
+            return slot;
+        }
+
+        let slot_size = (class + 1) * SLOT_ALIGN;
+        let layout =
+            Layout::from_size_align(slot_size * SLOTS_PER_CHUNK, SLOT_ALIGN).expect("chunk layout should be valid");
+        // SAFETY: the layout has a nonzero size
+        let chunk = NonNull::new(unsafe { alloc::alloc(layout) }).unwrap_or_else(|| alloc::handle_alloc_error(layout));
+        self.chunks.push((chunk, layout));
+
+        // hand out the first slot of the chunk and keep the rest for later
+        self.free[class].extend((1..SLOTS_PER_CHUNK).rev().map(|index| {
+            // SAFETY: every offset lies within the chunk, which is not null
+            unsafe { NonNull::new_unchecked(chunk.as_ptr().add(index * slot_size)) }
+        }));
+        chunk
+    }
+}
+
+impl Default for EventArena {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for EventArena {
+    fn drop(&mut self) {
+        for (chunk, layout) in self.chunks.drain(..) {
+            // SAFETY: each chunk was allocated with its paired layout and has not yet been deallocated
+            unsafe { alloc::dealloc(chunk.as_ptr(), layout) };
+        }
+    }
+}
+
+impl Debug for EventArena {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("EventArena")
+            .field("chunks", &self.chunks.len())
+            .finish()
+    }
+}
+
+/// An owning pointer to a value held in an [`EventArena`], or in a box as a fallback.
+///
+/// Unlike a [`Box`], dropping an [`ArenaBox`] does not make its memory available for reuse, so it should instead be
+/// handed back to [`EventArena::release()`]. One that is dropped anyway, e.g. while unwinding from a panicking event,
+/// still drops its value, but its slot stays unused until the arena itself is dropped. An [`ArenaBox`] must therefore
+/// never outlive the arena holding its value.
+pub(crate) struct ArenaBox<T>
+where
+    T: ?Sized,
+{
+    ptr: NonNull<T>,
+    /// The size class of the slot holding the value, or `None` if the value is boxed.
+    class: Option<usize>,
+}
+
+// SAFETY: an ArenaBox owns its value exclusively, as a Box does
+unsafe impl<T> Send for ArenaBox<T> where T: ?Sized + Send {}
+
+impl<T> ArenaBox<T>
+where
+    T: ?Sized,
+{
+    /// Take ownership of a boxed value, without moving it into an arena.
+    pub fn from_box(boxed: Box<T>) -> Self {
+        Self {
+            // SAFETY: boxes are never null
+            ptr: unsafe { NonNull::new_unchecked(Box::into_raw(boxed)) },
+            class: None,
+        }
+    }
+
+    /// Convert the pointer to the held value, typically to unsize it into a pointer to a trait object.
+    ///
+    /// # Safety
+    ///
+    /// The conversion must return a pointer to the same value, such that dropping the value through the returned
+    /// pointer is equivalent to dropping it through the original.
+    pub unsafe fn map_ptr<U, F>(self, convert: F) -> ArenaBox<U>
+    where
+        U: ?Sized,
+        F: FnOnce(NonNull<T>) -> NonNull<U>,
+    {
+        let value = ManuallyDrop::new(self);
+        ArenaBox {
+            ptr: convert(value.ptr),
+            class: value.class,
+        }
+    }
+}
+
+impl<T> Drop for ArenaBox<T>
+where
+    T: ?Sized,
+{
+    fn drop(&mut self) {
+        match self.class {
+            // SAFETY: the pointer refers to a live value owned exclusively by this ArenaBox, in a slot of an arena
+            // that outlives it
+            Some(_) => unsafe { std::ptr::drop_in_place(self.ptr.as_ptr()) },
+            // SAFETY: the pointer was produced by a box, which this ArenaBox owns exclusively
+            None => drop(unsafe { Box::from_raw(self.ptr.as_ptr()) }),
+        }
+    }
+}
+
+impl<T> Deref for ArenaBox<T>
+where
+    T: ?Sized,
+{
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: the pointer refers to a live value owned by this ArenaBox
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for ArenaBox<T>
+where
+    T: ?Sized,
+{
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: the pointer refers to a live value owned exclusively by this ArenaBox
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Debug for ArenaBox<T>
+where
+    T: ?Sized + Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        Debug::fmt(&**self, f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn released_slots_are_reused() {
+        let mut arena = EventArena::new();
+        let first = arena.insert(1_u64);
+        let first_address = first.ptr.cast::<u8>();
+        unsafe { arena.release(first) };
+
+        let second = arena.insert(2_u64);
+        assert_eq!(first_address, second.ptr.cast::<u8>(), "released slot was not reused");
+        assert_eq!(2, *second, "slot did not hold new value");
+        assert_eq!(1, arena.chunks.len(), "arena should not request more memory");
+        unsafe { arena.release(second) };
+    }
+
+    #[test]
+    fn released_values_are_dropped() {
+        let mut arena = EventArena::new();
+        let counter = Rc::new(());
+        let small = arena.insert(counter.clone());
+        let large = arena.insert((counter.clone(), [0_u8; 512]));
+        let boxed = ArenaBox::from_box(Box::new(counter.clone()));
+        assert_eq!(4, Rc::strong_count(&counter), "values should hold references");
+        assert_eq!(None, large.class, "oversized values should be boxed");
+
+        unsafe {
+            arena.release(small);
+            arena.release(large);
+            arena.release(boxed);
+        }
+        assert_eq!(
+            1,
+            Rc::strong_count(&counter),
+            "released values should have been dropped"
+        );
+    }
+
+    #[test]
+    fn unreleased_values_are_still_dropped() {
+        let mut arena = EventArena::new();
+        let counter = Rc::new(());
+        let small = arena.insert(counter.clone());
+        let boxed = ArenaBox::from_box(Box::new(counter.clone()));
+
+        drop(small);
+        drop(boxed);
+        assert_eq!(1, Rc::strong_count(&counter), "dropped values should have been dropped");
+        assert_eq!(
+            SLOTS_PER_CHUNK - 1,
+            arena.free[0].len(),
+            "dropped slot should not be made available for reuse"
+        );
+    }
+
+    #[test]
+    fn trait_objects_can_be_stored() {
+        let mut arena = EventArena::new();
+        let value = arena.insert(String::from("event"));
+        let mut value: ArenaBox<dyn Debug> = unsafe { value.map_ptr(|ptr| -> NonNull<dyn Debug> { ptr }) };
+        assert_eq!(
+            "\"event\"",
+            format!("{:?}", value),
+            "trait object did not refer to value"
+        );
+
+        let _: &mut dyn Debug = &mut *value;
+        unsafe { arena.release(value) };
+    }
+}
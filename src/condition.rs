@@ -0,0 +1,53 @@
+use crate::process::ProcessHandle;
+use std::fmt::{Debug, Formatter};
+
+/// Identifies a condition registered by one of the `schedule_when()` methods.
+///
+/// Presenting this handle to [`serial::Simulation::cancel_condition()`] or
+/// [`threadsafe::Simulation::cancel_condition()`] withdraws the condition before it holds, so that its event is never
+/// scheduled. As with an [`EventHandle`], a handle is only meaningful to the simulation that produced it.
+///
+/// [`serial::Simulation::cancel_condition()`]: crate::serial::Simulation::cancel_condition
+/// [`threadsafe::Simulation::cancel_condition()`]: crate::threadsafe::Simulation::cancel_condition
+/// [`EventHandle`]: crate::EventHandle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConditionHandle {
+    id: usize,
+}
+
+impl ConditionHandle {
+    /// Wrap the sequence number assigned to a condition by the simulation that registered it.
+    pub(crate) fn new(id: usize) -> Self {
+        Self { id }
+    }
+}
+
+/// Identifies what a [`Waiter`] will resume.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) enum WaiterId {
+    /// An event registered to be scheduled once a condition holds.
+    Condition(ConditionHandle),
+    /// A suspended process.
+    Process(ProcessHandle),
+}
+
+/// Something to resume once a predicate on the simulation's state holds or, lacking a predicate, once it is woken
+/// explicitly.
+///
+/// Simulations keep their waiters in the order they were registered and check each predicate after every event
+/// executes.
+pub(crate) struct Waiter<Predicate, Resumption> {
+    pub id: WaiterId,
+    pub predicate: Option<Predicate>,
+    /// Schedules whatever is waiting at the current time.
+    pub resume: Resumption,
+}
+
+impl<Predicate, Resumption> Debug for Waiter<Predicate, Resumption> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Waiter")
+            .field("id", &self.id)
+            .field("passive", &self.predicate.is_none())
+            .finish()
+    }
+}
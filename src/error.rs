@@ -5,6 +5,10 @@
 /// time. This error likely corresponds to a logical bug on the client side, e.g. forgetting to add an offset to the
 /// current time when scheduling a new event.
 ///
+/// The [`EventNotFound`] variant also originates from the safe interface of either simulation, indicating that an
+/// [`EventHandle`] presented to a method such as [`serial::Simulation::reschedule()`] does not refer to an event that
+/// is still pending. The event may have already executed or been cancelled.
+///
 /// The [`BadExecution`] variant originates from client code, providing a wrapper that can pass through
 /// [`serial::Simulation::run()`] or [`threadsafe::Simulation::run()`] in a type-safe manner. Invoking [`source()`] on
 /// this variant will acquire a shared reference to the wrapped [`std::error::Error`] for handling on the client side.
@@ -13,13 +17,20 @@
 /// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
 /// [`serial::Simulation::run()`]: crate::serial::Simulation::run
 /// [`threadsafe::Simulation::run()`]: crate::threadsafe::Simulation::run
+/// [`serial::Simulation::reschedule()`]: crate::serial::Simulation::reschedule
+/// [`EventHandle`]: crate::EventHandle
 /// [`BackInTime`]: Error::BackInTime
+/// [`EventNotFound`]: Error::EventNotFound
 /// [`BadExecution`]: Error::BadExecution
 /// [`source()`]: Error#method.source
 #[derive(Debug)]
 pub enum Error {
     /// The event queue rejected an event that would have been scheduled for a time that has already passed.
     BackInTime,
+    /// The event queue holds no pending event matching the provided [`EventHandle`].
+    ///
+    /// [`EventHandle`]: crate::EventHandle
+    EventNotFound,
     /// A client-generated error was encountered while executing an event. Call [`source()`] or unpack this value to
     /// handle it directly.
     ///
@@ -31,6 +42,7 @@ impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (Error::BackInTime, Error::BackInTime) => true,
+            (Error::EventNotFound, Error::EventNotFound) => true,
             (Error::BadExecution(e1), Error::BadExecution(e2)) => {
                 let e1: *const dyn std::error::Error = e1.as_ref();
                 let e2: *const dyn std::error::Error = e2.as_ref();
@@ -48,6 +60,7 @@ impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let descriptor = match self {
             Self::BackInTime => "event execution time is less than current simulation time".into(),
+            Self::EventNotFound => "no pending event matches the provided handle".into(),
             Self::BadExecution(e) => format!("error while executing event: {}", e),
         };
         write!(f, "{}", descriptor)
@@ -57,7 +70,7 @@ impl std::fmt::Display for Error {
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::BackInTime => None,
+            Self::BackInTime | Self::EventNotFound => None,
             Self::BadExecution(e) => Some(e.as_ref()),
         }
     }
@@ -65,5 +78,10 @@ impl std::error::Error for Error {
 
 /// Simplifies the signatures of various functions in desque.
 ///
+/// The success type defaults to `()`, which covers fallible operations such as [`Event::execute()`] that have no value
+/// to report. Scheduling methods instead succeed with an [`EventHandle`] that refers to the newly scheduled event.
+///
+/// [`Event::execute()`]: crate::serial::Event::execute
 /// [`desque::Error`]: Error
-pub type Result = std::result::Result<(), Error>;
+/// [`EventHandle`]: crate::EventHandle
+pub type Result<T = ()> = std::result::Result<T, Error>;
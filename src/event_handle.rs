@@ -0,0 +1,33 @@
+/// Identifies a single event that has been placed on a simulation's event queue.
+///
+/// Every scheduling method on [`serial::Simulation`], [`threadsafe::Simulation`], and [`inline::Simulation`] hands back
+/// one of these handles, which wraps the insertion sequence that the event queue assigned to the newly scheduled event.
+/// Holding on to a handle allows client code to refer to that event later, for example to [`cancel()`] it before it has
+/// a chance to execute.
+///
+/// Handles are cheap to copy and carry no reference to the simulation that produced them. A handle is only meaningful
+/// to the simulation whose scheduling method returned it; presenting it to any other simulation will refer to whatever
+/// event, if any, happens to share its insertion sequence there.
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+/// [`inline::Simulation`]: crate::inline::Simulation
+/// [`cancel()`]: crate::serial::Simulation::cancel
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EventHandle {
+    insertion_sequence: usize,
+}
+
+impl EventHandle {
+    /// Wrap the insertion sequence assigned to an event by the event queue.
+    pub(crate) fn new(insertion_sequence: usize) -> Self {
+        Self { insertion_sequence }
+    }
+
+    /// Get the insertion sequence of the event this handle refers to. Insertion sequences start at zero and increase by
+    /// one for each event scheduled on a simulation, and are used to break ties between events that share an execution
+    /// time.
+    pub fn insertion_sequence(&self) -> usize {
+        self.insertion_sequence
+    }
+}
@@ -8,8 +8,9 @@ use std::fmt::Debug;
 /// Your implementation of this trait should use the [`Ord`] trait to account for not only the overall sequencing of
 /// events, but also any tie breaking that may be necessary in your use case. Note that events will be executed in
 /// ascending order of execution time, i.e. if `A.cmp(&B) == std::cmp::Ordering::Less` then event A will execute before
-/// event B. Ties that you don't specify how to break will be resolved by the order in which events are enqueued, which
-/// should help provide some stability in a [`serial::Simulation`]. In a [`threadsafe::Simulation`], however, this
+/// event B. Ties that you don't specify how to break will be resolved by the priority each event was scheduled with,
+/// and then by the order in which events are enqueued, which should help provide some stability in a
+/// [`serial::Simulation`]. In a [`threadsafe::Simulation`], however, this
 /// tiebreaking scheme may be subject to benign race conditions, depending on how your use case takes advantage of
 /// parallelization.
 ///
@@ -55,6 +56,56 @@ impl<Float> SimTime for ordered_float::OrderedFloat<Float> where Float: ordered_
 #[cfg(feature = "ordered-float")]
 impl<Float> SimTime for ordered_float::NotNan<Float> where Float: ordered_float::FloatCore + Debug {}
 
+/// A [`SimTime`] that can be expressed as a number of time units.
+///
+/// desque itself never needs to treat the simulation clock as a number, but some of its optional utilities do, such as
+/// the [`ChromeTraceExporter`] which must place events on a numeric timeline. This trait provides the conversion those
+/// utilities rely on.
+///
+/// Implementations are provided for integral builtin types, which may lose precision for magnitudes beyond 2^53, and
+/// for the [`OrderedFloat`] and [`NotNan`] structs when the `ordered-float` feature is enabled. Implement this trait on
+/// your own clock type to use it with these utilities.
+///
+/// [`ChromeTraceExporter`]: crate::trace::ChromeTraceExporter
+/// [`OrderedFloat`]: https://docs.rs/ordered-float/4/ordered_float/struct.OrderedFloat.html
+/// [`NotNan`]: https://docs.rs/ordered-float/4/ordered_float/struct.NotNan.html
+pub trait NumericTime: SimTime {
+    /// Express this time as a number of time units.
+    fn to_f64(&self) -> f64;
+}
+
+macro_rules! impl_numeric_time {
+    ($($int:ty),*) => {$(
+        impl NumericTime for $int {
+            fn to_f64(&self) -> f64 {
+                *self as f64
+            }
+        }
+    )*};
+}
+
+impl_numeric_time!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "ordered-float")]
+impl<Float> NumericTime for ordered_float::OrderedFloat<Float>
+where
+    Float: ordered_float::FloatCore + Debug + Into<f64>,
+{
+    fn to_f64(&self) -> f64 {
+        self.0.into()
+    }
+}
+
+#[cfg(feature = "ordered-float")]
+impl<Float> NumericTime for ordered_float::NotNan<Float>
+where
+    Float: ordered_float::FloatCore + Debug + Into<f64>,
+{
+    fn to_f64(&self) -> f64 {
+        self.into_inner().into()
+    }
+}
+
 /// The generic type used for a simulation's overall state.
 ///
 /// This type may include to-date summary statistics, collections of simulated entities, terrain maps, historical
@@ -0,0 +1,61 @@
+//! For building and running a simulation whose events are all of one type, stored without boxing.
+//!
+//! The [`serial`] and [`threadsafe`] modules accept any mix of event types by storing each scheduled event as a trait
+//! object, which costs a virtual call per execution and a heap allocation for any event too large for their queues to
+//! store otherwise. This module instead requires every event to share one type chosen by client code, typically an enum
+//! with one variant per kind of event, and stores events inline on the simulation's queue. Scheduling an event then
+//! performs no allocations once the queue has grown to its working size, and executing one calls [`Event::execute()`]
+//! directly, which may help models whose run time is dominated by very large numbers of cheap events.
+//!
+//! Otherwise, this module mirrors [`serial`]: simulations built with it expect that only one thread will directly
+//! interact with the event queue and overall state, and provide the same methods for scheduling, cancelling, and
+//! executing events.
+//!
+//! ```
+//! use desque::inline::{OkEvent, Simulation};
+//! use desque::SimState;
+//!
+//! #[derive(Debug, Default)]
+//! struct Store {
+//!     customers_served: usize,
+//! }
+//!
+//! impl SimState<u64> for Store {}
+//!
+//! #[derive(Debug)]
+//! enum StoreEvent {
+//!     Arrival,
+//!     Departure,
+//! }
+//!
+//! impl OkEvent<Store, u64> for StoreEvent {
+//!     fn execute(&mut self, sim: &mut Simulation<Store, u64, Self>) {
+//!         match self {
+//!             StoreEvent::Arrival => {
+//!                 sim.schedule_with_delay(StoreEvent::Departure, 3)
+//!                     .expect("delay should not be negative");
+//!             },
+//!             StoreEvent::Departure => sim.state_mut().customers_served += 1,
+//!         }
+//!     }
+//! }
+//!
+//! let mut sim = Simulation::new(Store::default(), 0);
+//! sim.schedule(StoreEvent::Arrival, 1)?;
+//! sim.schedule(StoreEvent::Arrival, 2)?;
+//! sim.run()?;
+//! assert_eq!(2, sim.state().customers_served);
+//! assert_eq!(5, *sim.current_time());
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! [`serial`]: crate::serial
+//! [`threadsafe`]: crate::threadsafe
+//! [`Event::execute()`]: Event::execute
+
+mod events;
+mod simulation;
+
+pub use events::event_holder::PendingEvent;
+pub use events::event_traits::{Event, OkEvent};
+pub use simulation::Simulation;
@@ -0,0 +1,251 @@
+pub(super) mod event_holder;
+pub(super) mod event_traits;
+
+use crate::queue::{EventKey, EventQueueBackend};
+use crate::{EventHandle, SimTime};
+use event_holder::PendingEvent;
+
+use std::collections::HashMap;
+use std::fmt::{Debug, Formatter};
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// Hashes the insertion sequences that key the stored events.
+///
+/// Insertion sequences are unique and mostly consecutive, so multiplying by a large odd constant spreads them across
+/// the table well enough at a fraction of the cost of the default hasher, which guards against adversarial keys that
+/// cannot occur here.
+#[derive(Default)]
+struct SequenceHasher {
+    hash: u64,
+}
+
+impl SequenceHasher {
+    /// The golden ratio scaled to 64 bits, per Knuth's multiplicative hashing.
+    const MULTIPLIER: u64 = 0x9E37_79B9_7F4A_7C15;
+}
+
+impl Hasher for SequenceHasher {
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.hash = (self.hash ^ u64::from(*byte)).wrapping_mul(Self::MULTIPLIER);
+        }
+    }
+
+    fn write_usize(&mut self, sequence: usize) {
+        self.hash = (sequence as u64).wrapping_mul(Self::MULTIPLIER);
+    }
+}
+
+/// Helper struct to set a Debug impl that hides everything about the backend, and that pairs each key held by the
+/// backend with the event it refers to.
+///
+/// The backend only holds the keys necessary to order events, while the events themselves live in a map keyed by
+/// insertion sequence. The map stores events by value, and so reuses its memory as events come and go rather than
+/// allocating for each one. Cancelling an event removes it from the map and leaves its key behind in the backend; such
+/// stale keys are discarded as they reach the front of the backend, and so the front of the backend always refers to a
+/// pending event.
+struct QueueContents<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    keys: Backend,
+    events: HashMap<usize, StoredEvent<EventType>, BuildHasherDefault<SequenceHasher>>,
+    _time: std::marker::PhantomData<fn() -> Time>,
+}
+
+impl<Time, EventType, Backend> QueueContents<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Pop keys off the front of the backend until finding one that refers to an event which is still pending.
+    fn discard_cancelled(&mut self) {
+        while let Some(key) = self.keys.peek() {
+            if self.events.contains_key(&key.insertion_sequence()) {
+                return;
+            }
+            self.keys.pop();
+        }
+    }
+
+    /// Pair each key held by the backend with the event it refers to, skipping keys of cancelled events.
+    fn pending(&self) -> impl Iterator<Item = PendingEvent<'_, Time, EventType>> {
+        self.keys.iter().filter_map(move |key| {
+            self.events
+                .get(&key.insertion_sequence())
+                .map(|stored| PendingEvent::new(key, &stored.event))
+        })
+    }
+}
+
+impl<Time, EventType, Backend> Debug for QueueContents<Time, EventType, Backend>
+where
+    Time: SimTime,
+    EventType: Debug,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_list().entries(self.pending()).finish()
+    }
+}
+
+/// An event waiting on the queue, alongside the priority it was scheduled with so that rescheduling can preserve it.
+struct StoredEvent<EventType> {
+    event: EventType,
+    priority: i32,
+}
+
+/// Priority queue of scheduled events, all of which share one type.
+///
+/// Events will execute in ascending order of execution time, with ties broken first by descending priority and then by
+/// the order in which they were pushed onto the queue. These tiebreakers are in addition to any built-in to the
+/// implementation of [`SimTime`] used for the clock as a way to stabilize the observed order of execution.
+///
+/// This struct is generic over the type used to represent clock time for the sake of tracking the current time, as well
+/// as over the type of the events it stores and the [`EventQueueBackend`] that orders them.
+///
+/// An [`EventQueue`] provides several different methods for scheduling new events, but does not publicly support
+/// popping; popping events from the queue only occurs during [`Simulation::run()`]. Scheduled events may be cancelled
+/// by presenting the [`EventHandle`] returned when scheduling them.
+///
+/// [`Simulation::run()`]: crate::inline::Simulation::run
+pub(super) struct EventQueue<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    events: QueueContents<Time, EventType, Backend>,
+    total_events_scheduled: usize,
+}
+
+impl<Time, EventType, Backend> EventQueue<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Initialize an empty queue that orders its events with the provided backend, which should also be empty.
+    pub fn new(backend: Backend) -> Self {
+        Self {
+            events: QueueContents {
+                keys: backend,
+                events: HashMap::default(),
+                _time: std::marker::PhantomData,
+            },
+            total_events_scheduled: 0,
+        }
+    }
+
+    /// Place an event on the queue. By the time we're here, assume all error checking is complete.
+    pub fn schedule_event(&mut self, event: EventType, time: Time, priority: i32) -> EventHandle {
+        let count = self.increment_event_count();
+        self.events.keys.push(EventKey::new(time, priority, count));
+        self.events.events.insert(count, StoredEvent { event, priority });
+        EventHandle::new(count)
+    }
+
+    /// Remove the event referred to by the handle from the queue, if it is still pending. Returns whether there was
+    /// such an event to remove.
+    pub fn cancel_event(&mut self, handle: EventHandle) -> bool {
+        let removed = self.events.events.remove(&handle.insertion_sequence()).is_some();
+        if removed {
+            self.events.discard_cancelled();
+        }
+        removed
+    }
+
+    /// Move the event referred to by the handle to a new execution time, if it is still pending. The event receives a
+    /// new insertion sequence as though it were freshly scheduled, and a handle carrying that sequence is returned. The
+    /// event's priority is unchanged.
+    pub fn reschedule_event(&mut self, handle: EventHandle, time: Time) -> Option<EventHandle> {
+        let stored = self.events.events.remove(&handle.insertion_sequence())?;
+        let new_handle = self.schedule_event(stored.event, time, stored.priority);
+        self.events.discard_cancelled();
+        Some(new_handle)
+    }
+
+    /// Helper function to make sure incrementing the internal count of added events occurs the same way across all
+    /// scheduling methods.
+    fn increment_event_count(&mut self) -> usize {
+        let count = self.total_events_scheduled;
+        self.total_events_scheduled += 1;
+        count
+    }
+
+    /// Crate-internal function to pop an event from the queue, alongside its execution time and the handle it was
+    /// scheduled with.
+    pub fn next(&mut self) -> Option<(EventType, Time, EventHandle)> {
+        let key = self.events.keys.pop()?;
+        let event = self
+            .events
+            .events
+            .remove(&key.insertion_sequence())
+            .expect("front of the backend should always refer to a pending event")
+            .event;
+        self.events.discard_cancelled();
+        let handle = key.handle();
+        Some((event, key.into_execution_time(), handle))
+    }
+
+    /// Get the execution time of the event that will be popped next, if any, without modifying the queue.
+    pub fn peek_time(&self) -> Option<&Time> {
+        self.events.keys.peek().map(EventKey::execution_time)
+    }
+
+    /// Get the number of events that are currently waiting on the queue, excluding any that have been cancelled.
+    pub fn pending_count(&self) -> usize {
+        self.events.events.len()
+    }
+
+    /// Collect views of every event currently waiting on the queue, sorted in the order they will execute.
+    pub fn pending_events(&self) -> Vec<PendingEvent<'_, Time, EventType>> {
+        let mut pending: Vec<_> = self.events.pending().collect();
+        pending.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+        pending
+    }
+}
+
+impl<Time, EventType, Backend> Default for EventQueue<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time> + Default,
+{
+    fn default() -> Self {
+        Self::new(Backend::default())
+    }
+}
+
+impl<Time, EventType, Backend> Debug for EventQueue<Time, EventType, Backend>
+where
+    Time: SimTime,
+    EventType: Debug,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Formats the value with the given formatter. Scheduled events will be written in an arbitrary order, and the
+    /// `total_events_scheduled` is a total over the entire simulation run as opposed to the number currently on the
+    /// event queue.
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("EventQueue")
+            .field("events", &self.events)
+            .field("total_events_scheduled", &self.total_events_scheduled)
+            .finish()
+    }
+}
+
+impl<Time, EventType, Backend> std::fmt::Display for EventQueue<Time, EventType, Backend>
+where
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        write!(
+            formatter,
+            "EventQueue with {} scheduled events",
+            self.events.events.len(),
+        )
+    }
+}
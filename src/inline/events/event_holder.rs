@@ -0,0 +1,70 @@
+use crate::queue::EventKey;
+use crate::{EventHandle, SimTime};
+use std::fmt::{Debug, Formatter};
+
+/// A view of an event that is waiting on a [`Simulation`]'s queue.
+///
+/// Produced by [`Simulation::pending_events()`] to support inspecting the queue's contents without executing any
+/// events, e.g. to assert on which events a test scenario has scheduled. Each view provides shared access to the event
+/// itself alongside its execution time, its priority, and the [`EventHandle`] it was scheduled with. The
+/// implementation of [`Debug`] prints all four.
+///
+/// [`Simulation`]: crate::inline::Simulation
+/// [`Simulation::pending_events()`]: crate::inline::Simulation::pending_events
+pub struct PendingEvent<'a, Time, EventType>
+where
+    Time: SimTime,
+{
+    key: &'a EventKey<Time>,
+    event: &'a EventType,
+}
+
+impl<'a, Time, EventType> PendingEvent<'a, Time, EventType>
+where
+    Time: SimTime,
+{
+    /// Pair the key used to order an event on the queue with the event itself.
+    pub(super) fn new(key: &'a EventKey<Time>, event: &'a EventType) -> Self {
+        Self { key, event }
+    }
+
+    /// Get the key used to order the event on the queue.
+    pub(super) fn key(&self) -> &'a EventKey<Time> {
+        self.key
+    }
+
+    /// Get a shared reference to the time at which the event is scheduled to execute.
+    pub fn execution_time(&self) -> &'a Time {
+        self.key.execution_time()
+    }
+
+    /// Get the priority the event was scheduled with, which breaks ties between events scheduled for the same time.
+    pub fn priority(&self) -> i32 {
+        self.key.priority()
+    }
+
+    /// Get the handle that was returned when the event was scheduled.
+    pub fn handle(&self) -> EventHandle {
+        self.key.handle()
+    }
+
+    /// Get a shared reference to the event itself.
+    pub fn event(&self) -> &'a EventType {
+        self.event
+    }
+}
+
+impl<Time, EventType> Debug for PendingEvent<'_, Time, EventType>
+where
+    Time: SimTime,
+    EventType: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ScheduledEvent")
+            .field("event", &self.event)
+            .field("execution_time", self.key.execution_time())
+            .field("priority", &self.key.priority())
+            .field("insertion_sequence", &self.key.insertion_sequence())
+            .finish()
+    }
+}
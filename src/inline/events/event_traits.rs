@@ -0,0 +1,122 @@
+use crate::inline::Simulation;
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::{SimState, SimTime};
+use std::fmt::Debug;
+
+/// The behaviors and state changes that occur within a simulation, gathered into one type.
+///
+/// This trait has one required method that describes what happens when the implementing type executes. Unlike
+/// [`serial::Event`], which is implemented once per kind of event, this trait is implemented by the single type that
+/// every event in an [`inline::Simulation`] shares. That type is usually an enum with one variant per kind of event,
+/// whose implementation of [`execute()`] matches on the variant. The simulation stores these values directly on its
+/// queue rather than boxing them.
+///
+/// This trait is generic over the types used to represent simulation state and clock time to enable your
+/// implementations of each trait to work together within this framework. The final type parameter names the
+/// [`EventQueueBackend`] used by the simulation that executes the event. It defaults to [`BinaryHeapQueue`], and so
+/// only needs to be named by events for simulations constructed with another backend.
+///
+/// Requiring implementors to be [`Debug`] enables printing the full contents of a [`Simulation`]'s internal event queue
+/// when necessary.
+///
+/// [`serial::Event`]: crate::serial::Event
+/// [`inline::Simulation`]: Simulation
+/// [`execute()`]: Event::execute
+pub trait Event<State, Time, Backend = BinaryHeapQueue<Time>>: Debug + Sized
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Update the simulation according to the specific kind of event. The simulation will invoke this method during
+    /// [`Simulation::run()`] for each scheduled event in sequence. Exclusive access is provided to the simulation while
+    /// executing an event, allowing for both mutation of the simulation's state and scheduling of new events.
+    ///
+    /// This trait expects implementations of [`execute()`] to be fallible, and [`Simulation::run()`] will bubble any
+    /// errors back up to the client as an [`Error::BadExecution`]. Successful branches, as well as infallible
+    /// implementations, should simply return `Ok(())` to indicate to [`Simulation::run()`] that it may continue popping
+    /// events from the queue.
+    ///
+    /// Note that the simulation's clock time will update before invoking this method.
+    ///
+    /// # Errors
+    ///
+    /// This method signature allows for the possibility of encountering error conditions at runtime. Of particular note
+    /// here, the [`Error::BadExecution`] variant wraps a [`dyn std::error::Error`] and so enables client
+    /// implementations of this method to effectively shut down a simulation when encountering any problems that cannot
+    /// be handled at runtime without causing a panic or otherwise losing information about the error somewhere deep in
+    /// the event queue.
+    ///
+    /// See [`Error`] for more details on the variants of this error enum.
+    ///
+    /// [`Simulation::run()`]: Simulation::run
+    /// [`execute()`]: Event::execute
+    /// [`dyn std::error::Error`]: std::error::Error
+    /// [`Error`]: crate::Error
+    /// [`Error::BadExecution`]: crate::Error::BadExecution
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Self, Backend>) -> crate::Result;
+
+    /// Get a name for this kind of event, for use when tracing or otherwise reporting on executed events.
+    ///
+    /// The default implementation returns the name of the implementing type as reported by [`std::any::type_name()`].
+    /// As every event in the simulation shares that type, override this method to distinguish the variants of your
+    /// event enum in traces.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// An [`Event`] that is guaranteed not to return an [`Error`] on execution.
+///
+/// The [`execute()`] method on this trait differs from [`Event::execute()`] only by omitting the return type. An
+/// implementation of [`Event`] is provided for all implementors of this trait which simply invokes
+/// [`OkEvent::execute()`] then returns `Ok(())`.
+///
+/// As with the requirement on [`Event`], implementing [`Debug`] enables a [`Simulation`] to print all of its contents
+/// when client code deems it necessary.
+///
+/// [`execute()`]: OkEvent::execute
+/// [`Event::execute()`]: Event::execute
+/// [`OkEvent::execute()`]: OkEvent::execute
+/// [`Error`]: crate::Error
+pub trait OkEvent<State, Time, Backend = BinaryHeapQueue<Time>>: Debug + Sized
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Update the simulation according to the specific kind of event. The simulation will invoke this method during
+    /// [`Simulation::run()`] for each scheduled event in sequence. Exclusive access is provided to the simulation
+    /// while executing an event, allowing for both mutation of the simulation's state and scheduling of new events.
+    ///
+    /// Note that the simulation's clock time will update before invoking this method.
+    ///
+    /// [`Simulation::run()`]: Simulation::run
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Self, Backend>);
+
+    /// Get a name for this kind of event, for use when tracing or otherwise reporting on executed events.
+    ///
+    /// The default implementation returns the name of the implementing type as reported by [`std::any::type_name()`].
+    /// As every event in the simulation shares that type, override this method to distinguish the variants of your
+    /// event enum in traces.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+impl<State, Time, Backend, OkEventType> Event<State, Time, Backend> for OkEventType
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+    OkEventType: OkEvent<State, Time, Backend>,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Self, Backend>) -> crate::Result {
+        OkEvent::execute(self, simulation);
+        Ok(())
+    }
+
+    fn name(&self) -> &str {
+        OkEvent::name(self)
+    }
+}
@@ -0,0 +1,784 @@
+use super::events::EventQueue;
+use super::{Event, PendingEvent};
+use crate::observer::{AnyObserver, ObservedEvent, ObserverList};
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::{EventHandle, Observer, ObserverHandle, SimState, SimTime, StepOutcome};
+
+use std::fmt::{Debug, Formatter};
+use std::ops::Add;
+
+/// Contains the event queue and other state belonging to a simulation whose events all share one type.
+///
+/// This struct mirrors [`serial::Simulation`], differing in that every event must be of the type named by its
+/// `EventType` parameter, which implements [`Event`]. Events are stored by value on the queue rather than boxed, so
+/// scheduling an event does not allocate once the queue has grown to its working size.
+///
+/// A [`Simulation`] owns both its client-provided state and its internal event queue, alongside the authoritative
+/// current simulation time. The simulation provides both shared and mutable access to the client-provided state, shared
+/// access to the current time, and a variety of methods to schedule new events on the internal queue. Each scheduling
+/// method hands back an [`EventHandle`] which may be passed to [`cancel()`] to retract the event before it executes.
+/// Events scheduled for the same time execute in the order they were scheduled, unless given distinct priorities
+/// through [`schedule_with_priority()`].
+///
+/// The expected workflow for a Simulation is:
+///
+/// 1. Initialize a struct that implements [`SimState`].
+/// 2. Pass this struct and the start time to [`new()`].
+/// 3. Schedule at least one initial event.
+/// 4. Call [`run()`]. Handle any error it might return.
+/// 5. Use the [`state()`] or [`state_mut()`] accessors to finish processing the results.
+///
+/// > Note: in the implementation of [`Debug`], scheduled events will be printed in an arbitrary order and the number of
+/// > `total_events_scheduled` is over the entirety of the simulation run, as opposed to the number currently in queue.
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`new()`]: Simulation::new
+/// [`cancel()`]: Simulation::cancel
+/// [`schedule_with_priority()`]: Simulation::schedule_with_priority
+/// [`run()`]: Simulation::run
+/// [`state()`]: Simulation::state
+/// [`state_mut()`]: Simulation::state_mut
+#[derive(Debug)]
+pub struct Simulation<State, Time, EventType, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time>,
+{
+    /// A priority queue of events that have been scheduled to execute, ordered ascending by execution time.
+    event_queue: EventQueue<Time, EventType, Backend>,
+    /// The current shared state of the Simulation. Exclusive access will be granted to each event that executes.
+    state: State,
+    /// The current simulation time.
+    current_time: Time,
+    /// Observers to notify before and after each event executes.
+    observers: ObserverList<Time, dyn AnyObserver<Time>>,
+}
+
+impl<State, Time, EventType> Simulation<State, Time, EventType>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    EventType: Event<State, Time>,
+{
+    /// Initialize a Simulation instance with the provided starting state and an empty event queue, with clock set to
+    /// the provided starting time. Events are ordered by the default [`BinaryHeapQueue`] backend; use
+    /// [`with_backend()`] to select another.
+    ///
+    /// [`with_backend()`]: Simulation::with_backend
+    pub fn new(initial_state: State, start_time: Time) -> Self {
+        Self::with_backend(initial_state, start_time, BinaryHeapQueue::new())
+    }
+}
+
+impl<State, Time, EventType, Backend> Simulation<State, Time, EventType, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Initialize a Simulation instance with the provided starting state and an empty event queue ordered by the
+    /// provided backend, with clock set to the provided starting time. The backend should not hold any keys.
+    ///
+    /// See [`queue`] for the available backends and guidance on choosing between them.
+    ///
+    /// [`queue`]: crate::queue
+    pub fn with_backend(initial_state: State, start_time: Time, backend: Backend) -> Self {
+        Self {
+            event_queue: EventQueue::new(backend),
+            state: initial_state,
+            current_time: start_time,
+            observers: ObserverList::new(),
+        }
+    }
+
+    /// Execute events from the priority queue, one at a time, in ascending order by execution time.
+    ///
+    /// Follows this loop:
+    ///
+    /// 1. Does [`state.is_complete()`] return true? If so, return `Ok(())`.
+    /// 2. Attempt to pop the next event from the queue. If there isn't one, return `Ok(())`.
+    /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
+    ///    otherwise return to step 1.
+    ///
+    /// Any [`Observer`]s attached through [`add_observer()`] are notified immediately before and after each event
+    /// executes.
+    ///
+    /// # Errors
+    ///
+    /// Errors may occur during execution of events, and if encountered here they will be passed back to the caller,
+    /// unchanged. The two variants directly supported are:
+    ///
+    /// 1. [`Error::BackInTime`] means that client code attempted to schedule an event at some point in the simulation's
+    ///    past. This error is a likely indicator that client code contains a logical bug, as most discrete-event
+    ///    simulations would never rewind their clocks.
+    /// 2. [`Error::BadExecution`] wraps a client-generated error in a way that is type-safe to feed back through this
+    ///    method. To handle the underlying error, either unpack the [`BadExecution`] or call its [`source()`] method.
+    ///
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`event.execute()`]: Event::execute
+    /// [`add_observer()`]: Simulation::add_observer
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::BadExecution`]: crate::Error::BadExecution
+    /// [`BadExecution`]: crate::Error::BadExecution
+    /// [`source()`]: crate::Error#method.source
+    pub fn run(&mut self) -> crate::Result {
+        while let StepOutcome::Executed(_) = self.step()? {}
+        Ok(())
+    }
+
+    /// Execute at most `event_count` events from the priority queue, one at a time, in ascending order by execution
+    /// time. Returns the number of events that were executed, which will be less than `event_count` only if the
+    /// simulation reached completion or ran out of events first.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once `event_count`
+    /// events have been executed. Each call picks up where the previous call left off, making this method suitable for
+    /// e.g. a warm-up period of fixed length.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing events are passed back to the caller, unchanged, as with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    pub fn run_for(&mut self, event_count: usize) -> crate::Result<usize> {
+        let mut events_executed = 0;
+        while events_executed < event_count {
+            match self.step()? {
+                StepOutcome::Executed(_) => events_executed += 1,
+                StepOutcome::Complete | StepOutcome::QueueEmpty => break,
+            }
+        }
+        Ok(events_executed)
+    }
+
+    /// Execute exactly one event from the priority queue, if possible, and report what happened.
+    ///
+    /// Performs a single iteration of the loop described on [`run()`]:
+    ///
+    /// 1. Does [`state.is_complete()`] return true? If so, return [`StepOutcome::Complete`].
+    /// 2. Attempt to pop the next event from the queue. If there isn't one, return [`StepOutcome::QueueEmpty`].
+    /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
+    ///    otherwise return [`StepOutcome::Executed`] with the handle that the event was scheduled with.
+    ///
+    /// As with [`run()`], any attached [`Observer`]s are notified immediately before and after the event executes.
+    ///
+    /// Stepping through a simulation is useful for driving it from a debugger or interactive session, or for inspecting
+    /// the simulation's state between events.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing the event are passed back to the caller, unchanged, as with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`event.execute()`]: Event::execute
+    /// [`StepOutcome::Complete`]: crate::StepOutcome::Complete
+    /// [`StepOutcome::QueueEmpty`]: crate::StepOutcome::QueueEmpty
+    /// [`StepOutcome::Executed`]: crate::StepOutcome::Executed
+    pub fn step(&mut self) -> crate::Result<StepOutcome> {
+        if self.state.is_complete(self.current_time()) {
+            return Ok(StepOutcome::Complete);
+        }
+
+        match self.event_queue.next() {
+            Some((mut event, time, handle)) => {
+                self.current_time = time;
+                self.observers
+                    .before_event(&ObservedEvent::new(&event, event.name(), &self.current_time, handle));
+                let result = event.execute(self);
+                self.observers.after_event(
+                    &ObservedEvent::new(&event, event.name(), &self.current_time, handle),
+                    &result,
+                );
+                result.map(|_| StepOutcome::Executed(handle))
+            },
+            None => Ok(StepOutcome::QueueEmpty),
+        }
+    }
+
+    // only needed by tests that pop events without executing them,
+    // as step() works with the handle of the popped event as well
+    #[cfg(test)]
+    fn next_event(&mut self) -> Option<EventType> {
+        if let Some((event, time, _)) = self.event_queue.next() {
+            self.current_time = time;
+            Some(event)
+        } else {
+            None
+        }
+    }
+
+    /// Schedule the provided event at the specified time.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule(&mut self, event: EventType, time: Time) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_unchecked(event, time)) }
+    }
+
+    /// Schedule the provided event at the specified time. Assumes that the provided time is valid in the context of the
+    /// client's simulation.
+    ///
+    /// # Safety
+    ///
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means. For example, adding a strictly positive offset to
+    /// the current clock time to get the `time` argument for the call.
+    pub unsafe fn schedule_unchecked(&mut self, event: EventType, time: Time) -> EventHandle {
+        self.schedule_with_priority_unchecked(event, time, 0)
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Among events scheduled for the same
+    /// time, those with a higher priority execute first, while events with equal priorities execute in the order they
+    /// were scheduled. Events scheduled without an explicit priority receive a priority of 0.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_priority(
+        &mut self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_with_priority_unchecked(event, time, priority)) }
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Assumes that the provided time is
+    /// valid in the context of the client's simulation.
+    ///
+    /// Among events scheduled for the same time, those with a higher priority execute first, while events with equal
+    /// priorities execute in the order they were scheduled.
+    ///
+    /// # Safety
+    ///
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means.
+    pub unsafe fn schedule_with_priority_unchecked(
+        &mut self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        self.event_queue.schedule_event(event, time, priority)
+    }
+
+    /// Cancel a previously scheduled event so that it will not execute. Returns `true` if the event referred to by
+    /// `handle` was still pending and has now been removed from the queue, or `false` if it had already executed or
+    /// been cancelled.
+    ///
+    /// The cancelled event is dropped immediately.
+    pub fn cancel(&mut self, handle: EventHandle) -> bool {
+        self.event_queue.cancel_event(handle)
+    }
+
+    /// Move a pending event to a new execution time, returning a fresh [`EventHandle`] that refers to the moved event.
+    ///
+    /// The event is treated as though it were newly scheduled for `time` with its original priority: it receives a new
+    /// insertion sequence, and so will execute after any other events of equal priority already scheduled for that same
+    /// time. The handle passed in no longer refers to the event once this method succeeds, so hold on to the returned
+    /// handle instead.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue. If `handle` does not refer to a
+    /// pending event, e.g. because that event has already executed or been cancelled, returns an
+    /// [`Error::EventNotFound`] instead.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    pub fn reschedule(&mut self, handle: EventHandle, time: Time) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        self.event_queue
+            .reschedule_event(handle, time)
+            .ok_or(crate::Error::EventNotFound)
+    }
+
+    /// Get a shared reference to the simulation state.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
+
+    /// Get an exclusive reference to the simulation state.
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.state
+    }
+
+    /// Get a shared reference to the current simulation time.
+    pub fn current_time(&self) -> &Time {
+        &self.current_time
+    }
+
+    /// Attach an observer that will be notified immediately before and after each event executes, returning a handle
+    /// through which the observer may later be retrieved.
+    ///
+    /// Observers are notified in the order they were attached.
+    pub fn add_observer<ObserverType>(&mut self, observer: ObserverType) -> ObserverHandle<ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.add(Box::new(observer))
+    }
+
+    /// Get a shared reference to a previously attached observer, e.g. to read the results it has gathered. Returns
+    /// `None` if the observer has since been removed.
+    pub fn observer<ObserverType>(&self, handle: &ObserverHandle<ObserverType>) -> Option<&ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.get(handle)
+    }
+
+    /// Get an exclusive reference to a previously attached observer. Returns `None` if the observer has since been
+    /// removed.
+    pub fn observer_mut<ObserverType>(&mut self, handle: &ObserverHandle<ObserverType>) -> Option<&mut ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.get_mut(handle)
+    }
+
+    /// Detach a previously attached observer so that it will no longer be notified of executing events, handing it
+    /// back to the caller. Returns `None` if the observer has already been removed.
+    pub fn remove_observer<ObserverType>(&mut self, handle: ObserverHandle<ObserverType>) -> Option<ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.remove(handle)
+    }
+
+    /// Get a shared reference to the execution time of the next event on the queue, if any, without executing it.
+    ///
+    /// Cancelled events are never reported here. This method is useful for e.g. deciding how far a continuous process
+    /// may be integrated before the next discrete event interrupts it.
+    pub fn peek_next_time(&self) -> Option<&Time> {
+        self.event_queue.peek_time()
+    }
+
+    /// Get the number of events currently waiting on the queue. Cancelled events are not counted.
+    pub fn pending_event_count(&self) -> usize {
+        self.event_queue.pending_count()
+    }
+
+    /// Iterate over views of every event currently waiting on the queue, in the order they are expected to execute.
+    ///
+    /// Each [`PendingEvent`] provides the event's execution time, the [`EventHandle`] it was scheduled with, and shared
+    /// access to the event itself for printing via [`Debug`]. Cancelled events are skipped.
+    ///
+    /// Note that gathering this view of the queue requires sorting its contents, and so should be avoided in hot loops.
+    ///
+    /// [`PendingEvent`]: super::PendingEvent
+    pub fn pending_events(&self) -> impl Iterator<Item = PendingEvent<'_, Time, EventType>> {
+        self.event_queue.pending_events().into_iter()
+    }
+}
+
+impl<State, Time, EventType, Backend> Simulation<State, Time, EventType, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Execute every event scheduled at or before `horizon`, one at a time, in ascending order by execution time, then
+    /// advance the clock to `horizon`.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once the next
+    /// event on the queue is scheduled for some time after `horizon`. Events scheduled for exactly `horizon` will
+    /// execute. If the loop stops because the event queue is empty or because the next event lies beyond `horizon`, the
+    /// clock is then set to a clone of `horizon`. If instead [`state.is_complete()`] returns true, the clock is left at
+    /// the execution time of the last event so that it accurately reflects when the simulation finished.
+    ///
+    /// Splitting a run with this method allows for e.g. clearing statistics after a warm-up period, then continuing
+    /// with the measurement phase.
+    ///
+    /// # Errors
+    ///
+    /// If `horizon` is less than the current clock time on `self`, returns an [`Error::BackInTime`] without executing
+    /// any events. Otherwise, errors encountered while executing events are passed back to the caller, unchanged, as
+    /// with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn run_until(&mut self, horizon: &Time) -> crate::Result {
+        if *horizon < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        loop {
+            match self.event_queue.peek_time() {
+                Some(next_time) if next_time <= horizon => {},
+                _ => break,
+            }
+
+            match self.step()? {
+                StepOutcome::Executed(_) => {},
+                StepOutcome::Complete => return Ok(()),
+                StepOutcome::QueueEmpty => break,
+            }
+        }
+
+        if !self.state.is_complete(self.current_time()) {
+            self.current_time = horizon.clone();
+        }
+        Ok(())
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
+    ///
+    /// # Errors
+    ///
+    /// If the result of calling [`Clone::clone`] on the current sim time results in a new value that is somehow less
+    /// than the current sim time, this method will return an [`Error::BackInTime`]. Note that such behavior is not
+    /// expected from implementations of [`Clone::clone`] in most cases.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_now(&mut self, event: EventType) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone();
+        self.schedule(event, event_time)
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on client implementations of
+    /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
+    /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
+    /// chosen type, this method will be safe to call.
+    pub unsafe fn schedule_now_unchecked(&mut self, event: EventType) -> EventHandle {
+        self.schedule_unchecked(event, self.current_time.clone())
+    }
+}
+
+impl<State, Time, EventType, Backend> Simulation<State, Time, EventType, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone + Add<Output = Time>,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_delay(&mut self, event: EventType, delay: Time) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
+    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
+    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
+    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    pub unsafe fn schedule_with_delay_unchecked(&mut self, event: EventType, delay: Time) -> EventHandle {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_unchecked(event, event_time)
+    }
+
+    /// Move a pending event so that it executes after the specified delay, returning a fresh [`EventHandle`] that
+    /// refers to the moved event. The event's new execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// As with [`reschedule()`], the event is treated as though it were newly scheduled and the handle passed in no
+    /// longer refers to it once this method succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// with no modifications to the queue. If `handle` does not refer to a pending event, returns an
+    /// [`Error::EventNotFound`] instead.
+    ///
+    /// [`reschedule()`]: Simulation::reschedule
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    pub fn reschedule_with_delay(&mut self, handle: EventHandle, delay: Time) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.reschedule(handle, event_time)
+    }
+}
+
+impl<State, Time, EventType, Backend> Default for Simulation<State, Time, EventType, Backend>
+where
+    State: SimState<Time> + Default,
+    Time: SimTime + Default,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time> + Default,
+{
+    // implemented by hand, as deriving would needlessly require the event type to implement Default
+    fn default() -> Self {
+        Self::with_backend(State::default(), Time::default(), Backend::default())
+    }
+}
+
+impl<State, Time, EventType, Backend> std::fmt::Display for Simulation<State, Time, EventType, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    EventType: Event<State, Time, Backend>,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Simulation at time {:?}", self.current_time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct State {
+        executed_event_values: Vec<i32>,
+        complete: bool,
+    }
+    impl SimState<i32> for State {
+        fn is_complete(&self, _: &i32) -> bool {
+            self.complete
+        }
+    }
+
+    #[derive(Debug)]
+    enum TestEvent {
+        Record(i32),
+        Complete,
+        Fail,
+    }
+
+    impl Event<State, i32> for TestEvent {
+        fn execute(&mut self, simulation: &mut Simulation<State, i32, Self>) -> crate::Result {
+            match self {
+                TestEvent::Record(value) => simulation.state_mut().executed_event_values.push(*value),
+                TestEvent::Complete => simulation.state_mut().complete = true,
+                TestEvent::Fail => return Err(crate::Error::BackInTime),
+            }
+            Ok(())
+        }
+
+        fn name(&self) -> &str {
+            match self {
+                TestEvent::Record(_) => "Record",
+                TestEvent::Complete => "Complete",
+                TestEvent::Fail => "Fail",
+            }
+        }
+    }
+
+    fn setup() -> Simulation<State, i32, TestEvent> {
+        let mut sim = Simulation::new(
+            State {
+                executed_event_values: Vec::with_capacity(3),
+                complete: false,
+            },
+            0,
+        );
+
+        for (i, value) in [1, 3, 2].into_iter().enumerate() {
+            sim.schedule(TestEvent::Record(value), 2 * i as i32).unwrap();
+        }
+        sim
+    }
+
+    #[test]
+    fn execution_time_ascends() {
+        let mut sim = setup();
+        sim.run().expect("simulation should run to completion");
+
+        assert_eq!(
+            vec![1, 3, 2],
+            sim.state().executed_event_values,
+            "events did not execute in expected order"
+        );
+    }
+
+    #[test]
+    fn schedule_fails_if_given_invalid_execution_time() {
+        let mut sim = setup();
+        let result = sim.schedule(TestEvent::Record(0), -1);
+        assert_eq!(
+            Some(crate::Error::BackInTime),
+            result.err(),
+            "sim failed to reject event scheduled for the past"
+        );
+    }
+
+    #[test]
+    fn unsafe_schedulers_allow_time_to_reverse() {
+        let mut sim = setup();
+        unsafe {
+            sim.schedule_unchecked(TestEvent::Record(1), -1);
+        }
+        sim.next_event().expect("event queue should yield a scheduled event");
+        assert_eq!(
+            -1,
+            *sim.current_time(),
+            "current time did not update when popping event"
+        );
+    }
+
+    #[test]
+    fn insertion_sequence_breaks_ties_in_execution_time() {
+        const NUM_EVENTS: i32 = 10;
+        let state = State {
+            executed_event_values: Vec::with_capacity(NUM_EVENTS as usize),
+            complete: false,
+        };
+        let mut sim = Simulation::new(state, 0);
+
+        for copy_id in 0..NUM_EVENTS {
+            sim.schedule(TestEvent::Record(copy_id), 1)
+                .expect("failed to schedule event");
+        }
+        sim.run().expect("simulation should run to completion");
+
+        let expected: Vec<_> = (0..NUM_EVENTS).collect();
+        assert_eq!(
+            expected,
+            sim.state().executed_event_values,
+            "events executed out of insertion sequence"
+        );
+    }
+
+    #[test]
+    fn simulation_stops_with_events_still_in_queue() {
+        let mut sim = setup();
+        sim.schedule(TestEvent::Complete, 3).unwrap();
+        sim.run().unwrap();
+
+        assert_eq!(
+            vec![1, 3],
+            sim.state().executed_event_values,
+            "simulation did not terminate with completion event"
+        );
+        assert_eq!(1, sim.pending_event_count(), "last event should remain on the queue");
+    }
+
+    #[test]
+    fn execution_errors_are_returned() {
+        let mut sim = setup();
+        sim.schedule(TestEvent::Fail, 1).unwrap();
+        assert_eq!(Err(crate::Error::BackInTime), sim.run(), "error was not returned");
+        assert_eq!(
+            vec![1],
+            sim.state().executed_event_values,
+            "simulation did not stop at failing event"
+        );
+    }
+
+    #[test]
+    fn cancelled_and_rescheduled_events_are_respected() {
+        let mut sim = setup();
+        let cancelled = sim.schedule(TestEvent::Record(4), 1).unwrap();
+        let moved = sim.schedule(TestEvent::Record(5), 1).unwrap();
+
+        assert!(sim.cancel(cancelled), "cancelled event should have been pending");
+        assert!(!sim.cancel(cancelled), "event should not be cancelled twice");
+        sim.reschedule(moved, 5).expect("event should be rescheduled");
+        sim.run().unwrap();
+
+        assert_eq!(
+            vec![1, 3, 2, 5],
+            sim.state().executed_event_values,
+            "cancelled or rescheduled event executed unexpectedly"
+        );
+    }
+
+    #[test]
+    fn priorities_break_ties_before_insertion_order() {
+        let mut sim = setup();
+        sim.schedule_with_priority(TestEvent::Record(4), 2, -1).unwrap();
+        sim.schedule_with_priority(TestEvent::Record(5), 2, 1).unwrap();
+        sim.run().unwrap();
+
+        assert_eq!(
+            vec![1, 5, 3, 4, 2],
+            sim.state().executed_event_values,
+            "priorities did not break ties as expected"
+        );
+    }
+
+    #[test]
+    fn pending_events_can_be_inspected() {
+        let sim = setup();
+        let pending: Vec<_> = sim.pending_events().map(|pending| format!("{:?}", pending)).collect();
+        assert_eq!(
+            vec![
+                "ScheduledEvent { event: Record(1), execution_time: 0, priority: 0, insertion_sequence: 0 }",
+                "ScheduledEvent { event: Record(3), execution_time: 2, priority: 0, insertion_sequence: 1 }",
+                "ScheduledEvent { event: Record(2), execution_time: 4, priority: 0, insertion_sequence: 2 }",
+            ],
+            pending,
+            "pending events did not match expectation"
+        );
+    }
+
+    #[derive(Default)]
+    struct NameObserver {
+        names: Vec<String>,
+    }
+
+    impl Observer<i32> for NameObserver {
+        fn before_event(&mut self, event: &ObservedEvent<'_, i32>) {
+            self.names.push(event.name().to_owned());
+        }
+    }
+
+    #[test]
+    fn observers_see_event_names() {
+        let mut sim = setup();
+        sim.schedule(TestEvent::Complete, 3).unwrap();
+        let handle = sim.add_observer(NameObserver::default());
+        sim.run().unwrap();
+
+        let observer = sim.remove_observer(handle).expect("observer should still be attached");
+        assert_eq!(
+            vec!["Record", "Record", "Complete"],
+            observer.names,
+            "observer did not see expected events"
+        );
+    }
+}
@@ -12,6 +12,8 @@
 //!   returns an error to give client code the option of handling it outside the event loop.
 //! * Parameterizing over the [`SimTime`] trait gives full control over how events are sequenced at runtime, determined
 //!   entirely through your type's implementation of the [`Ord`] supertrait.
+//! * Parameterizing over the [`EventQueueBackend`] trait lets you choose the data structure that orders scheduled
+//!   events, defaulting to a binary heap when left unspecified.
 //!
 //! The expectation in desque that a [`Simulation`] own all data associated with a replication also supports the
 //! application of variance-reduction techniques from the statistical field known as design of experiments. For example,
@@ -34,11 +36,34 @@
 //! [`NotNan`]: https://docs.rs/ordered-float/4/ordered_float/struct.NotNan.html
 //! [`Simulation`]: serial::Simulation
 //! [`Event`]: serial::Event
+//! [`EventQueueBackend`]: queue::EventQueueBackend
 
+mod activity;
+mod arena;
+mod condition;
 mod error;
+mod event_handle;
 mod generic_parameters;
+pub mod inline;
+mod observer;
+mod periodic;
+pub mod process;
+pub mod queue;
+pub mod replications;
+pub mod resource;
 pub mod serial;
+mod signal;
+pub mod stats;
+mod step_outcome;
 pub mod threadsafe;
+pub mod trace;
 
+pub use activity::{ActivityHandle, Interrupted};
+pub use condition::ConditionHandle;
 pub use error::{Error, Result};
-pub use generic_parameters::{SimState, SimTime};
+pub use event_handle::EventHandle;
+pub use generic_parameters::{NumericTime, SimState, SimTime};
+pub use observer::{ObservedEvent, Observer, ObserverHandle};
+pub use periodic::{PeriodicHandle, Recurrence};
+pub use signal::Signal;
+pub use step_outcome::StepOutcome;
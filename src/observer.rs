@@ -0,0 +1,294 @@
+use crate::{EventHandle, SimTime};
+use std::any::Any;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+
+/// Watches events as a simulation executes them.
+///
+/// Observers are attached to a [`serial::Simulation`] or [`threadsafe::Simulation`] through its `add_observer()`
+/// method, after which the simulation will invoke [`before_event()`] and [`after_event()`] around every call to
+/// [`Event::execute()`]. This arrangement allows for plugging tracing, statistics collection, or invariant checks into
+/// a simulation without editing each of its event types.
+///
+/// Both methods have default implementations that do nothing, so implementors need only override the hooks they care
+/// about. Observers are invoked in the order they were added to the simulation.
+///
+/// Observers do not receive access to the simulation itself, and so cannot alter its state or schedule events. They
+/// instead receive an [`ObservedEvent`] describing the event that is about to execute or has just executed.
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+/// [`before_event()`]: Observer::before_event
+/// [`after_event()`]: Observer::after_event
+/// [`Event::execute()`]: crate::serial::Event::execute
+pub trait Observer<Time>
+where
+    Time: SimTime,
+{
+    /// Invoked immediately before an event executes. The simulation's clock will already have advanced to the event's
+    /// execution time.
+    ///
+    /// The default implementation does nothing.
+    // expect that other implementations will make use of the
+    // argument even though this one doesn't
+    #[allow(unused_variables)]
+    fn before_event(&mut self, event: &ObservedEvent<'_, Time>) {}
+
+    /// Invoked immediately after an event executes, with the result that the event returned. An `Err` result will be
+    /// passed back to the caller of the simulation's run method once every observer has seen it.
+    ///
+    /// The default implementation does nothing.
+    // expect that other implementations will make use of the
+    // arguments even though this one doesn't
+    #[allow(unused_variables)]
+    fn after_event(&mut self, event: &ObservedEvent<'_, Time>, result: &crate::Result) {}
+}
+
+/// Describes an event to an [`Observer`].
+///
+/// Provides the event's execution time, the [`EventHandle`] it was scheduled with, the name reported by the event's
+/// `name()` method, and shared access to the event itself through its implementation of [`Debug`].
+pub struct ObservedEvent<'a, Time>
+where
+    Time: SimTime,
+{
+    event: &'a dyn Debug,
+    name: &'a str,
+    execution_time: &'a Time,
+    handle: EventHandle,
+}
+
+impl<'a, Time> ObservedEvent<'a, Time>
+where
+    Time: SimTime,
+{
+    pub(crate) fn new(event: &'a dyn Debug, name: &'a str, execution_time: &'a Time, handle: EventHandle) -> Self {
+        Self {
+            event,
+            name,
+            execution_time,
+            handle,
+        }
+    }
+
+    /// Get a shared reference to the event, which may be formatted with its implementation of [`Debug`].
+    pub fn event(&self) -> &'a dyn Debug {
+        self.event
+    }
+
+    /// Get the name of the event, as reported by its `name()` method. By default, this is the name of the event's type.
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Get a shared reference to the time at which the event executes.
+    pub fn execution_time(&self) -> &'a Time {
+        self.execution_time
+    }
+
+    /// Get the handle that was returned when the event was scheduled.
+    pub fn handle(&self) -> EventHandle {
+        self.handle
+    }
+}
+
+impl<Time> Debug for ObservedEvent<'_, Time>
+where
+    Time: SimTime,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ObservedEvent")
+            .field("event", &self.event)
+            .field("name", &self.name)
+            .field("execution_time", &self.execution_time)
+            .field("insertion_sequence", &self.handle.insertion_sequence())
+            .finish()
+    }
+}
+
+/// Refers to an [`Observer`] that has been attached to a simulation.
+///
+/// Returned from `add_observer()` on [`serial::Simulation`] or [`threadsafe::Simulation`], this handle remembers the
+/// concrete type of the observer so that the simulation can hand back typed references to it. Reading the results an
+/// observer has gathered over the course of a run is then a matter of presenting this handle to the simulation's
+/// `observer()` or `remove_observer()` methods.
+///
+/// As with an [`EventHandle`], an [`ObserverHandle`] is only meaningful to the simulation that produced it.
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+pub struct ObserverHandle<ObserverType> {
+    index: usize,
+    _observer_type: PhantomData<fn() -> ObserverType>,
+}
+
+impl<ObserverType> ObserverHandle<ObserverType> {
+    fn new(index: usize) -> Self {
+        Self {
+            index,
+            _observer_type: PhantomData,
+        }
+    }
+}
+
+// manual implementations avoid requiring the observer type
+// itself to implement these traits, as a derive would
+impl<ObserverType> Clone for ObserverHandle<ObserverType> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<ObserverType> Copy for ObserverHandle<ObserverType> {}
+
+impl<ObserverType> PartialEq for ObserverHandle<ObserverType> {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+
+impl<ObserverType> Eq for ObserverHandle<ObserverType> {}
+
+impl<ObserverType> Debug for ObserverHandle<ObserverType> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ObserverHandle")
+            .field("index", &self.index)
+            .field("observer_type", &std::any::type_name::<ObserverType>())
+            .finish()
+    }
+}
+
+/// Extends [`Observer`] with the ability to recover the implementing type after being boxed.
+pub(crate) trait AnyObserver<Time>: Observer<Time>
+where
+    Time: SimTime,
+{
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<Time, ObserverType> AnyObserver<Time> for ObserverType
+where
+    Time: SimTime,
+    ObserverType: Observer<Time> + Any,
+{
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
+
+/// Holds the observers attached to a simulation, keeping each one at a stable index so that an [`ObserverHandle`]
+/// remains valid when other observers are removed.
+///
+/// Generic over the boxed type so that the [`threadsafe::Simulation`] can impose [`Send`] and [`Sync`] on its
+/// observers while the [`serial::Simulation`] does not.
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+pub(crate) struct ObserverList<Time, Erased>
+where
+    Time: SimTime,
+    Erased: AnyObserver<Time> + ?Sized,
+{
+    observers: Vec<Option<Box<Erased>>>,
+    _time: PhantomData<fn(&Time)>,
+}
+
+impl<Time, Erased> ObserverList<Time, Erased>
+where
+    Time: SimTime,
+    Erased: AnyObserver<Time> + ?Sized,
+{
+    /// Construct an empty list.
+    pub fn new() -> Self {
+        Self {
+            observers: Vec::new(),
+            _time: PhantomData,
+        }
+    }
+
+    /// Attach a boxed observer, producing a handle that refers to it.
+    pub fn add<ObserverType>(&mut self, observer: Box<Erased>) -> ObserverHandle<ObserverType> {
+        self.observers.push(Some(observer));
+        ObserverHandle::new(self.observers.len() - 1)
+    }
+
+    /// Look up an observer by handle, returning `None` if it has been removed.
+    pub fn get<ObserverType>(&self, handle: &ObserverHandle<ObserverType>) -> Option<&ObserverType>
+    where
+        ObserverType: Any,
+    {
+        self.observers.get(handle.index)?.as_ref()?.as_any().downcast_ref()
+    }
+
+    /// Look up an observer by handle, returning `None` if it has been removed.
+    pub fn get_mut<ObserverType>(&mut self, handle: &ObserverHandle<ObserverType>) -> Option<&mut ObserverType>
+    where
+        ObserverType: Any,
+    {
+        self.observers
+            .get_mut(handle.index)?
+            .as_mut()?
+            .as_any_mut()
+            .downcast_mut()
+    }
+
+    /// Take an observer out of the list, leaving its slot empty so that other handles remain valid.
+    pub fn remove<ObserverType>(&mut self, handle: ObserverHandle<ObserverType>) -> Option<ObserverType>
+    where
+        ObserverType: Any,
+    {
+        let slot = self.observers.get_mut(handle.index)?;
+        if !slot.as_ref()?.as_any().is::<ObserverType>() {
+            return None;
+        }
+
+        let observer = slot.take()?.into_any().downcast().ok()?;
+        Some(*observer)
+    }
+
+    /// Notify every attached observer that an event is about to execute.
+    pub fn before_event(&mut self, event: &ObservedEvent<'_, Time>) {
+        for observer in self.observers.iter_mut().flatten() {
+            observer.before_event(event);
+        }
+    }
+
+    /// Notify every attached observer that an event has finished executing.
+    pub fn after_event(&mut self, event: &ObservedEvent<'_, Time>, result: &crate::Result) {
+        for observer in self.observers.iter_mut().flatten() {
+            observer.after_event(event, result);
+        }
+    }
+}
+
+impl<Time, Erased> Default for ObserverList<Time, Erased>
+where
+    Time: SimTime,
+    Erased: AnyObserver<Time> + ?Sized,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time, Erased> Debug for ObserverList<Time, Erased>
+where
+    Time: SimTime,
+    Erased: AnyObserver<Time> + ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ObserverList")
+            .field("attached", &self.observers.iter().flatten().count())
+            .finish()
+    }
+}
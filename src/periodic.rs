@@ -0,0 +1,64 @@
+use crate::SimTime;
+
+/// Identifies a recurrence started by one of the `schedule_periodic()` methods.
+///
+/// Presenting this handle to [`serial::Simulation::stop_periodic()`] or [`threadsafe::Simulation::stop_periodic()`]
+/// cancels the recurrence's pending occurrence, and no further occurrences are scheduled. As with an [`EventHandle`], a
+/// handle is only meaningful to the simulation that produced it.
+///
+/// [`serial::Simulation::stop_periodic()`]: crate::serial::Simulation::stop_periodic
+/// [`threadsafe::Simulation::stop_periodic()`]: crate::threadsafe::Simulation::stop_periodic
+/// [`EventHandle`]: crate::EventHandle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PeriodicHandle {
+    id: usize,
+}
+
+impl PeriodicHandle {
+    /// Wrap the sequence number assigned to a recurrence by the simulation that started it.
+    pub(crate) fn new(id: usize) -> Self {
+        Self { id }
+    }
+}
+
+/// Limits how many times a periodic event recurs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Recurrence<Time> {
+    /// Recur until stopped through the [`PeriodicHandle`], or until the simulation ends.
+    Forever,
+    /// Recur at every multiple of the period that does not exceed the provided time.
+    Until(Time),
+    /// Recur the provided number of times in total, including the first occurrence.
+    Times(usize),
+}
+
+impl<Time> Recurrence<Time>
+where
+    Time: SimTime,
+{
+    /// Check whether an occurrence at the provided time is permitted, returning the limit that will apply to the
+    /// following occurrences if so.
+    pub(crate) fn admit(self, time: &Time) -> Option<Self> {
+        match self {
+            Recurrence::Forever => Some(Recurrence::Forever),
+            Recurrence::Until(end) => (*time <= end).then_some(Recurrence::Until(end)),
+            Recurrence::Times(count) => count.checked_sub(1).map(Recurrence::Times),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_admit_expected_occurrences() {
+        assert_eq!(Some(Recurrence::Forever), Recurrence::<u32>::Forever.admit(&u32::MAX));
+
+        assert_eq!(Some(Recurrence::Until(5)), Recurrence::Until(5).admit(&5));
+        assert_eq!(None, Recurrence::Until(5).admit(&6));
+
+        assert_eq!(Some(Recurrence::Times(0)), Recurrence::<u32>::Times(1).admit(&0));
+        assert_eq!(None, Recurrence::<u32>::Times(0).admit(&0));
+    }
+}
@@ -0,0 +1,364 @@
+//! For modelling entities as processes that suspend and resume, rather than as individual events.
+//!
+//! In the process-interaction view of discrete-event simulation, an entity's lifecycle is written as one sequential
+//! routine that repeatedly does some work and then waits: for time to pass, for the simulation's state to satisfy some
+//! condition, for a unit of a [`Resource`], or for another part of the model to wake it. This module layers that view
+//! on top of the event loop of a [`serial::Simulation`]. A [`Process`] is a state machine whose [`resume()`] method
+//! runs until the process must wait, then returns a [`Yield`] describing what it is waiting for. The simulation resumes
+//! it once that wait is over, each resumption executing as an ordinary event that [`Observer`]s see under the process's
+//! [`name()`].
+//!
+//! Processes are started with [`Simulation::start_process()`], which returns a [`ProcessHandle`] that other events
+//! can later present to [`Simulation::activate()`] to wake a passive process.
+//!
+//! Processes may also be written as async functions and started with [`Simulation::spawn()`]. The function receives a
+//! [`ProcessContext`] through which it accesses the simulation and awaits each wait in turn, leaving the compiler to
+//! build the state machine. No async runtime is involved: the simulation polls the future once each time the process
+//! resumes, so runs remain single-threaded and deterministic.
+//!
+//! ```
+//! use desque::process::{Process, Yield};
+//! use desque::serial::Simulation;
+//! use desque::SimState;
+//!
+//! #[derive(Debug, Default)]
+//! struct Shop {
+//!     server_busy: bool,
+//!     customers_served: usize,
+//! }
+//!
+//! impl SimState<u32> for Shop {}
+//!
+//! #[derive(Debug)]
+//! enum Customer {
+//!     Arriving,
+//!     BeingServed,
+//! }
+//!
+//! impl Process<Shop, u32> for Customer {
+//!     fn resume(&mut self, sim: &mut Simulation<Shop, u32>) -> desque::Result<Yield<Shop, u32>> {
+//!         match self {
+//!             Customer::Arriving if sim.state().server_busy => Ok(Yield::wait_for(|shop: &Shop| !shop.server_busy)),
+//!             Customer::Arriving => {
+//!                 sim.state_mut().server_busy = true;
+//!                 *self = Customer::BeingServed;
+//!                 Ok(Yield::Hold(5))
+//!             },
+//!             Customer::BeingServed => {
+//!                 let shop = sim.state_mut();
+//!                 shop.server_busy = false;
+//!                 shop.customers_served += 1;
+//!                 Ok(Yield::Finish)
+//!             },
+//!         }
+//!     }
+//! }
+//!
+//! let mut sim = Simulation::new(Shop::default(), 0);
+//! sim.start_process(Customer::Arriving, 0)?;
+//! sim.start_process(Customer::Arriving, 1)?;
+//! sim.run()?;
+//!
+//! assert_eq!(2, sim.state().customers_served);
+//! assert_eq!(10, *sim.current_time(), "second customer should wait for the first");
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! The same model, written as an async process:
+//!
+//! ```
+//! # use desque::serial::Simulation;
+//! # use desque::SimState;
+//! # #[derive(Debug, Default)]
+//! # struct Shop {
+//! #     server_busy: bool,
+//! #     customers_served: usize,
+//! # }
+//! # impl SimState<u32> for Shop {}
+//! use desque::process::ProcessContext;
+//!
+//! async fn customer(ctx: ProcessContext<Shop, u32>) -> desque::Result {
+//!     while ctx.with_simulation(|sim| sim.state().server_busy) {
+//!         ctx.wait_for(|shop: &Shop| !shop.server_busy).await;
+//!     }
+//!     ctx.with_simulation(|sim| sim.state_mut().server_busy = true);
+//!     ctx.timeout(5).await;
+//!     ctx.with_simulation(|sim| {
+//!         let shop = sim.state_mut();
+//!         shop.server_busy = false;
+//!         shop.customers_served += 1;
+//!     });
+//!     Ok(())
+//! }
+//!
+//! let mut sim = Simulation::new(Shop::default(), 0);
+//! sim.spawn(customer, 0)?;
+//! sim.spawn(customer, 1)?;
+//! sim.run()?;
+//!
+//! assert_eq!(2, sim.state().customers_served);
+//! assert_eq!(10, *sim.current_time());
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! [`serial::Simulation`]: crate::serial::Simulation
+//! [`Simulation::start_process()`]: crate::serial::Simulation::start_process
+//! [`Simulation::activate()`]: crate::serial::Simulation::activate
+//! [`Simulation::spawn()`]: crate::serial::Simulation::spawn
+//! [`resume()`]: Process::resume
+//! [`name()`]: Process::name
+//! [`Observer`]: crate::Observer
+//! [`Resource`]: crate::resource::Resource
+
+mod executor;
+
+pub(crate) use executor::Task;
+pub use executor::{ProcessContext, Wait};
+
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::resource::Resource;
+use crate::serial::{Event, OkEvent, Simulation};
+use crate::{SimState, SimTime};
+use std::fmt::{Debug, Formatter};
+use std::ops::Add;
+
+/// An entity whose lifecycle is written as a sequence of steps separated by waits.
+///
+/// Each call to [`resume()`] should perform the work up to the process's next wait and return a [`Yield`] describing
+/// that wait. Implementors typically track their progress through the lifecycle in an enum, advancing it before
+/// returning. As with events, requiring [`Debug`] enables printing the contents of a simulation's queue, on which a
+/// holding process waits.
+///
+/// [`resume()`]: Process::resume
+pub trait Process<State, Time, Backend = BinaryHeapQueue<Time>>: Debug
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Run the process until it must wait, with exclusive access to the simulation as in [`Event::execute()`].
+    ///
+    /// # Errors
+    ///
+    /// Errors are passed back from [`Simulation::run()`] as though they had been returned by an event, and end the
+    /// process.
+    ///
+    /// [`Event::execute()`]: crate::serial::Event::execute
+    /// [`Simulation::run()`]: crate::serial::Simulation::run
+    fn resume(
+        &mut self,
+        simulation: &mut Simulation<State, Time, Backend>,
+    ) -> crate::Result<Yield<State, Time, Backend>>;
+
+    /// Get a name for this kind of process, used as the name of each event that resumes it.
+    ///
+    /// The default implementation returns the name of the implementing type as reported by [`std::any::type_name()`],
+    /// with the same caveats as [`Event::name()`].
+    ///
+    /// [`Event::name()`]: crate::serial::Event::name
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
+}
+
+/// Selects the resource in the simulation's state for which a process waits.
+type ResourceSelector<State, Time, Backend> =
+    Box<dyn FnOnce(&mut State) -> &mut Resource<Resume<State, Time, Backend>>>;
+
+/// What a [`Process`] waits for before it next resumes.
+pub enum Yield<State, Time, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Resume after the provided delay has passed.
+    Hold(Time),
+    /// Resume once the predicate holds for the simulation's state. Predicates are checked after each event executes, in
+    /// the order that processes began waiting, and a process is resumed at the time its predicate is first found to
+    /// hold.
+    WaitFor(Box<dyn Fn(&State) -> bool>),
+    /// Request a unit of the selected [`Resource`], resuming once it is granted. The process waits in the resource's
+    /// line like any other request, and should release the unit through [`Simulation::release_resource()`] when done
+    /// with it.
+    ///
+    /// [`Simulation::release_resource()`]: crate::serial::Simulation::release_resource
+    Request(ResourceSelector<State, Time, Backend>),
+    /// Resume only when activated through [`Simulation::activate()`].
+    ///
+    /// [`Simulation::activate()`]: crate::serial::Simulation::activate
+    Passivate,
+    /// End the process.
+    Finish,
+}
+
+impl<State, Time, Backend> Yield<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Wait until the provided predicate holds for the simulation's state.
+    pub fn wait_for<Predicate>(predicate: Predicate) -> Self
+    where
+        Predicate: Fn(&State) -> bool + 'static,
+    {
+        Yield::WaitFor(Box::new(predicate))
+    }
+
+    /// Wait until a unit of the selected resource is granted.
+    pub fn request<Select>(resource: Select) -> Self
+    where
+        Select: FnOnce(&mut State) -> &mut Resource<Resume<State, Time, Backend>> + 'static,
+    {
+        Yield::Request(Box::new(resource))
+    }
+}
+
+impl<State, Time, Backend> Debug for Yield<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Yield::Hold(delay) => f.debug_tuple("Hold").field(delay).finish(),
+            Yield::WaitFor(_) => f.write_str("WaitFor(..)"),
+            Yield::Request(_) => f.write_str("Request(..)"),
+            Yield::Passivate => f.write_str("Passivate"),
+            Yield::Finish => f.write_str("Finish"),
+        }
+    }
+}
+
+/// Identifies a process started on a simulation.
+///
+/// As with an [`EventHandle`], a handle is only meaningful to the simulation that produced it.
+///
+/// [`EventHandle`]: crate::EventHandle
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ProcessHandle {
+    id: usize,
+}
+
+impl ProcessHandle {
+    /// Wrap the sequence number assigned to a process by the simulation that started it.
+    pub(crate) fn new(id: usize) -> Self {
+        Self { id }
+    }
+}
+
+/// The continuation with which a process waits in a [`Resource`]'s line, resuming the process once its request is
+/// granted.
+///
+/// A resource that processes request through [`Yield::Request`] or [`ProcessContext::request()`] must hold
+/// continuations of this type.
+pub struct Resume<State, Time, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    event: Box<dyn Event<State, Time, Backend>>,
+}
+
+impl<State, Time, Backend> OkEvent<State, Time, Backend> for Resume<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        if let Err(error) = self.event.execute(simulation) {
+            simulation.defer_error(error);
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.event.name()
+    }
+}
+
+impl<State, Time, Backend> Debug for Resume<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.event.fmt(f)
+    }
+}
+
+/// The event that resumes a process, carrying the process between resumptions.
+pub(crate) struct ProcessEvent<ProcessType> {
+    handle: ProcessHandle,
+    /// Taken when the event executes, to be carried by the process's next resumption.
+    process: Option<ProcessType>,
+}
+
+impl<ProcessType> ProcessEvent<ProcessType> {
+    pub fn new(handle: ProcessHandle, process: ProcessType) -> Self {
+        Self {
+            handle,
+            process: Some(process),
+        }
+    }
+}
+
+impl<State, Time, Backend, ProcessType> OkEvent<State, Time, Backend> for ProcessEvent<ProcessType>
+where
+    State: SimState<Time> + 'static,
+    Time: SimTime + Clone + Add<Output = Time> + 'static,
+    Backend: EventQueueBackend<Time> + 'static,
+    ProcessType: Process<State, Time, Backend> + 'static,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        let mut process = self.process.take().expect("process event should only execute once");
+        let resumption = match process.resume(simulation) {
+            Ok(resumption) => resumption,
+            Err(error) => {
+                simulation.defer_error(error);
+                return;
+            },
+        };
+
+        let next = ProcessEvent::new(self.handle, process);
+        match resumption {
+            Yield::Hold(delay) => {
+                if let Err(error) = simulation.schedule_with_delay(next, delay) {
+                    simulation.defer_error(error);
+                }
+            },
+            Yield::WaitFor(predicate) => simulation.suspend_process(self.handle, Some(predicate), next),
+            Yield::Request(resource) => {
+                let resume = Resume { event: Box::new(next) };
+                if let Err(error) = simulation.request_resource(resource, resume) {
+                    simulation.defer_error(error);
+                }
+            },
+            Yield::Passivate => simulation.suspend_process(self.handle, None, next),
+            Yield::Finish => (),
+        }
+    }
+
+    fn name(&self) -> &str {
+        match &self.process {
+            Some(process) => process.name(),
+            None => std::any::type_name::<ProcessType>(),
+        }
+    }
+}
+
+impl<ProcessType> Debug for ProcessEvent<ProcessType>
+where
+    ProcessType: Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ProcessEvent")
+            .field("handle", &self.handle)
+            .field("process", &self.process)
+            .finish()
+    }
+}
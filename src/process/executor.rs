@@ -0,0 +1,461 @@
+use super::{Process, Resume, Yield};
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::resource::Resource;
+use crate::serial::Simulation;
+use crate::{SimState, SimTime};
+use std::cell::Cell;
+use std::fmt::{Debug, Formatter};
+use std::future::Future;
+use std::pin::Pin;
+use std::ptr::NonNull;
+use std::rc::Rc;
+use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+/// The link between an async process and the task that polls it.
+struct Shared<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// The simulation being run, present only while the task is polling the process and not already lent out.
+    simulation: Cell<Option<NonNull<Simulation<State, Time, Backend>>>>,
+    /// The wait requested by the future that last returned pending.
+    request: Cell<Option<Yield<State, Time, Backend>>>,
+}
+
+/// A process's access to the simulation running it, handed to each async process when it is spawned.
+///
+/// Between waits, the process can inspect and modify the simulation through [`with_simulation()`]. The futures returned
+/// by [`timeout()`], [`wait_for()`], [`request()`], and [`passivate()`] suspend the process when awaited, and complete
+/// once the simulation resumes it. A process should await only these futures, or futures built from them: the
+/// simulation polls a process only when its resumption event fires, so any other future that returns pending leaves the
+/// process passive until it is [activated].
+///
+/// [`with_simulation()`]: ProcessContext::with_simulation
+/// [`timeout()`]: ProcessContext::timeout
+/// [`wait_for()`]: ProcessContext::wait_for
+/// [`request()`]: ProcessContext::request
+/// [`passivate()`]: ProcessContext::passivate
+/// [activated]: crate::serial::Simulation::activate
+pub struct ProcessContext<State, Time, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    shared: Rc<Shared<State, Time, Backend>>,
+}
+
+impl<State, Time, Backend> ProcessContext<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Call the provided closure with exclusive access to the simulation, returning its result.
+    ///
+    /// # Panics
+    ///
+    /// The simulation is only available while the process is running, so this method panics if called from outside the
+    /// process's own future, or from within another call to itself.
+    pub fn with_simulation<R>(&self, f: impl FnOnce(&mut Simulation<State, Time, Backend>) -> R) -> R {
+        let mut simulation = self
+            .shared
+            .simulation
+            .take()
+            .expect("simulation should only be accessed from its running process, and not reentrantly");
+        // SAFETY: the pointer was created by the executing task from its exclusive borrow of the simulation, which
+        // lasts for the whole poll, and the task does not otherwise use that borrow while polling. Taking the pointer
+        // out of the cell for the duration of the closure ensures no other reference to the simulation is derived from
+        // it.
+        let result = f(unsafe { simulation.as_mut() });
+        self.shared.simulation.set(Some(simulation));
+        result
+    }
+
+    /// Get the current simulation time.
+    ///
+    /// # Panics
+    ///
+    /// As with [`with_simulation()`], this method panics if called from outside the process's own future.
+    ///
+    /// [`with_simulation()`]: ProcessContext::with_simulation
+    pub fn now(&self) -> Time
+    where
+        Time: Clone,
+    {
+        self.with_simulation(|simulation| simulation.current_time().clone())
+    }
+
+    /// Suspend the process for the provided delay, resuming once that much simulation time has passed.
+    pub fn timeout(&self, delay: Time) -> Wait<State, Time, Backend> {
+        self.wait(Yield::Hold(delay))
+    }
+
+    /// Suspend the process until the provided predicate holds for the simulation's state, as with [`Yield::WaitFor`].
+    pub fn wait_for<Predicate>(&self, predicate: Predicate) -> Wait<State, Time, Backend>
+    where
+        Predicate: Fn(&State) -> bool + 'static,
+    {
+        self.wait(Yield::wait_for(predicate))
+    }
+
+    /// Suspend the process until a unit of the selected resource is granted, as with [`Yield::Request`]. The process
+    /// holds the unit from then on, and should release it through [`Simulation::release_resource()`].
+    pub fn request<Select>(&self, resource: Select) -> Wait<State, Time, Backend>
+    where
+        Select: FnOnce(&mut State) -> &mut Resource<Resume<State, Time, Backend>> + 'static,
+    {
+        self.wait(Yield::request(resource))
+    }
+
+    /// Suspend the process until it is [activated].
+    ///
+    /// [activated]: crate::serial::Simulation::activate
+    pub fn passivate(&self) -> Wait<State, Time, Backend> {
+        self.wait(Yield::Passivate)
+    }
+
+    fn wait(&self, request: Yield<State, Time, Backend>) -> Wait<State, Time, Backend> {
+        Wait {
+            shared: Rc::clone(&self.shared),
+            request: Some(request),
+        }
+    }
+}
+
+impl<State, Time, Backend> Debug for ProcessContext<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.write_str("ProcessContext")
+    }
+}
+
+/// A future that suspends an async process until the simulation resumes it. Created by the methods of
+/// [`ProcessContext`].
+#[must_use = "futures do nothing unless awaited"]
+pub struct Wait<State, Time, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    shared: Rc<Shared<State, Time, Backend>>,
+    /// Handed to the task on the first poll.
+    request: Option<Yield<State, Time, Backend>>,
+}
+
+// no field is ever pinned
+impl<State, Time, Backend> Unpin for Wait<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+}
+
+impl<State, Time, Backend> Future for Wait<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    type Output = ();
+
+    /// Request the wait from the task on the first poll. The task only polls the process again once the wait is over,
+    /// so the second poll completes.
+    ///
+    /// # Panics
+    ///
+    /// A process can only wait for one thing at a time, so this method panics if another wait has been requested during
+    /// the same poll of the process, e.g. by joining two of these futures.
+    fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+        let this = self.get_mut();
+        match this.request.take() {
+            Some(request) => {
+                let previous = this.shared.request.replace(Some(request));
+                assert!(previous.is_none(), "a process should only wait for one thing at a time");
+                Poll::Pending
+            },
+            None => Poll::Ready(()),
+        }
+    }
+}
+
+impl<State, Time, Backend> Debug for Wait<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Wait").field("request", &self.request).finish()
+    }
+}
+
+/// An async process, driven as a [`Process`] whose resumptions each poll the future once.
+pub(crate) struct Task<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    future: Pin<Box<dyn Future<Output = crate::Result>>>,
+    shared: Rc<Shared<State, Time, Backend>>,
+    /// The name of the function that created the future, as the future's own type is unnameable.
+    name: &'static str,
+}
+
+impl<State, Time, Backend> Task<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    pub fn new<ProcessFn, Fut>(process: ProcessFn) -> Self
+    where
+        ProcessFn: FnOnce(ProcessContext<State, Time, Backend>) -> Fut,
+        Fut: Future<Output = crate::Result> + 'static,
+    {
+        let shared = Rc::new(Shared {
+            simulation: Cell::new(None),
+            request: Cell::new(None),
+        });
+        let context = ProcessContext {
+            shared: Rc::clone(&shared),
+        };
+        Self {
+            future: Box::pin(process(context)),
+            shared,
+            name: std::any::type_name::<ProcessFn>(),
+        }
+    }
+}
+
+impl<State, Time, Backend> Process<State, Time, Backend> for Task<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Poll the future once, translating the wait it requests into a [`Yield`]. A future that completes finishes the
+    /// process, and one that returns pending without requesting a wait leaves it passive.
+    fn resume(
+        &mut self,
+        simulation: &mut Simulation<State, Time, Backend>,
+    ) -> crate::Result<Yield<State, Time, Backend>> {
+        self.shared.simulation.set(Some(NonNull::from(simulation)));
+        let waker = noop_waker();
+        let poll = self.future.as_mut().poll(&mut Context::from_waker(&waker));
+        self.shared.simulation.set(None);
+
+        let request = self.shared.request.take();
+        match poll {
+            Poll::Ready(result) => result.map(|()| Yield::Finish),
+            Poll::Pending => Ok(request.unwrap_or(Yield::Passivate)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+
+impl<State, Time, Backend> Debug for Task<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_tuple("Task").field(&self.name).finish()
+    }
+}
+
+/// Create a waker that does nothing, as processes are woken by scheduled events instead.
+fn noop_waker() -> Waker {
+    fn clone(_: *const ()) -> RawWaker {
+        RawWaker::new(std::ptr::null(), &VTABLE)
+    }
+    fn noop(_: *const ()) {}
+    static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+
+    // SAFETY: every function in the vtable ignores the data pointer, so the contract is trivially upheld.
+    unsafe { Waker::from_raw(RawWaker::new(std::ptr::null(), &VTABLE)) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct State {
+        log: Vec<(u32, &'static str)>,
+        open: bool,
+    }
+
+    impl SimState<u32> for State {}
+
+    fn record(context: &ProcessContext<State, u32>, entry: &'static str) {
+        context.with_simulation(|sim| {
+            let now = *sim.current_time();
+            sim.state_mut().log.push((now, entry));
+        });
+    }
+
+    #[test]
+    fn async_processes_interleave_with_events() {
+        let mut sim = Simulation::new(State::default(), 0);
+        sim.spawn(
+            |context| async move {
+                record(&context, "started");
+                context.timeout(3).await;
+                record(&context, "held");
+                context.wait_for(|state: &State| state.open).await;
+                record(&context, "entered");
+                Ok(())
+            },
+            1,
+        )
+        .unwrap();
+        sim.schedule_fn(
+            |sim| {
+                sim.state_mut().open = true;
+                Ok(())
+            },
+            6,
+        )
+        .unwrap();
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![(1, "started"), (4, "held"), (6, "entered")],
+            sim.state().log,
+            "process should resume after each wait"
+        );
+    }
+
+    #[test]
+    fn passive_async_processes_resume_when_activated() {
+        let mut sim = Simulation::new(State::default(), 0);
+        let handle = sim
+            .spawn(
+                |context| async move {
+                    context.passivate().await;
+                    record(&context, "activated");
+                    Ok(())
+                },
+                0,
+            )
+            .unwrap();
+        sim.schedule_fn(move |sim| sim.activate(handle).map(|_| ()), 2).unwrap();
+
+        sim.run().unwrap();
+        assert_eq!(vec![(2, "activated")], sim.state().log);
+    }
+
+    #[test]
+    fn async_process_errors_are_reported() {
+        let mut sim = Simulation::new(State::default(), 0);
+        sim.spawn(
+            |context: ProcessContext<State, u32>| async move {
+                context.timeout(1).await;
+                Err(crate::Error::EventNotFound)
+            },
+            0,
+        )
+        .unwrap();
+
+        assert_eq!(Err(crate::Error::EventNotFound), sim.run());
+        assert_eq!(1, *sim.current_time());
+    }
+
+    #[derive(Debug)]
+    struct Shop {
+        server: Resource<Resume<Shop, u32>>,
+        log: Vec<(u32, &'static str, usize)>,
+    }
+
+    impl SimState<u32> for Shop {}
+
+    async fn customer(context: ProcessContext<Shop, u32>, id: usize) -> crate::Result {
+        let log = |entry| {
+            context.with_simulation(|sim| {
+                let now = *sim.current_time();
+                sim.state_mut().log.push((now, entry, id));
+            })
+        };
+
+        context.request(|shop: &mut Shop| &mut shop.server).await;
+        log("started");
+        context.timeout(5).await;
+        log("finished");
+        context
+            .with_simulation(|sim| sim.release_resource(|shop| &mut shop.server))
+            .map(|_| ())
+    }
+
+    #[test]
+    fn processes_wait_in_line_for_resources() {
+        let shop = Shop {
+            server: Resource::new(1),
+            log: Vec::new(),
+        };
+        let mut sim = Simulation::new(shop, 0);
+        sim.spawn(|context| customer(context, 0), 0).unwrap();
+        sim.spawn(|context| customer(context, 1), 1).unwrap();
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![
+                (0, "started", 0),
+                (5, "finished", 0),
+                (5, "started", 1),
+                (10, "finished", 1),
+            ],
+            sim.state().log,
+            "second process should start once the first releases the server"
+        );
+        assert_eq!(0, sim.state().server.in_use());
+        assert_eq!(2, sim.state().server.grants());
+    }
+
+    /// Polls both of its futures on each poll, as a join would.
+    struct Both(Wait<State, u32>, Wait<State, u32>);
+
+    impl Future for Both {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            let this = self.get_mut();
+            let first = Pin::new(&mut this.0).poll(cx);
+            let second = Pin::new(&mut this.1).poll(cx);
+            if first.is_ready() && second.is_ready() {
+                Poll::Ready(())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "one thing at a time")]
+    fn processes_cannot_wait_twice_at_once() {
+        let mut sim = Simulation::new(State::default(), 0);
+        sim.spawn(
+            |context: ProcessContext<State, u32>| async move {
+                Both(context.timeout(1), context.timeout(2)).await;
+                Ok(())
+            },
+            0,
+        )
+        .unwrap();
+
+        let _ = sim.run();
+    }
+}
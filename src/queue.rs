@@ -0,0 +1,277 @@
+//! Priority queue implementations that determine the order in which a simulation executes its events.
+//!
+//! Every [`serial::Simulation`], [`threadsafe::Simulation`], and [`inline::Simulation`] keeps its scheduled events on
+//! an internal queue. The events themselves are stored separately from the data structure that orders them, which holds
+//! only an [`EventKey`] for each event: its execution time, its priority, and its insertion sequence. The
+//! [`EventQueueBackend`] trait describes that ordering structure, and a simulation is generic over which implementation
+//! it uses.
+//!
+//! By default, simulations use a [`BinaryHeapQueue`], which performs well for a wide variety of workloads. This module
+//! also provides alternatives suited to more specialized workloads:
+//!
+//! * [`PairingHeapQueue`] offers constant-time insertion, which may help for models that schedule many events that are
+//!   later cancelled.
+//! * [`CalendarQueue`] sorts events into buckets by execution time, offering expected constant-time operations for
+//!   large queues whose execution times are spread fairly evenly. It requires a clock that implements [`NumericTime`].
+//! * [`LadderQueue`] spreads events across layers of buckets, offering amortized constant-time operations for large
+//!   queues whose execution times are clustered. It requires a clock that implements [`NumericTime`].
+//! * [`RadixHeapQueue`] exploits the fact that a simulation's clock never runs backwards, offering fast operations for
+//!   integral clocks that implement [`RadixKey`].
+//!
+//! Choose a backend by passing it to `with_backend()` when constructing a simulation:
+//!
+//! ```
+//! # use desque::serial::{OkEvent, Simulation};
+//! # use desque::queue::LadderQueue;
+//! # use desque::SimState;
+//! # #[derive(Debug)]
+//! # struct State;
+//! # impl SimState<u64> for State {}
+//! #[derive(Debug)]
+//! struct Arrival;
+//!
+//! impl OkEvent<State, u64, LadderQueue<u64>> for Arrival {
+//!     fn execute(&mut self, sim: &mut Simulation<State, u64, LadderQueue<u64>>) {
+//!         // ...
+//!     }
+//! }
+//!
+//! let mut sim = Simulation::with_backend(State, 0, LadderQueue::new());
+//! sim.schedule(Arrival, 5)?;
+//! sim.run()?;
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! Events must name the backend of the simulation they execute in, as seen above. Events that should work with any
+//! backend may instead be generic over it.
+//!
+//! [`serial::Simulation`]: crate::serial::Simulation
+//! [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+//! [`inline::Simulation`]: crate::inline::Simulation
+//! [`NumericTime`]: crate::NumericTime
+
+mod binary_heap;
+mod calendar_queue;
+mod ladder_queue;
+mod pairing_heap;
+mod radix_heap;
+
+pub use binary_heap::BinaryHeapQueue;
+pub use calendar_queue::CalendarQueue;
+pub use ladder_queue::LadderQueue;
+pub use pairing_heap::PairingHeapQueue;
+pub use radix_heap::{RadixHeapQueue, RadixKey};
+
+use crate::{EventHandle, SimTime};
+use std::cmp::Ordering;
+
+/// The data by which events are ordered on a simulation's queue.
+///
+/// Keys compare first by execution time, giving full control of event ordering to client code. Ties in execution time
+/// are broken by priority, such that keys with a higher priority sort as lesser and so execute first, and then by
+/// insertion sequence, such that events scheduled earlier execute first. As each event receives a unique insertion
+/// sequence, no two keys on a queue will ever compare as equal.
+///
+/// Keys are only created by the simulation itself, and an [`EventQueueBackend`] need only store them and hand them back
+/// in ascending order.
+#[derive(Debug, Clone)]
+pub struct EventKey<Time>
+where
+    Time: SimTime,
+{
+    execution_time: Time,
+    priority: i32,
+    insertion_sequence: usize,
+}
+
+impl<Time> EventKey<Time>
+where
+    Time: SimTime,
+{
+    pub(crate) fn new(execution_time: Time, priority: i32, insertion_sequence: usize) -> Self {
+        Self {
+            execution_time,
+            priority,
+            insertion_sequence,
+        }
+    }
+
+    /// Get a shared reference to the time at which the event is scheduled to execute.
+    pub fn execution_time(&self) -> &Time {
+        &self.execution_time
+    }
+
+    /// Take ownership of the time at which the event is scheduled to execute.
+    pub fn into_execution_time(self) -> Time {
+        self.execution_time
+    }
+
+    /// Get the priority the event was scheduled with.
+    pub fn priority(&self) -> i32 {
+        self.priority
+    }
+
+    /// Get the insertion sequence of the event, which is unique within a simulation.
+    pub fn insertion_sequence(&self) -> usize {
+        self.insertion_sequence
+    }
+
+    /// Get the handle that was returned when the event was scheduled.
+    pub fn handle(&self) -> EventHandle {
+        EventHandle::new(self.insertion_sequence)
+    }
+}
+
+impl<Time> PartialEq for EventKey<Time>
+where
+    Time: SimTime,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.insertion_sequence == other.insertion_sequence
+            && self.priority == other.priority
+            && self.execution_time == other.execution_time
+    }
+}
+
+impl<Time> Eq for EventKey<Time> where Time: SimTime {}
+
+impl<Time> PartialOrd for EventKey<Time>
+where
+    Time: SimTime,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<Time> Ord for EventKey<Time>
+where
+    Time: SimTime,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.execution_time
+            .cmp(&other.execution_time)
+            // higher priorities execute first, and so sort as lesser
+            .then_with(|| other.priority.cmp(&self.priority))
+            .then_with(|| self.insertion_sequence.cmp(&other.insertion_sequence))
+    }
+}
+
+/// A priority queue of [`EventKey`]s, used by a simulation to decide which event to execute next.
+///
+/// Implementations must hand keys back from [`pop()`] in ascending order according to the implementation of [`Ord`] on
+/// [`EventKey`], regardless of the order in which they were pushed. The simulation checks that events are not scheduled
+/// for times earlier than its clock, but the unchecked scheduling methods allow client code to bypass that check, and
+/// so implementations must also remain correct if a key is pushed that is lesser than the last key popped.
+///
+/// The simulation discards the keys of cancelled events lazily, popping them once they reach the front of the queue.
+/// Implementations therefore need not support removing arbitrary keys.
+///
+/// [`pop()`]: EventQueueBackend::pop
+pub trait EventQueueBackend<Time>
+where
+    Time: SimTime,
+{
+    /// Add a key to the queue.
+    fn push(&mut self, key: EventKey<Time>);
+
+    /// Remove the least key from the queue and return it, or `None` if the queue is empty.
+    fn pop(&mut self) -> Option<EventKey<Time>>;
+
+    /// Get a shared reference to the least key on the queue, or `None` if the queue is empty.
+    fn peek(&self) -> Option<&EventKey<Time>>;
+
+    /// Get the number of keys on the queue.
+    fn len(&self) -> usize;
+
+    /// Get whether the queue holds no keys.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Iterate over every key on the queue, in an arbitrary order.
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use rand_pcg::Pcg64;
+
+    /// Drive a backend through a long, random sequence of pushes and pops, checking each pop against a sorted
+    /// reference. Execution times advance as a simulation's clock would, with occasional keys scheduled into the past.
+    pub(super) fn check_backend<Backend>(mut backend: Backend)
+    where
+        Backend: EventQueueBackend<u64>,
+    {
+        let mut rng = Pcg64::seed_from_u64(6_942_451_305_273_114_027);
+        let mut reference = std::collections::BTreeSet::new();
+        let mut now = 1_000_u64;
+        let mut sequence = 0;
+
+        for round in 0..20_000 {
+            let pushes = if round % 1000 < 500 { 2 } else { rng.random_range(0..2) };
+            for _ in 0..pushes {
+                let time = match rng.random_range(0..20) {
+                    0 => now.saturating_sub(rng.random_range(0..50)),
+                    1..=4 => now,
+                    5..=16 => now + rng.random_range(0..100),
+                    _ => now + rng.random_range(0..1_000_000),
+                };
+                let key = EventKey::new(time, rng.random_range(-1..2), sequence);
+                sequence += 1;
+                reference.insert(key.clone());
+                backend.push(key);
+            }
+
+            assert_eq!(reference.len(), backend.len(), "backend should count its keys");
+            assert_eq!(
+                reference.iter().next(),
+                backend.peek(),
+                "backend should peek at least key"
+            );
+            if rng.random_range(0..3) > 0 {
+                let expected = pop_first(&mut reference);
+                let actual = backend.pop();
+                assert_eq!(expected, actual, "backend should pop keys in ascending order");
+                if let Some(key) = actual {
+                    now = *key.execution_time();
+                }
+            }
+        }
+
+        let mut held: Vec<_> = backend.iter().cloned().collect();
+        held.sort();
+        assert_eq!(
+            reference.iter().cloned().collect::<Vec<_>>(),
+            held,
+            "backend should iterate over every key"
+        );
+
+        while let Some(expected) = pop_first(&mut reference) {
+            assert_eq!(Some(expected), backend.pop(), "backend should drain in ascending order");
+        }
+        assert!(backend.is_empty(), "backend should be empty once drained");
+        assert_eq!(None, backend.peek(), "empty backend should have nothing to peek");
+    }
+
+    fn pop_first(reference: &mut std::collections::BTreeSet<EventKey<u64>>) -> Option<EventKey<u64>> {
+        let first = reference.iter().next()?.clone();
+        reference.take(&first)
+    }
+
+    #[test]
+    fn keys_sort_by_time_then_priority_then_sequence() {
+        let mut keys = [
+            EventKey::new(2, 0, 0),
+            EventKey::new(1, 0, 1),
+            EventKey::new(1, 5, 2),
+            EventKey::new(1, 0, 3),
+            EventKey::new(1, -5, 4),
+        ];
+        keys.sort();
+        let sequences: Vec<_> = keys.iter().map(EventKey::insertion_sequence).collect();
+        assert_eq!(vec![2, 1, 3, 4, 0], sequences, "keys did not sort in expected order");
+    }
+}
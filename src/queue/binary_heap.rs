@@ -0,0 +1,72 @@
+use super::{EventKey, EventQueueBackend};
+use crate::SimTime;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// An [`EventQueueBackend`] built on [`std::collections::BinaryHeap`], and the default backend for simulations.
+///
+/// Both pushing and popping keys take logarithmic time in the number of keys on the queue. This backend places no
+/// requirements on the clock type beyond those of [`SimTime`], and performs well for a wide variety of workloads.
+#[derive(Debug)]
+pub struct BinaryHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    heap: BinaryHeap<Reverse<EventKey<Time>>>,
+}
+
+impl<Time> BinaryHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    /// Construct an empty queue.
+    pub fn new() -> Self {
+        Self {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<Time> Default for BinaryHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time> EventQueueBackend<Time> for BinaryHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    fn push(&mut self, key: EventKey<Time>) {
+        self.heap.push(Reverse(key));
+    }
+
+    fn pop(&mut self) -> Option<EventKey<Time>> {
+        self.heap.pop().map(|key| key.0)
+    }
+
+    fn peek(&self) -> Option<&EventKey<Time>> {
+        self.heap.peek().map(|key| &key.0)
+    }
+
+    fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_> {
+        Box::new(self.heap.iter().map(|key| &key.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pop_in_order() {
+        super::super::tests::check_backend(BinaryHeapQueue::new());
+    }
+}
@@ -0,0 +1,247 @@
+use super::{EventKey, EventQueueBackend};
+use crate::NumericTime;
+use std::collections::VecDeque;
+use std::fmt::Formatter;
+
+/// The fewest buckets a calendar will shrink to.
+const MIN_BUCKETS: usize = 2;
+
+/// The number of keys from the front of the queue sampled when choosing a new bucket width.
+const WIDTH_SAMPLE_SIZE: usize = 25;
+
+/// An [`EventQueueBackend`] built on a calendar queue (Brown, 1988), for clocks that implement [`NumericTime`].
+///
+/// A calendar queue divides time into "days" of equal width and assigns each key to a bucket by its day, wrapping
+/// around the array of buckets like the pages of a desk calendar wrap around the year. Each bucket is kept sorted, and
+/// popping a key scans forward from the bucket of the last key popped for one whose day has arrived. When the days are
+/// sized so that each holds only a handful of keys, both pushing and popping take expected constant time. That
+/// advantage only outweighs the overhead of selecting buckets once the queue holds thousands of keys, so prefer the
+/// default [`BinaryHeapQueue`] for smaller models.
+///
+/// The calendar resizes itself as the queue grows and shrinks, doubling or halving its number of buckets and choosing a
+/// new width for its days from the spacing of the keys nearest the front of the queue. No tuning is necessary, though
+/// workloads whose execution times are tightly clustered among a few distant outliers may be better served by a
+/// [`LadderQueue`].
+///
+/// Execution times are converted to [`f64`] only to select buckets; keys within a bucket are sorted with the full
+/// implementation of [`Ord`] on [`EventKey`], and so precision lost in that conversion cannot reorder events.
+///
+/// [`BinaryHeapQueue`]: super::BinaryHeapQueue
+/// [`LadderQueue`]: super::LadderQueue
+pub struct CalendarQueue<Time>
+where
+    Time: NumericTime,
+{
+    /// Sorted keys, each bucket in ascending order. Keys pushed for the same time as others land at the back of the
+    /// bucket, keeping insertion cheap when many events share an execution time.
+    buckets: Vec<VecDeque<EventKey<Time>>>,
+    width: f64,
+    /// The day being scanned for the next key to pop. No key on the queue falls on an earlier day.
+    current_day: f64,
+    len: usize,
+}
+
+impl<Time> CalendarQueue<Time>
+where
+    Time: NumericTime,
+{
+    /// Construct an empty queue.
+    pub fn new() -> Self {
+        Self {
+            buckets: (0..MIN_BUCKETS).map(|_| VecDeque::new()).collect(),
+            width: 1.0,
+            current_day: f64::NEG_INFINITY,
+            len: 0,
+        }
+    }
+
+    /// Get the day on which a key falls.
+    fn day(&self, key: &EventKey<Time>) -> f64 {
+        (key.execution_time().to_f64() / self.width).floor()
+    }
+
+    /// Get the bucket that holds the keys falling on a day.
+    fn bucket(&self, day: f64) -> usize {
+        // the number of buckets is always a power of two, so masking the day's two's complement representation wraps
+        // negative days correctly, while float-to-int casts saturate so that extreme days still land in a valid bucket
+        (day as i64 as usize) & (self.buckets.len() - 1)
+    }
+
+    /// Insert a key into the sorted bucket for its day, without resizing.
+    fn insert(&mut self, day: f64, key: EventKey<Time>) {
+        let bucket = self.bucket(day);
+        let bucket = &mut self.buckets[bucket];
+        let index = bucket.partition_point(|other| *other < key);
+        bucket.insert(index, key);
+    }
+
+    /// Advance `current_day` to the day of the least key, so that key is found at the front of the current bucket.
+    fn advance(&mut self) {
+        if self.len == 0 {
+            return;
+        }
+
+        // a full lap of the calendar without finding a key means the next one lies more than a year ahead
+        for _ in 0..self.buckets.len() {
+            let bucket = self.bucket(self.current_day);
+            if let Some(key) = self.buckets[bucket].front() {
+                if self.day(key) <= self.current_day {
+                    return;
+                }
+            }
+            self.current_day += 1.0;
+        }
+
+        let least = self
+            .buckets
+            .iter()
+            .filter_map(|bucket| bucket.front())
+            .min()
+            .expect("calendar should not be empty");
+        self.current_day = self.day(least);
+    }
+
+    /// Rebuild the calendar with the provided number of buckets and a width fitted to the keys at the front of the
+    /// queue.
+    fn resize(&mut self, bucket_count: usize) {
+        // drain rather than drop the buckets, so that their allocations may be reused
+        let mut keys = Vec::with_capacity(self.len);
+        for bucket in self.buckets.iter_mut() {
+            keys.extend(bucket.drain(..));
+        }
+        self.width = Self::fit_width(&mut keys).unwrap_or(self.width);
+        self.buckets.resize_with(bucket_count, VecDeque::new);
+
+        self.current_day = f64::INFINITY;
+        for key in keys {
+            let day = self.day(&key);
+            self.current_day = self.current_day.min(day);
+            self.insert(day, key);
+        }
+    }
+
+    /// Choose a width for the days of the calendar, following Brown's heuristic of three times the average separation
+    /// between the keys at the front of the queue once unusually large separations are excluded. Returns `None` if the
+    /// sampled keys do not provide a usable width.
+    fn fit_width(keys: &mut [EventKey<Time>]) -> Option<f64> {
+        let sample_size = keys.len().min(WIDTH_SAMPLE_SIZE);
+        if sample_size < 2 {
+            return None;
+        }
+        if sample_size < keys.len() {
+            keys.select_nth_unstable(sample_size - 1);
+        }
+
+        let mut sample: Vec<_> = keys[..sample_size]
+            .iter()
+            .map(|key| key.execution_time().to_f64())
+            .collect();
+        sample.sort_unstable_by(f64::total_cmp);
+        let separations: Vec<_> = sample.windows(2).map(|pair| pair[1] - pair[0]).collect();
+
+        let average = separations.iter().sum::<f64>() / separations.len() as f64;
+        let (total, count) = separations
+            .iter()
+            .filter(|separation| **separation <= 2.0 * average)
+            .fold((0.0, 0), |(total, count), separation| (total + separation, count + 1));
+        let width = 3.0 * total / count as f64;
+        (width > 0.0 && width.is_finite()).then_some(width)
+    }
+}
+
+impl<Time> Default for CalendarQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time> EventQueueBackend<Time> for CalendarQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn push(&mut self, key: EventKey<Time>) {
+        // keep the least key at the front of the current bucket so that peeking needs only shared access, which only
+        // requires moving the scan back when the new key is the least; that is only possible for keys scheduled before
+        // the current day through the unchecked scheduling methods, or when the queue was empty
+        let day = self.day(&key);
+        if self.len == 0 || day < self.current_day {
+            self.current_day = day;
+        }
+        self.insert(day, key);
+        self.len += 1;
+
+        if self.len > 2 * self.buckets.len() {
+            self.resize(2 * self.buckets.len());
+        }
+    }
+
+    fn pop(&mut self) -> Option<EventKey<Time>> {
+        let bucket = self.bucket(self.current_day);
+        let key = self.buckets[bucket].pop_front()?;
+        self.len -= 1;
+
+        if self.len < self.buckets.len() / 2 && self.buckets.len() > MIN_BUCKETS {
+            self.resize(self.buckets.len() / 2);
+        }
+
+        self.advance();
+        Some(key)
+    }
+
+    fn peek(&self) -> Option<&EventKey<Time>> {
+        if self.len == 0 {
+            return None;
+        }
+        self.buckets[self.bucket(self.current_day)].front()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_> {
+        Box::new(self.buckets.iter().flatten())
+    }
+}
+
+impl<Time> std::fmt::Debug for CalendarQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("CalendarQueue")
+            .field("buckets", &self.buckets.len())
+            .field("width", &self.width)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pop_in_order() {
+        super::super::tests::check_backend(CalendarQueue::new());
+    }
+
+    #[test]
+    fn calendar_resizes_with_queue() {
+        let mut queue = CalendarQueue::new();
+        for sequence in 0..1_000 {
+            queue.push(EventKey::new(sequence as u64 * 3, 0, sequence));
+        }
+        assert_eq!(512, queue.buckets.len(), "calendar should grow to hold keys");
+        assert_eq!(9.0, queue.width, "width should be three times the spacing between keys");
+
+        for _ in 0..990 {
+            queue.pop();
+        }
+        assert!(queue.buckets.len() <= 32, "calendar should shrink as keys are popped");
+        assert_eq!(Some(2_970), queue.peek().map(|key| *key.execution_time()));
+    }
+}
@@ -0,0 +1,314 @@
+use super::{EventKey, EventQueueBackend};
+use crate::NumericTime;
+use std::fmt::Formatter;
+
+/// A bucket holding more keys than this is split into a new rung rather than sorted, and a bottom tier that grows past
+/// this through insertions is moved onto a new rung, if the ladder has room.
+const SPLIT_THRESHOLD: usize = 50;
+
+/// The most rungs the ladder may hold at once. Buckets reached beyond this depth are sorted regardless of their size.
+const MAX_RUNGS: usize = 8;
+
+/// A layer of the ladder, dividing an interval of execution times into equally sized buckets.
+///
+/// Buckets before `current` have already been emptied into the bottom or into a child rung.
+struct Rung<Time>
+where
+    Time: NumericTime,
+{
+    start: f64,
+    width: f64,
+    current: usize,
+    buckets: Vec<Vec<EventKey<Time>>>,
+}
+
+impl<Time> Rung<Time>
+where
+    Time: NumericTime,
+{
+    /// Spread the provided keys across a new rung with one bucket per key. Hands the keys back if their execution
+    /// times are too close together to be divided.
+    fn spawn(keys: Vec<EventKey<Time>>) -> Result<Self, Vec<EventKey<Time>>> {
+        let (min, max) = keys.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), key| {
+            let time = key.execution_time().to_f64();
+            (min.min(time), max.max(time))
+        });
+        let width = (max - min) / keys.len() as f64;
+        if !(width > 0.0 && width.is_finite()) {
+            return Err(keys);
+        }
+
+        let mut rung = Self {
+            start: min,
+            width,
+            current: 0,
+            buckets: (0..keys.len()).map(|_| Vec::new()).collect(),
+        };
+        for key in keys {
+            let position = rung.position(key.execution_time().to_f64());
+            rung.insert(position, key);
+        }
+        Ok(rung)
+    }
+
+    /// Get the fractional bucket position of a time, which increases monotonically with the time.
+    fn position(&self, time: f64) -> f64 {
+        (time - self.start) / self.width
+    }
+
+    /// Get whether a time at the given position belongs to this rung rather than to a lower rung or the bottom, i.e.
+    /// whether its bucket has yet to be emptied.
+    fn accepts(&self, position: f64) -> bool {
+        position >= 0.0 && self.bucket(position) >= self.current
+    }
+
+    /// Get the bucket for a position. Positions beyond the last bucket are clamped into it, as the times that reach
+    /// this rung are bounded by the parent bucket that spawned it.
+    fn bucket(&self, position: f64) -> usize {
+        // float-to-int casts saturate, so positions beyond the range of usize still land in the last bucket
+        (position as usize).min(self.buckets.len() - 1)
+    }
+
+    /// Place a key in the bucket for its position.
+    fn insert(&mut self, position: f64, key: EventKey<Time>) {
+        let bucket = self.bucket(position);
+        self.buckets[bucket].push(key);
+    }
+
+    /// Advance past empty buckets and take the contents of the next nonempty bucket, if any remain.
+    fn take_next_bucket(&mut self) -> Option<Vec<EventKey<Time>>> {
+        while self.current < self.buckets.len() {
+            let bucket = std::mem::take(&mut self.buckets[self.current]);
+            self.current += 1;
+            if !bucket.is_empty() {
+                return Some(bucket);
+            }
+        }
+        None
+    }
+}
+
+/// An [`EventQueueBackend`] built on a ladder queue (Tang, Goh, and Thng, 2005), for clocks that implement
+/// [`NumericTime`].
+///
+/// A ladder queue keeps three tiers of keys. Keys for the distant future accumulate unsorted in the top tier. Once the
+/// nearer tiers empty out, the top tier is spread across a rung of buckets, each spanning an equal interval of time;
+/// buckets holding too many keys are in turn spread across a finer rung beneath them. The nearest bucket is finally
+/// sorted into the bottom tier, from which keys are popped. Each key is therefore touched a small number of times
+/// regardless of how many keys are on the queue, giving amortized constant-time operations that excel for large queues
+/// of clustered execution times.
+///
+/// Bucket widths adapt to the execution times on the queue, so no tuning is necessary. Execution times are converted to
+/// [`f64`] only to select buckets; keys within a bucket are sorted with the full implementation of [`Ord`] on
+/// [`EventKey`], and so precision lost in that conversion cannot reorder events.
+pub struct LadderQueue<Time>
+where
+    Time: NumericTime,
+{
+    /// Unsorted keys whose times lie beyond `top_start`.
+    top: Vec<EventKey<Time>>,
+    top_start: f64,
+    rungs: Vec<Rung<Time>>,
+    /// Sorted keys, in descending order so that the least key can be popped from the end.
+    bottom: Vec<EventKey<Time>>,
+    len: usize,
+}
+
+impl<Time> LadderQueue<Time>
+where
+    Time: NumericTime,
+{
+    /// Construct an empty queue.
+    pub fn new() -> Self {
+        Self {
+            top: Vec::new(),
+            top_start: f64::NEG_INFINITY,
+            rungs: Vec::new(),
+            bottom: Vec::new(),
+            len: 0,
+        }
+    }
+
+    /// Insert a key into the sorted bottom tier, moving the bottom tier onto a new rung once it grows too large to keep
+    /// insertions cheap.
+    fn insert_bottom(&mut self, key: EventKey<Time>) {
+        let index = self.bottom.partition_point(|other| *other > key);
+        self.bottom.insert(index, key);
+
+        if self.bottom.len() > SPLIT_THRESHOLD && self.rungs.len() < MAX_RUNGS && self.bottom_spans_interval() {
+            // every key in the bottom tier precedes every key on the rungs, so the new rung belongs beneath them all
+            match Rung::spawn(std::mem::take(&mut self.bottom)) {
+                Ok(rung) => self.rungs.push(rung),
+                Err(keys) => self.bottom = keys,
+            }
+        }
+    }
+
+    /// Get whether the keys in the bottom tier have distinct execution times, and so can be spread across a rung.
+    fn bottom_spans_interval(&self) -> bool {
+        match (self.bottom.first(), self.bottom.last()) {
+            (Some(greatest), Some(least)) => greatest.execution_time().to_f64() > least.execution_time().to_f64(),
+            _ => false,
+        }
+    }
+
+    /// Sort a batch of keys into the bottom tier, which must be empty.
+    fn fill_bottom(&mut self, mut keys: Vec<EventKey<Time>>) {
+        keys.sort_unstable_by(|a, b| b.cmp(a));
+        self.bottom = keys;
+    }
+
+    /// Work down the ladder until the bottom tier holds the least key, if the queue is not empty.
+    fn refill_bottom(&mut self) {
+        while self.bottom.is_empty() {
+            let rung = match self.rungs.last_mut() {
+                Some(rung) => rung,
+                None => {
+                    if self.top.is_empty() {
+                        return;
+                    }
+
+                    let keys = std::mem::take(&mut self.top);
+                    self.top_start = keys
+                        .iter()
+                        .map(|key| key.execution_time().to_f64())
+                        .fold(f64::NEG_INFINITY, f64::max);
+                    if keys.len() <= SPLIT_THRESHOLD {
+                        self.fill_bottom(keys);
+                    } else {
+                        match Rung::spawn(keys) {
+                            Ok(rung) => self.rungs.push(rung),
+                            Err(keys) => self.fill_bottom(keys),
+                        }
+                    }
+                    continue;
+                },
+            };
+
+            let bucket = match rung.take_next_bucket() {
+                Some(bucket) => bucket,
+                None => {
+                    self.rungs.pop();
+                    continue;
+                },
+            };
+
+            if bucket.len() > SPLIT_THRESHOLD && self.rungs.len() < MAX_RUNGS {
+                match Rung::spawn(bucket) {
+                    Ok(rung) => self.rungs.push(rung),
+                    Err(bucket) => self.fill_bottom(bucket),
+                }
+            } else {
+                self.fill_bottom(bucket);
+            }
+        }
+    }
+}
+
+impl<Time> Default for LadderQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time> EventQueueBackend<Time> for LadderQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn push(&mut self, key: EventKey<Time>) {
+        self.len += 1;
+        let time = key.execution_time().to_f64();
+        if time > self.top_start {
+            self.top.push(key);
+        } else {
+            match self.rungs.iter_mut().find_map(|rung| {
+                let position = rung.position(time);
+                rung.accepts(position).then_some((rung, position))
+            }) {
+                Some((rung, position)) => rung.insert(position, key),
+                None => self.insert_bottom(key),
+            }
+        }
+
+        // keep the least key in the bottom tier so that peeking needs only shared access
+        self.refill_bottom();
+    }
+
+    fn pop(&mut self) -> Option<EventKey<Time>> {
+        let key = self.bottom.pop()?;
+        self.len -= 1;
+        self.refill_bottom();
+        Some(key)
+    }
+
+    fn peek(&self) -> Option<&EventKey<Time>> {
+        self.bottom.last()
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_> {
+        Box::new(
+            self.bottom
+                .iter()
+                .chain(self.rungs.iter().flat_map(|rung| rung.buckets.iter().flatten()))
+                .chain(self.top.iter()),
+        )
+    }
+}
+
+impl<Time> std::fmt::Debug for LadderQueue<Time>
+where
+    Time: NumericTime,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("LadderQueue")
+            .field("top", &self.top.len())
+            .field("rungs", &self.rungs.len())
+            .field("bottom", &self.bottom.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pop_in_order() {
+        super::super::tests::check_backend(LadderQueue::new());
+    }
+
+    #[test]
+    fn bursts_into_current_bucket_spill_onto_new_rungs() {
+        let mut queue = LadderQueue::new();
+        let mut sequence = 0;
+        for time in (0..1000).map(|time| time * 1000) {
+            queue.push(EventKey::new(time, 0, sequence));
+            sequence += 1;
+        }
+        assert_eq!(Some(0), queue.pop().map(|key| *key.execution_time()));
+
+        // a burst of near-future keys, all within the bucket currently sorted into the bottom tier
+        let mut expected: Vec<_> = (1..1000).map(|time| time * 1000).collect();
+        for offset in 0..2000_u64 {
+            let time = offset * 7919 % 1000;
+            queue.push(EventKey::new(time, 0, sequence));
+            sequence += 1;
+            expected.push(time);
+            assert!(
+                queue.bottom.len() <= SPLIT_THRESHOLD,
+                "bottom tier should not grow past the split threshold"
+            );
+        }
+
+        expected.sort_unstable();
+        let popped: Vec<_> = std::iter::from_fn(|| queue.pop().map(|key| *key.execution_time())).collect();
+        assert_eq!(expected, popped, "keys should pop in ascending order");
+    }
+}
@@ -0,0 +1,189 @@
+use super::{EventKey, EventQueueBackend};
+use crate::SimTime;
+use std::fmt::Formatter;
+
+/// Marks the absence of a link between nodes.
+const NONE: usize = usize::MAX;
+
+/// A node of the heap, linked to its leftmost child and its next sibling by index.
+struct Node<Time>
+where
+    Time: SimTime,
+{
+    /// The key held by this node, or `None` if the node's slot is free for reuse.
+    key: Option<EventKey<Time>>,
+    child: usize,
+    sibling: usize,
+}
+
+/// An [`EventQueueBackend`] built on a pairing heap.
+///
+/// Pushing a key takes constant time, while popping takes amortized logarithmic time. The cheap insertion suits models
+/// that schedule many more events than they execute, such as timeouts that are usually cancelled before they fire.
+///
+/// Nodes are kept in a single growable buffer and reused once popped, so that the heap performs no allocations once it
+/// has grown to the largest size a run requires.
+pub struct PairingHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    nodes: Vec<Node<Time>>,
+    free: Vec<usize>,
+    /// Scratch space for the children of a popped root, kept to avoid allocating on each pop.
+    children: Vec<usize>,
+    root: usize,
+    len: usize,
+}
+
+impl<Time> PairingHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    /// Construct an empty queue.
+    pub fn new() -> Self {
+        Self {
+            nodes: Vec::new(),
+            free: Vec::new(),
+            children: Vec::new(),
+            root: NONE,
+            len: 0,
+        }
+    }
+
+    fn key(&self, node: usize) -> &EventKey<Time> {
+        self.nodes[node]
+            .key
+            .as_ref()
+            .expect("linked nodes should always hold a key")
+    }
+
+    /// Combine two heaps, returning the root of the result.
+    fn meld(&mut self, a: usize, b: usize) -> usize {
+        if a == NONE {
+            return b;
+        }
+        if b == NONE {
+            return a;
+        }
+
+        let (parent, child) = if self.key(a) <= self.key(b) { (a, b) } else { (b, a) };
+        self.nodes[child].sibling = self.nodes[parent].child;
+        self.nodes[parent].child = child;
+        parent
+    }
+
+    /// Combine the children of a popped root with the standard two-pass strategy: meld children in pairs from left to
+    /// right, then meld the resulting heaps from right to left.
+    fn merge_children(&mut self, first_child: usize) -> usize {
+        let mut children = std::mem::take(&mut self.children);
+        let mut child = first_child;
+        while child != NONE {
+            let next = self.nodes[child].sibling;
+            self.nodes[child].sibling = NONE;
+            children.push(child);
+            child = next;
+        }
+
+        // pair up children in place, writing each melded pair over the front of the buffer
+        let pairs = (children.len() + 1) / 2;
+        for pair in 0..pairs {
+            let first = children[2 * pair];
+            let melded = match children.get(2 * pair + 1) {
+                Some(second) => self.meld(first, *second),
+                None => first,
+            };
+            children[pair] = melded;
+        }
+        children.truncate(pairs);
+
+        let mut root = NONE;
+        while let Some(heap) = children.pop() {
+            root = self.meld(heap, root);
+        }
+
+        self.children = children;
+        root
+    }
+}
+
+impl<Time> Default for PairingHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time> EventQueueBackend<Time> for PairingHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    fn push(&mut self, key: EventKey<Time>) {
+        let node = Node {
+            key: Some(key),
+            child: NONE,
+            sibling: NONE,
+        };
+        let index = match self.free.pop() {
+            Some(index) => {
+                self.nodes[index] = node;
+                index
+            },
+            None => {
+                self.nodes.push(node);
+                self.nodes.len() - 1
+            },
+        };
+
+        self.root = self.meld(self.root, index);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<EventKey<Time>> {
+        if self.root == NONE {
+            return None;
+        }
+
+        let root = self.root;
+        let key = self.nodes[root].key.take();
+        self.free.push(root);
+        self.root = self.merge_children(self.nodes[root].child);
+        self.len -= 1;
+        key
+    }
+
+    fn peek(&self) -> Option<&EventKey<Time>> {
+        if self.root == NONE {
+            return None;
+        }
+        Some(self.key(self.root))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_> {
+        Box::new(self.nodes.iter().filter_map(|node| node.key.as_ref()))
+    }
+}
+
+impl<Time> std::fmt::Debug for PairingHeapQueue<Time>
+where
+    Time: SimTime,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PairingHeapQueue").field("len", &self.len).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pop_in_order() {
+        super::super::tests::check_backend(PairingHeapQueue::new());
+    }
+}
@@ -0,0 +1,204 @@
+use super::{EventKey, EventQueueBackend};
+use crate::SimTime;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::Formatter;
+
+/// A [`SimTime`] that can be mapped onto unsigned integers without changing its order, as required by a
+/// [`RadixHeapQueue`].
+///
+/// Implementations must guarantee that `a < b` implies `a.radix_key() <= b.radix_key()`, and ideally that distinct
+/// times map to distinct keys. Implementations are provided for the integral builtin types of up to 64 bits.
+pub trait RadixKey: SimTime {
+    /// Map this time onto an unsigned integer.
+    fn radix_key(&self) -> u64;
+}
+
+macro_rules! impl_radix_key_unsigned {
+    ($($int:ty),*) => {$(
+        impl RadixKey for $int {
+            fn radix_key(&self) -> u64 {
+                *self as u64
+            }
+        }
+    )*};
+}
+
+macro_rules! impl_radix_key_signed {
+    ($($int:ty),*) => {$(
+        impl RadixKey for $int {
+            fn radix_key(&self) -> u64 {
+                // flipping the sign bit moves negative numbers below positive ones
+                (*self as i64 as u64) ^ (1 << 63)
+            }
+        }
+    )*};
+}
+
+impl_radix_key_unsigned!(u8, u16, u32, u64, usize);
+impl_radix_key_signed!(i8, i16, i32, i64, isize);
+
+/// Number of buckets needed to cover every possible difference between two 64-bit keys, plus one for equal keys.
+const BUCKET_COUNT: usize = 65;
+
+/// An [`EventQueueBackend`] built on a radix heap, for clocks that implement [`RadixKey`].
+///
+/// A radix heap relies on the simulation's clock never running backwards: every key on the queue is at least as great
+/// as the last key popped. Keys are grouped into buckets by the highest bit in which they differ from that last key,
+/// and each key moves between buckets at most once per bit, giving amortized constant-time pushes and pops that take
+/// time logarithmic only in the range of execution times. Events that share an execution time are ordered with a binary
+/// heap.
+///
+/// Scheduling an event for a time before the last event popped, which is only possible through the unchecked
+/// scheduling methods, remains correct but forces the whole queue to be redistributed.
+pub struct RadixHeapQueue<Time>
+where
+    Time: RadixKey,
+{
+    /// Keys whose radix key equals `last`, ordered by the remainder of the key.
+    front: BinaryHeap<Reverse<EventKey<Time>>>,
+    /// Keys whose radix key differs from `last`, grouped by the position of the highest differing bit.
+    buckets: Vec<Vec<EventKey<Time>>>,
+    last: u64,
+    len: usize,
+}
+
+impl<Time> RadixHeapQueue<Time>
+where
+    Time: RadixKey,
+{
+    /// Construct an empty queue.
+    pub fn new() -> Self {
+        Self {
+            front: BinaryHeap::new(),
+            buckets: (0..BUCKET_COUNT).map(|_| Vec::new()).collect(),
+            last: 0,
+            len: 0,
+        }
+    }
+
+    /// Place a key in the front heap or a bucket according to its relation to `last`, which it must not be less than.
+    fn place(&mut self, key: EventKey<Time>) {
+        let radix = key.execution_time().radix_key();
+        let difference = radix ^ self.last;
+        if difference == 0 {
+            self.front.push(Reverse(key));
+        } else {
+            let bucket = (u64::BITS - difference.leading_zeros()) as usize;
+            self.buckets[bucket].push(key);
+        }
+    }
+
+    /// Lower `last` to the provided value, redistributing every key on the queue.
+    fn rebase(&mut self, last: u64) {
+        let mut keys: Vec<_> = self.front.drain().map(|key| key.0).collect();
+        for bucket in self.buckets.iter_mut() {
+            keys.append(bucket);
+        }
+
+        self.last = last;
+        for key in keys {
+            self.place(key);
+        }
+    }
+
+    /// Refill the front heap from the lowest nonempty bucket, if the front heap is empty.
+    fn refill_front(&mut self) {
+        if !self.front.is_empty() {
+            return;
+        }
+
+        let bucket = match self.buckets.iter().position(|bucket| !bucket.is_empty()) {
+            Some(bucket) => bucket,
+            None => return,
+        };
+
+        let keys = std::mem::take(&mut self.buckets[bucket]);
+        self.last = keys
+            .iter()
+            .map(|key| key.execution_time().radix_key())
+            .min()
+            .expect("bucket should not be empty");
+        for key in keys {
+            self.place(key);
+        }
+    }
+}
+
+impl<Time> Default for RadixHeapQueue<Time>
+where
+    Time: RadixKey,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<Time> EventQueueBackend<Time> for RadixHeapQueue<Time>
+where
+    Time: RadixKey,
+{
+    fn push(&mut self, key: EventKey<Time>) {
+        let radix = key.execution_time().radix_key();
+        if self.len == 0 {
+            self.last = radix;
+        } else if radix < self.last {
+            self.rebase(radix);
+        }
+
+        self.place(key);
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<EventKey<Time>> {
+        let key = self.front.pop()?.0;
+        self.len -= 1;
+        // keep the least key in the front heap so that peeking needs only shared access
+        self.refill_front();
+        Some(key)
+    }
+
+    fn peek(&self) -> Option<&EventKey<Time>> {
+        self.front.peek().map(|key| &key.0)
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Box<dyn Iterator<Item = &EventKey<Time>> + '_> {
+        Box::new(self.front.iter().map(|key| &key.0).chain(self.buckets.iter().flatten()))
+    }
+}
+
+impl<Time> std::fmt::Debug for RadixHeapQueue<Time>
+where
+    Time: RadixKey,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("RadixHeapQueue")
+            .field("last", &self.last)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_pop_in_order() {
+        super::super::tests::check_backend(RadixHeapQueue::new());
+    }
+
+    #[test]
+    fn signed_keys_preserve_order() {
+        let times = [i64::MIN, -5, -1, 0, 1, 5, i64::MAX];
+        let keys: Vec<_> = times.iter().map(RadixKey::radix_key).collect();
+        assert!(
+            keys.windows(2).all(|pair| pair[0] < pair[1]),
+            "radix keys should ascend"
+        );
+    }
+}
@@ -0,0 +1,232 @@
+//! For running independent replications of a simulation across threads.
+//!
+//! A single run of a stochastic simulation is one sample from its distribution of outcomes, so drawing conclusions
+//! from a model generally calls for many replications that differ only in their random numbers. [`Replications`] runs
+//! a batch of [`serial::Simulation`]s on a pool of scoped threads: a factory builds each replication from its index and
+//! a seed, each simulation runs to completion on whichever thread picked it up, and an extractor reduces it to the
+//! outputs of interest. Results come back in replication order no matter which thread finished first.
+//!
+//! Each replication's seed is derived from the batch's base seed and the replication's index, so two batches with the
+//! same base seed hand the same seeds to the same replications. Running one batch per system configuration with a
+//! shared base seed therefore applies common random numbers across the configurations.
+//!
+//! ```
+//! use desque::replications::Replications;
+//! use desque::serial::{OkEvent, Simulation};
+//! use desque::SimState;
+//!
+//! struct Counter {
+//!     count: u64,
+//! }
+//!
+//! impl SimState<u64> for Counter {}
+//!
+//! #[derive(Debug)]
+//! struct Increment;
+//!
+//! impl OkEvent<Counter, u64> for Increment {
+//!     fn execute(&mut self, sim: &mut Simulation<Counter, u64>) {
+//!         sim.state_mut().count += 1;
+//!     }
+//! }
+//!
+//! let counts = Replications::new(4, 12345).run(
+//!     |index, _seed| {
+//!         let mut sim = Simulation::new(Counter { count: 0 }, 0);
+//!         for time in 0..=index as u64 {
+//!             sim.schedule(Increment, time).expect("time should not be in the past");
+//!         }
+//!         sim
+//!     },
+//!     |sim| sim.state().count,
+//! )?;
+//!
+//! assert_eq!(vec![1, 2, 3, 4], counts);
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! [`serial::Simulation`]: crate::serial::Simulation
+
+use crate::queue::EventQueueBackend;
+use crate::serial::Simulation;
+use crate::{SimState, SimTime};
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+/// A batch of independent replications to run across a pool of threads.
+///
+/// See the [module-level documentation] for an overview.
+///
+/// [module-level documentation]: crate::replications
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Replications {
+    count: usize,
+    seed: u64,
+    threads: usize,
+}
+
+impl Replications {
+    /// Describe a batch of `count` replications whose seeds derive from `seed`, to be run on as many threads as the
+    /// system reports being available.
+    pub fn new(count: usize, seed: u64) -> Self {
+        Self {
+            count,
+            seed,
+            threads: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+
+    /// Limit the batch to running on the given number of threads.
+    pub fn with_threads(self, threads: NonZeroUsize) -> Self {
+        Self {
+            threads: threads.get(),
+            ..self
+        }
+    }
+
+    /// Get the number of replications in the batch.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the seed that the replication at `index` will receive.
+    ///
+    /// Seeds are spread from the base seed with the SplitMix64 generator, so that replications with neighbouring
+    /// indices receive unrelated seeds.
+    pub fn seed_for(&self, index: usize) -> u64 {
+        let mut z = self
+            .seed
+            .wrapping_add((index as u64).wrapping_add(1).wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Build, run, and extract the outputs of every replication in the batch, returning the outputs in replication
+    /// order.
+    ///
+    /// Each replication's simulation is built by `factory` from the replication's index and seed, run until its event
+    /// queue is exhausted, and then handed to `extract`. Simulations never leave the thread that built them, so they
+    /// need not be [`Send`].
+    ///
+    /// # Errors
+    ///
+    /// If any replication's simulation returns an error from [`Simulation::run()`], threads stop picking up new
+    /// replications and this method returns the error from the earliest failed replication.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `factory`, `extract`, or any event panics.
+    pub fn run<State, Time, Backend, Factory, Extract, Output>(
+        &self,
+        factory: Factory,
+        extract: Extract,
+    ) -> crate::Result<Vec<Output>>
+    where
+        State: SimState<Time>,
+        Time: SimTime,
+        Backend: EventQueueBackend<Time>,
+        Factory: Fn(usize, u64) -> Simulation<State, Time, Backend> + Sync,
+        Extract: Fn(&Simulation<State, Time, Backend>) -> Output + Sync,
+        Output: Send,
+    {
+        let next_index = AtomicUsize::new(0);
+        let failed = AtomicBool::new(false);
+        let results = Mutex::new((0..self.count).map(|_| None).collect::<Vec<_>>());
+
+        let replicate = || loop {
+            if failed.load(Ordering::Relaxed) {
+                break;
+            }
+            let index = next_index.fetch_add(1, Ordering::Relaxed);
+            if index >= self.count {
+                break;
+            }
+
+            let mut simulation = factory(index, self.seed_for(index));
+            let result = simulation.run().map(|_| extract(&simulation));
+            if result.is_err() {
+                failed.store(true, Ordering::Relaxed);
+            }
+            results.lock().expect("results mutex should not have been poisoned")[index] = Some(result);
+        };
+
+        thread::scope(|scope| {
+            for _ in 1..self.threads.min(self.count) {
+                scope.spawn(replicate);
+            }
+            replicate();
+        });
+
+        // Replications are picked up in order, so every replication before the earliest failure has completed.
+        results
+            .into_inner()
+            .expect("results mutex should not have been poisoned")
+            .into_iter()
+            .flatten()
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Error;
+
+    #[derive(Debug)]
+    struct State {
+        seed: u64,
+    }
+
+    impl SimState<u32> for State {}
+
+    #[test]
+    fn results_are_returned_in_replication_order() {
+        let replications = Replications::new(20, 7).with_threads(NonZeroUsize::new(4).unwrap());
+        let seeds = replications
+            .run(
+                |index, seed| {
+                    // later replications finish first
+                    thread::sleep(std::time::Duration::from_millis(20 - index as u64));
+                    Simulation::new(State { seed }, 0)
+                },
+                |sim| sim.state().seed,
+            )
+            .unwrap();
+
+        let expected: Vec<_> = (0..20).map(|index| replications.seed_for(index)).collect();
+        assert_eq!(expected, seeds);
+
+        let mut distinct = seeds.clone();
+        distinct.sort_unstable();
+        distinct.dedup();
+        assert_eq!(20, distinct.len(), "replications should receive distinct seeds");
+        assert_eq!(
+            seeds[3],
+            Replications::new(5, 7).seed_for(3),
+            "seeds should depend only on base seed and index"
+        );
+    }
+
+    #[test]
+    fn earliest_failure_is_reported() {
+        let result = Replications::new(10, 0).run(
+            |index, seed| {
+                let mut sim = Simulation::new(State { seed }, 0);
+                if index % 4 == 3 {
+                    sim.schedule_fn(move |_| Err(Error::BadExecution(format!("{}", index).into())), 1)
+                        .unwrap();
+                }
+                sim
+            },
+            |sim| sim.state().seed,
+        );
+
+        match result {
+            Err(Error::BadExecution(error)) => assert_eq!("3", error.to_string()),
+            other => panic!("expected replication 3 to fail, got {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! For modelling servers, machines, inventories, and other capacity-limited parts of a system.
+//!
+//! A [`Resource`] is stored in the simulation's state and tracks how many of its units are held, along with the
+//! requests waiting for one to become free. Each request carries a continuation: an event to execute once the request
+//! is granted. The simulations' `request_resource()` and `release_resource()` methods update a resource and schedule
+//! the continuations of granted requests at the current time, so that models no longer need to track busy servers and
+//! their lines by hand.
+//!
+//! ```
+//! use desque::resource::Resource;
+//! use desque::serial::{OkEvent, Simulation};
+//! use desque::SimState;
+//!
+//! struct Shop {
+//!     server: Resource<StartService>,
+//!     served: Vec<(u32, u32)>,
+//! }
+//!
+//! impl SimState<u32> for Shop {}
+//!
+//! #[derive(Debug)]
+//! struct StartService {
+//!     customer: u32,
+//! }
+//!
+//! impl OkEvent<Shop, u32> for StartService {
+//!     fn execute(&mut self, sim: &mut Simulation<Shop, u32>) {
+//!         let customer = self.customer;
+//!         let start = *sim.current_time();
+//!         sim.state_mut().served.push((customer, start));
+//!         sim.schedule_fn(|sim| sim.release_resource(|shop| &mut shop.server).map(|_| ()), start + 5)
+//!             .unwrap();
+//!     }
+//! }
+//!
+//! let shop = Shop {
+//!     server: Resource::new(1),
+//!     served: Vec::new(),
+//! };
+//! let mut sim = Simulation::new(shop, 0);
+//! for customer in 0..3 {
+//!     sim.schedule_fn(
+//!         move |sim| sim.request_resource(|shop| &mut shop.server, StartService { customer }).map(|_| ()),
+//!         customer,
+//!     )?;
+//! }
+//! sim.run()?;
+//!
+//! assert_eq!(vec![(0, 0), (1, 5), (2, 10)], sim.state().served);
+//! assert_eq!(3, sim.state().server.grants());
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! Waiting requests are granted according to the resource's [`Discipline`]. Holders of a [`PreemptiveResource`] may
+//! additionally be interrupted by higher-priority requests, with their service resumed or restarted once they regain a
+//! unit.
+//!
+//! For inventory-style models, a [`Container`] holds a quantity that requests add to and remove from, and a [`Store`]
+//! holds discrete items. Requests to either wait until they can be satisfied, then have their continuations scheduled
+//! in the same way.
+
+mod container;
+mod preemptive;
+mod store;
+
+pub use container::Container;
+pub(crate) use preemptive::request_preemptive;
+pub use preemptive::{Preemption, PreemptiveResource};
+pub use store::{Store, SyncStore};
+
+use std::collections::VecDeque;
+
+/// The order in which a [`Resource`] grants units to waiting requests.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Discipline {
+    /// Grant the request that has waited longest.
+    #[default]
+    Fifo,
+    /// Grant the request that has waited least.
+    Lifo,
+    /// Grant the request with the highest priority, breaking ties in the order that requests were made.
+    Priority,
+}
+
+/// A request waiting for a unit of a resource.
+#[derive(Debug, Clone)]
+struct Waiting<Continuation> {
+    continuation: Continuation,
+    priority: i32,
+}
+
+/// A pool of identical units, granted to requests according to a [`Discipline`].
+///
+/// The type parameter is the continuation carried by each request, usually an event type to schedule once the request
+/// is granted. Models that mix several kinds of continuation can use an enum, or a boxed [`serial::Event`] trait
+/// object.
+///
+/// [`serial::Event`]: crate::serial::Event
+#[derive(Debug, Clone)]
+pub struct Resource<Continuation> {
+    capacity: usize,
+    discipline: Discipline,
+    in_use: usize,
+    /// Ordered so that the next request to be granted is at the front.
+    waiting: VecDeque<Waiting<Continuation>>,
+    /// The number of requests granted over the resource's lifetime.
+    grants: usize,
+}
+
+impl<Continuation> Resource<Continuation> {
+    /// Create a resource with the provided number of units, all of them free, that grants waiting requests in the order
+    /// they were made.
+    pub fn new(capacity: usize) -> Self {
+        Self::with_discipline(capacity, Discipline::Fifo)
+    }
+
+    /// Create a resource with the provided number of units, all of them free, that grants waiting requests according to
+    /// the provided discipline.
+    pub fn with_discipline(capacity: usize, discipline: Discipline) -> Self {
+        Self {
+            capacity,
+            discipline,
+            in_use: 0,
+            waiting: VecDeque::new(),
+            grants: 0,
+        }
+    }
+
+    /// Request a unit of the resource. If one is free, it is granted immediately and the continuation handed back to
+    /// be executed. Otherwise, the continuation waits in line and `None` is returned.
+    ///
+    /// The simulations' `request_resource()` methods call this method and schedule the returned continuation; calling
+    /// it directly leaves scheduling to the caller.
+    pub fn request(&mut self, continuation: Continuation) -> Option<Continuation> {
+        self.request_with_priority(continuation, 0)
+    }
+
+    /// Request a unit of the resource with the provided priority, as with [`request()`]. Priorities only affect the
+    /// order of waiting requests under [`Discipline::Priority`], where higher priorities are granted first.
+    ///
+    /// [`request()`]: Resource::request
+    pub fn request_with_priority(&mut self, continuation: Continuation, priority: i32) -> Option<Continuation> {
+        if self.in_use < self.capacity {
+            self.in_use += 1;
+            self.grants += 1;
+            return Some(continuation);
+        }
+
+        let request = Waiting { continuation, priority };
+        match self.discipline {
+            Discipline::Fifo => self.waiting.push_back(request),
+            Discipline::Lifo => self.waiting.push_front(request),
+            Discipline::Priority => {
+                let position = self.waiting.partition_point(|waiting| waiting.priority >= priority);
+                self.waiting.insert(position, request);
+            },
+        }
+        None
+    }
+
+    /// Release a unit of the resource. If a request is waiting, the unit is granted to it and its continuation handed
+    /// back to be executed. Otherwise, the unit becomes free and `None` is returned.
+    ///
+    /// # Panics
+    ///
+    /// Releasing a resource of which no units are held indicates a logical bug in the model, and so panics.
+    pub fn release(&mut self) -> Option<Continuation> {
+        assert!(self.in_use > 0, "resource should only be released while a unit is held");
+        match self.waiting.pop_front() {
+            Some(waiting) => {
+                self.grants += 1;
+                Some(waiting.continuation)
+            },
+            None => {
+                self.in_use -= 1;
+                None
+            },
+        }
+    }
+
+    /// Get the total number of units.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the order in which waiting requests are granted.
+    pub fn discipline(&self) -> Discipline {
+        self.discipline
+    }
+
+    /// Get the number of units currently held.
+    pub fn in_use(&self) -> usize {
+        self.in_use
+    }
+
+    /// Get the number of units currently free.
+    pub fn available(&self) -> usize {
+        self.capacity - self.in_use
+    }
+
+    /// Get the number of requests waiting for a unit.
+    pub fn queue_length(&self) -> usize {
+        self.waiting.len()
+    }
+
+    /// Iterate over the continuations of waiting requests, starting with the next to be granted.
+    pub fn waiting(&self) -> impl Iterator<Item = &Continuation> {
+        self.waiting.iter().map(|waiting| &waiting.continuation)
+    }
+
+    /// Get the number of requests granted over the resource's lifetime, including those granted immediately.
+    pub fn grants(&self) -> usize {
+        self.grants
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn units_are_granted_in_request_order() {
+        let mut resource = Resource::new(2);
+        assert_eq!(Some(1), resource.request(1));
+        assert_eq!(Some(2), resource.request(2));
+        assert_eq!(None, resource.request(3));
+        assert_eq!(None, resource.request(4));
+        assert_eq!((0, 2), (resource.available(), resource.queue_length()));
+
+        assert_eq!(
+            Some(3),
+            resource.release(),
+            "release should grant to first waiting request"
+        );
+        assert_eq!(Some(4), resource.release());
+        assert_eq!(None, resource.release());
+        assert_eq!((1, 0), (resource.available(), resource.queue_length()));
+        assert_eq!(4, resource.grants());
+    }
+
+    #[test]
+    fn disciplines_order_waiting_requests() {
+        let mut lifo = Resource::with_discipline(1, Discipline::Lifo);
+        let mut priority = Resource::with_discipline(1, Discipline::Priority);
+        for (value, level) in [(0, 0), (1, 1), (2, 5), (3, 1)] {
+            lifo.request(value);
+            priority.request_with_priority(value, level);
+        }
+
+        assert_eq!(vec![&3, &2, &1], lifo.waiting().collect::<Vec<_>>());
+        assert_eq!(
+            vec![&2, &1, &3],
+            priority.waiting().collect::<Vec<_>>(),
+            "priority should order requests, with ties in request order"
+        );
+        assert_eq!(Some(2), priority.release());
+    }
+
+    #[test]
+    #[should_panic(expected = "unit is held")]
+    fn releasing_free_resource_panics() {
+        Resource::<()>::new(1).release();
+    }
+}
@@ -0,0 +1,137 @@
+use std::collections::VecDeque;
+use std::ops::{Add, Sub};
+
+/// A reservoir holding a continuous or bulk quantity, such as fuel in a tank or stock measured by weight.
+///
+/// Requests to fill the container wait until the added amount fits below its capacity, and requests to drain it wait
+/// until enough is stored. Each kind of request is served in the order it was made, so a large request at the head of
+/// its line holds up smaller ones behind it. As with a [`Resource`], each request carries a continuation to execute
+/// once it is satisfied; the simulations' `fill_container()` and `drain_container()` methods schedule these
+/// continuations at the current time.
+///
+/// ```
+/// use desque::resource::Container;
+///
+/// let mut tank = Container::new(10.0, 4.0);
+/// assert_eq!(Vec::<&str>::new(), tank.drain(6.0, "truck"), "drain should wait for enough fuel");
+/// assert_eq!(vec!["delivery", "truck"], tank.fill(5.0, "delivery"));
+/// assert_eq!(3.0, tank.level());
+/// ```
+///
+/// [`Resource`]: super::Resource
+#[derive(Debug, Clone)]
+pub struct Container<Amount, Continuation> {
+    capacity: Amount,
+    level: Amount,
+    fills: VecDeque<(Amount, Continuation)>,
+    drains: VecDeque<(Amount, Continuation)>,
+}
+
+impl<Amount, Continuation> Container<Amount, Continuation>
+where
+    Amount: Copy + PartialOrd + Add<Output = Amount> + Sub<Output = Amount>,
+{
+    /// Create a container with the provided capacity and initial level.
+    pub fn new(capacity: Amount, initial_level: Amount) -> Self {
+        Self {
+            capacity,
+            level: initial_level,
+            fills: VecDeque::new(),
+            drains: VecDeque::new(),
+        }
+    }
+
+    /// Request to add the provided amount, returning the continuations of all requests satisfied as a result in the
+    /// order they were satisfied. The request waits if the amount does not yet fit.
+    pub fn fill(&mut self, amount: Amount, continuation: Continuation) -> Vec<Continuation> {
+        self.fills.push_back((amount, continuation));
+        self.settle()
+    }
+
+    /// Request to remove the provided amount, returning the continuations of all requests satisfied as a result in the
+    /// order they were satisfied. The request waits if not enough is yet stored.
+    pub fn drain(&mut self, amount: Amount, continuation: Continuation) -> Vec<Continuation> {
+        self.drains.push_back((amount, continuation));
+        self.settle()
+    }
+
+    /// Satisfy the requests at the head of each line for as long as either can proceed.
+    fn settle(&mut self) -> Vec<Continuation> {
+        let mut satisfied = Vec::new();
+        loop {
+            let progress = satisfied.len();
+            if let Some(&(amount, _)) = self.fills.front() {
+                if self.level + amount <= self.capacity {
+                    self.level = self.level + amount;
+                    satisfied.extend(self.fills.pop_front().map(|(_, continuation)| continuation));
+                }
+            }
+            if let Some(&(amount, _)) = self.drains.front() {
+                if amount <= self.level {
+                    self.level = self.level - amount;
+                    satisfied.extend(self.drains.pop_front().map(|(_, continuation)| continuation));
+                }
+            }
+            if satisfied.len() == progress {
+                return satisfied;
+            }
+        }
+    }
+
+    /// Get the maximum amount the container can hold.
+    pub fn capacity(&self) -> Amount {
+        self.capacity
+    }
+
+    /// Get the amount currently stored.
+    pub fn level(&self) -> Amount {
+        self.level
+    }
+
+    /// Get the number of requests waiting to add to the container.
+    pub fn fills_waiting(&self) -> usize {
+        self.fills.len()
+    }
+
+    /// Get the number of requests waiting to remove from the container.
+    pub fn drains_waiting(&self) -> usize {
+        self.drains.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_wait_in_order_until_satisfiable() {
+        let mut container = Container::new(10, 0);
+        assert_eq!(Vec::<i32>::new(), container.drain(4, 1));
+        assert_eq!(
+            Vec::<i32>::new(),
+            container.drain(1, 2),
+            "smaller drain should wait behind larger"
+        );
+        assert_eq!(vec![3], container.fill(3, 3));
+        assert_eq!((3, 2), (container.level(), container.drains_waiting()));
+
+        assert_eq!(
+            vec![4, 1, 2],
+            container.fill(2, 4),
+            "fill should satisfy waiting drains in order"
+        );
+        assert_eq!(0, container.level());
+        assert_eq!(vec![5], container.fill(10, 5));
+        assert_eq!(
+            Vec::<i32>::new(),
+            container.fill(1, 6),
+            "fill should wait while container is full"
+        );
+        assert_eq!(
+            vec![7, 6],
+            container.drain(2, 7),
+            "drain should make room for waiting fill"
+        );
+        assert_eq!((9, 0), (container.level(), container.fills_waiting()));
+    }
+}
@@ -0,0 +1,428 @@
+use crate::queue::EventQueueBackend;
+use crate::serial::{Event, OkEvent, Simulation};
+use crate::{EventHandle, SimState, SimTime};
+use std::collections::VecDeque;
+use std::fmt::Formatter;
+use std::marker::PhantomData;
+use std::ops::{Add, Sub};
+
+/// What becomes of the service of a holder that is interrupted by a higher-priority request.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Preemption {
+    /// Continue the interrupted service where it left off once the holder regains a unit.
+    Resume,
+    /// Repeat the interrupted service in full once the holder regains a unit.
+    Restart,
+}
+
+/// A request for a preemptive resource, carried from its arrival through to its completion.
+#[derive(Debug)]
+struct Job<Continuation, Time> {
+    id: usize,
+    continuation: Continuation,
+    priority: i32,
+    service: Time,
+    /// The service still owed, which is less than the full service after a preemption under [`Preemption::Resume`].
+    remaining: Time,
+}
+
+/// A job being served, alongside the event that will complete it.
+#[derive(Debug)]
+struct Holder<Continuation, Time> {
+    job: Job<Continuation, Time>,
+    completion: EventHandle,
+    completes_at: Time,
+}
+
+/// A pool of identical units whose holders can be interrupted by requests of higher priority.
+///
+/// Unlike a [`Resource`], which leaves holders to release their units, a preemptive resource manages each request's
+/// service itself, as it must postpone the service of an interrupted holder. Requests are made through
+/// [`serial::Simulation::request_preemptive()`] with a priority and a service time. A request that finds no unit free
+/// interrupts the holder with the lowest priority below its own, if there is one, preferring the holder that started
+/// most recently among those tied; otherwise it waits. The interrupted holder returns to the front of the waiting
+/// requests of its priority, and its service is resumed or restarted according to the resource's [`Preemption`] once it
+/// regains a unit. When a request's service completes, its unit passes to the highest-priority waiting request and its
+/// continuation is scheduled at the current time.
+///
+/// ```
+/// use desque::resource::{Preemption, PreemptiveResource};
+/// use desque::serial::{OkEvent, Simulation};
+/// use desque::SimState;
+///
+/// struct Cpu {
+///     core: PreemptiveResource<Finished, u32>,
+///     finished: Vec<(&'static str, u32)>,
+/// }
+///
+/// impl SimState<u32> for Cpu {}
+///
+/// #[derive(Debug)]
+/// struct Finished(&'static str);
+///
+/// impl OkEvent<Cpu, u32> for Finished {
+///     fn execute(&mut self, sim: &mut Simulation<Cpu, u32>) {
+///         let now = *sim.current_time();
+///         sim.state_mut().finished.push((self.0, now));
+///     }
+/// }
+///
+/// let cpu = Cpu {
+///     core: PreemptiveResource::new(1, Preemption::Resume),
+///     finished: Vec::new(),
+/// };
+/// let mut sim = Simulation::new(cpu, 0);
+/// sim.request_preemptive(|cpu| &mut cpu.core, Finished("batch"), 0, 10)?;
+/// sim.schedule_fn(
+///     |sim| sim.request_preemptive(|cpu| &mut cpu.core, Finished("interactive"), 5, 3).map(|_| ()),
+///     2,
+/// )?;
+/// sim.run()?;
+///
+/// assert_eq!(vec![("interactive", 5), ("batch", 13)], sim.state().finished);
+/// # Ok::<(), desque::Error>(())
+/// ```
+///
+/// [`Resource`]: super::Resource
+/// [`serial::Simulation::request_preemptive()`]: crate::serial::Simulation::request_preemptive
+#[derive(Debug)]
+pub struct PreemptiveResource<Continuation, Time> {
+    capacity: usize,
+    preemption: Preemption,
+    /// Ordered by the time each holder started its current service.
+    holders: Vec<Holder<Continuation, Time>>,
+    /// Ordered so that the next request to be granted is at the front.
+    waiting: VecDeque<Job<Continuation, Time>>,
+    /// The number of requests made over the resource's lifetime, used to identify them.
+    requests: usize,
+    grants: usize,
+    preemptions: usize,
+}
+
+impl<Continuation, Time> PreemptiveResource<Continuation, Time> {
+    /// Create a resource with the provided number of units, all of them free, whose interrupted holders are treated
+    /// according to the provided [`Preemption`].
+    pub fn new(capacity: usize, preemption: Preemption) -> Self {
+        Self {
+            capacity,
+            preemption,
+            holders: Vec::new(),
+            waiting: VecDeque::new(),
+            requests: 0,
+            grants: 0,
+            preemptions: 0,
+        }
+    }
+
+    /// Get the total number of units.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get what becomes of the service of interrupted holders.
+    pub fn preemption(&self) -> Preemption {
+        self.preemption
+    }
+
+    /// Get the number of units currently held.
+    pub fn in_use(&self) -> usize {
+        self.holders.len()
+    }
+
+    /// Get the number of units currently free.
+    pub fn available(&self) -> usize {
+        self.capacity - self.holders.len()
+    }
+
+    /// Get the number of requests waiting for a unit, including interrupted holders.
+    pub fn queue_length(&self) -> usize {
+        self.waiting.len()
+    }
+
+    /// Get the number of times a unit has been granted over the resource's lifetime, counting each time an interrupted
+    /// holder regains one.
+    pub fn grants(&self) -> usize {
+        self.grants
+    }
+
+    /// Get the number of times a holder has been interrupted over the resource's lifetime.
+    pub fn preemptions(&self) -> usize {
+        self.preemptions
+    }
+
+    /// Find the holder that a request of the provided priority would interrupt, if any.
+    fn preemptible_holder(&self, priority: i32) -> Option<usize> {
+        let mut victim: Option<(usize, i32)> = None;
+        for (index, holder) in self.holders.iter().enumerate() {
+            let lowest = victim.map_or(priority, |(_, lowest)| lowest);
+            if holder.job.priority < priority && holder.job.priority <= lowest {
+                victim = Some((index, holder.job.priority));
+            }
+        }
+        victim.map(|(index, _)| index)
+    }
+
+    /// Place a job behind waiting jobs of equal or higher priority, or if it was interrupted, ahead of those of equal
+    /// priority.
+    fn enqueue(&mut self, job: Job<Continuation, Time>, interrupted: bool) {
+        let priority = job.priority;
+        let position = if interrupted {
+            self.waiting.partition_point(|waiting| waiting.priority > priority)
+        } else {
+            self.waiting.partition_point(|waiting| waiting.priority >= priority)
+        };
+        self.waiting.insert(position, job);
+    }
+}
+
+/// Request a unit of a preemptive resource on behalf of [`Simulation::request_preemptive()`].
+pub(crate) fn request_preemptive<State, Time, Backend, Continuation, Select>(
+    simulation: &mut Simulation<State, Time, Backend>,
+    select: Select,
+    continuation: Continuation,
+    priority: i32,
+    service: Time,
+) -> crate::Result<bool>
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone + Add<Output = Time> + Sub<Output = Time> + 'static,
+    Backend: EventQueueBackend<Time>,
+    Continuation: Event<State, Time, Backend> + 'static,
+    Select: Fn(&mut State) -> &mut PreemptiveResource<Continuation, Time> + Copy + 'static,
+{
+    // check the job's completion time before interrupting anyone, as an interruption cannot be undone
+    let now = simulation.current_time().clone();
+    if now.clone() + service.clone() < now {
+        return Err(crate::Error::BackInTime);
+    }
+
+    let resource = select(simulation.state_mut());
+    let job = Job {
+        id: resource.requests,
+        continuation,
+        priority,
+        remaining: service.clone(),
+        service,
+    };
+    resource.requests += 1;
+
+    if resource.holders.len() < resource.capacity {
+        start(simulation, select, job)?;
+        return Ok(true);
+    }
+    let victim = match resource.preemptible_holder(priority) {
+        Some(index) => resource.holders.remove(index),
+        None => {
+            resource.enqueue(job, false);
+            return Ok(false);
+        },
+    };
+
+    simulation.cancel(victim.completion);
+    let mut interrupted = victim.job;
+    interrupted.remaining = match select(simulation.state_mut()).preemption {
+        Preemption::Resume => victim.completes_at - simulation.current_time().clone(),
+        Preemption::Restart => interrupted.service.clone(),
+    };
+    let resource = select(simulation.state_mut());
+    resource.preemptions += 1;
+    resource.enqueue(interrupted, true);
+
+    start(simulation, select, job)?;
+    Ok(true)
+}
+
+/// Grant a unit to the provided job and schedule the completion of its service.
+fn start<State, Time, Backend, Continuation, Select>(
+    simulation: &mut Simulation<State, Time, Backend>,
+    select: Select,
+    job: Job<Continuation, Time>,
+) -> crate::Result
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone + Add<Output = Time> + 'static,
+    Backend: EventQueueBackend<Time>,
+    Continuation: Event<State, Time, Backend> + 'static,
+    Select: Fn(&mut State) -> &mut PreemptiveResource<Continuation, Time> + Copy + 'static,
+{
+    let completes_at = simulation.current_time().clone() + job.remaining.clone();
+    let completion = Completion {
+        select,
+        job: job.id,
+        continuation: PhantomData,
+    };
+    let completion = simulation.schedule(completion, completes_at.clone())?;
+
+    let resource = select(simulation.state_mut());
+    resource.grants += 1;
+    resource.holders.push(Holder {
+        job,
+        completion,
+        completes_at,
+    });
+    Ok(())
+}
+
+/// The end of a job's service, which frees its unit for the next waiting job and schedules the job's continuation.
+struct Completion<Select, Continuation> {
+    select: Select,
+    job: usize,
+    continuation: PhantomData<fn() -> Continuation>,
+}
+
+impl<State, Time, Backend, Continuation, Select> OkEvent<State, Time, Backend> for Completion<Select, Continuation>
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone + Add<Output = Time> + 'static,
+    Backend: EventQueueBackend<Time>,
+    Continuation: Event<State, Time, Backend> + 'static,
+    Select: Fn(&mut State) -> &mut PreemptiveResource<Continuation, Time> + Copy + 'static,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        let resource = (self.select)(simulation.state_mut());
+        let position = resource
+            .holders
+            .iter()
+            .position(|holder| holder.job.id == self.job)
+            .expect("completing job should hold a unit, as interrupted jobs have their completion cancelled");
+        let holder = resource.holders.remove(position);
+        let next = resource.waiting.pop_front();
+
+        let now = simulation.current_time().clone();
+        if let Err(error) = simulation.schedule(holder.job.continuation, now) {
+            simulation.defer_error(error);
+        }
+        if let Some(next) = next {
+            if let Err(error) = start(simulation, self.select, next) {
+                simulation.defer_error(error);
+            }
+        }
+    }
+}
+
+impl<Select, Continuation> std::fmt::Debug for Completion<Select, Continuation> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Completion").field("job", &self.job).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug)]
+    struct State {
+        core: PreemptiveResource<Finished, u32>,
+        finished: Vec<(&'static str, u32)>,
+    }
+
+    impl SimState<u32> for State {}
+
+    #[derive(Debug)]
+    struct Finished(&'static str);
+
+    impl OkEvent<State, u32> for Finished {
+        fn execute(&mut self, simulation: &mut Simulation<State, u32>) {
+            let now = *simulation.current_time();
+            simulation.state_mut().finished.push((self.0, now));
+        }
+    }
+
+    /// Run a low-priority job from time 0 that is interrupted at time 2, then contended for by a job of equal priority.
+    fn run_jobs(preemption: Preemption) -> Simulation<State, u32> {
+        let state = State {
+            core: PreemptiveResource::new(1, preemption),
+            finished: Vec::new(),
+        };
+        let mut sim = Simulation::new(state, 0);
+        sim.request_preemptive(|state| &mut state.core, Finished("low"), 0, 10)
+            .unwrap();
+        sim.schedule_fn(
+            |sim| {
+                sim.request_preemptive(|state| &mut state.core, Finished("high"), 5, 3)?;
+                sim.request_preemptive(|state| &mut state.core, Finished("later"), 0, 1)?;
+                Ok(())
+            },
+            2,
+        )
+        .unwrap();
+        sim.run().unwrap();
+        sim
+    }
+
+    #[test]
+    fn interrupted_service_resumes() {
+        let sim = run_jobs(Preemption::Resume);
+        assert_eq!(
+            vec![("high", 5), ("low", 13), ("later", 14)],
+            sim.state().finished,
+            "interrupted job should finish its remaining service ahead of equal priorities"
+        );
+        assert_eq!((1, 4), (sim.state().core.preemptions(), sim.state().core.grants()));
+    }
+
+    #[test]
+    fn interrupted_service_restarts() {
+        let sim = run_jobs(Preemption::Restart);
+        assert_eq!(
+            vec![("high", 5), ("low", 15), ("later", 16)],
+            sim.state().finished,
+            "interrupted job should repeat its service in full"
+        );
+    }
+
+    #[derive(Debug)]
+    struct Shift {
+        core: PreemptiveResource<Clocked, i32>,
+    }
+
+    impl SimState<i32> for Shift {}
+
+    #[derive(Debug)]
+    struct Clocked;
+
+    impl OkEvent<Shift, i32> for Clocked {
+        fn execute(&mut self, _: &mut Simulation<Shift, i32>) {}
+    }
+
+    #[test]
+    fn requests_ending_in_the_past_interrupt_nobody() {
+        let shift = Shift {
+            core: PreemptiveResource::new(1, Preemption::Resume),
+        };
+        let mut sim = Simulation::new(shift, 0);
+        assert!(sim.request_preemptive(|shift| &mut shift.core, Clocked, 0, 5).unwrap());
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.request_preemptive(|shift| &mut shift.core, Clocked, 5, -1)
+        );
+
+        let core = &sim.state().core;
+        assert_eq!(
+            (1, 0, 0, 1),
+            (core.in_use(), core.queue_length(), core.preemptions(), core.grants()),
+            "failed request should leave the holder in place"
+        );
+        sim.run().unwrap();
+        assert_eq!(5, *sim.current_time(), "holder should still complete its service");
+    }
+
+    #[test]
+    fn equal_priorities_do_not_interrupt() {
+        let state = State {
+            core: PreemptiveResource::new(1, Preemption::Resume),
+            finished: Vec::new(),
+        };
+        let mut sim = Simulation::new(state, 0);
+        assert!(sim
+            .request_preemptive(|state| &mut state.core, Finished("first"), 1, 4)
+            .unwrap());
+        assert!(!sim
+            .request_preemptive(|state| &mut state.core, Finished("second"), 1, 4)
+            .unwrap());
+
+        sim.run().unwrap();
+        assert_eq!(vec![("first", 4), ("second", 8)], sim.state().finished);
+        assert_eq!(0, sim.state().core.preemptions());
+    }
+}
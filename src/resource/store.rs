@@ -0,0 +1,221 @@
+use std::collections::VecDeque;
+use std::fmt::{Debug, Formatter};
+
+/// A request to take an item from a store.
+struct Get<Filter, Deliver>
+where
+    Filter: ?Sized,
+    Deliver: ?Sized,
+{
+    /// Restricts the items that can satisfy the request, or `None` to accept any.
+    filter: Option<Box<Filter>>,
+    /// Builds the continuation from the item taken.
+    deliver: Box<Deliver>,
+}
+
+/// A buffer of discrete items, such as parts on a shelf or pallets in a warehouse.
+///
+/// Requests to put an item wait while the store is full, and are served in the order they were made. Requests to get
+/// an item wait until the store holds one they accept, taking the longest-held such item; a request that filters the
+/// items it accepts does not hold up later requests that can be satisfied. Each request carries a continuation to
+/// execute once it is satisfied, which for a get is built from the item taken. The simulations' `put_item()`,
+/// `get_item()`, and `get_item_where()` methods schedule these continuations at the current time.
+///
+/// Filters and the closures that build continuations are held boxed, and the store is generic over the boxed types.
+/// The defaults suit a [`serial::Simulation`], placing no further requirements on the closures. Stores held in the
+/// state of a [`threadsafe::Simulation`] must instead be [`SyncStore`]s, whose closures are [`Send`] and [`Sync`].
+///
+/// ```
+/// use desque::resource::Store;
+///
+/// let mut shelf: Store<u32, String> = Store::new(2);
+/// assert_eq!(vec!["stocked"], shelf.put(7, String::from("stocked")));
+/// assert_eq!(vec!["stocked"], shelf.put(12, String::from("stocked")));
+///
+/// let picked = shelf.get_where(
+///     Box::new(|part: &u32| part % 2 == 0),
+///     Box::new(|part| format!("picked {}", part)),
+/// );
+/// assert_eq!(vec!["picked 12"], picked);
+/// assert_eq!(vec![&7], shelf.items().collect::<Vec<_>>());
+/// ```
+///
+/// [`serial::Simulation`]: crate::serial::Simulation
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+pub struct Store<Item, Continuation, Filter = dyn Fn(&Item) -> bool, Deliver = dyn FnOnce(Item) -> Continuation>
+where
+    Filter: ?Sized,
+    Deliver: ?Sized,
+{
+    capacity: usize,
+    items: VecDeque<Item>,
+    puts: VecDeque<(Item, Continuation)>,
+    gets: VecDeque<Get<Filter, Deliver>>,
+}
+
+/// A [`Store`] that can be held in the state of a [`threadsafe::Simulation`], as its filters and the closures that
+/// build its continuations are [`Send`] and [`Sync`].
+///
+/// [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+pub type SyncStore<Item, Continuation> =
+    Store<Item, Continuation, dyn Fn(&Item) -> bool + Send + Sync, dyn FnOnce(Item) -> Continuation + Send + Sync>;
+
+impl<Item, Continuation, Filter, Deliver> Store<Item, Continuation, Filter, Deliver>
+where
+    Filter: Fn(&Item) -> bool + ?Sized,
+    Deliver: FnOnce(Item) -> Continuation + ?Sized,
+{
+    /// Create an empty store that can hold the provided number of items.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            items: VecDeque::new(),
+            puts: VecDeque::new(),
+            gets: VecDeque::new(),
+        }
+    }
+
+    /// Request to add an item, returning the continuations of all requests satisfied as a result in the order they were
+    /// satisfied. The request waits if the store is full.
+    pub fn put(&mut self, item: Item, continuation: Continuation) -> Vec<Continuation> {
+        self.puts.push_back((item, continuation));
+        self.settle()
+    }
+
+    /// Request to take any item, returning the continuations of all requests satisfied as a result in the order they
+    /// were satisfied. The request's own continuation is built from the item it takes, once there is one.
+    pub fn get(&mut self, deliver: Box<Deliver>) -> Vec<Continuation> {
+        self.gets.push_back(Get { filter: None, deliver });
+        self.settle()
+    }
+
+    /// Request to take an item accepted by the provided filter, as with [`get()`].
+    ///
+    /// [`get()`]: Store::get
+    pub fn get_where(&mut self, filter: Box<Filter>, deliver: Box<Deliver>) -> Vec<Continuation> {
+        self.gets.push_back(Get {
+            filter: Some(filter),
+            deliver,
+        });
+        self.settle()
+    }
+
+    /// Admit waiting puts while there is room, then satisfy waiting gets in order, for as long as either can proceed.
+    fn settle(&mut self) -> Vec<Continuation> {
+        let mut satisfied = Vec::new();
+        loop {
+            let progress = satisfied.len();
+            while self.items.len() < self.capacity {
+                match self.puts.pop_front() {
+                    Some((item, continuation)) => {
+                        self.items.push_back(item);
+                        satisfied.push(continuation);
+                    },
+                    None => break,
+                }
+            }
+
+            let mut index = 0;
+            while index < self.gets.len() && !self.items.is_empty() {
+                let filter = &self.gets[index].filter;
+                let position = self
+                    .items
+                    .iter()
+                    .position(|item| filter.as_ref().map_or(true, |filter| filter(item)));
+                match position.and_then(|position| self.items.remove(position)) {
+                    Some(item) => {
+                        let get = self.gets.remove(index).expect("index should be in bounds");
+                        satisfied.push((get.deliver)(item));
+                    },
+                    None => index += 1,
+                }
+            }
+
+            if satisfied.len() == progress {
+                return satisfied;
+            }
+        }
+    }
+
+    /// Get the number of items the store can hold.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Iterate over the items currently held, starting with the longest-held.
+    pub fn items(&self) -> impl Iterator<Item = &Item> {
+        self.items.iter()
+    }
+
+    /// Get the number of items currently held.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Check whether the store holds no items.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Get the number of requests waiting to put an item.
+    pub fn puts_waiting(&self) -> usize {
+        self.puts.len()
+    }
+
+    /// Get the number of requests waiting to get an item.
+    pub fn gets_waiting(&self) -> usize {
+        self.gets.len()
+    }
+}
+
+impl<Item, Continuation, Filter, Deliver> Debug for Store<Item, Continuation, Filter, Deliver>
+where
+    Item: Debug,
+    Filter: ?Sized,
+    Deliver: ?Sized,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("Store")
+            .field("capacity", &self.capacity)
+            .field("items", &self.items)
+            .field("puts_waiting", &self.puts.len())
+            .field("gets_waiting", &self.gets.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filtered_gets_do_not_block_later_gets() {
+        let mut store: Store<i32, i32> = Store::new(usize::MAX);
+        assert!(store
+            .get_where(Box::new(|item: &i32| *item > 10), Box::new(|item| item))
+            .is_empty());
+        assert!(store.get(Box::new(|item| item + 100)).is_empty());
+
+        assert_eq!(
+            vec![0, 103],
+            store.put(3, 0),
+            "unfiltered get should take item rejected by filter"
+        );
+        assert_eq!(vec![0, 20], store.put(20, 0));
+        assert_eq!(0, store.gets_waiting());
+    }
+
+    #[test]
+    fn puts_wait_while_full() {
+        let mut store: Store<i32, &str> = Store::new(1);
+        assert_eq!(vec!["first"], store.put(1, "first"));
+        assert!(store.put(2, "second").is_empty());
+        assert_eq!(1, store.puts_waiting());
+
+        assert_eq!(
+            vec!["got 1", "second"],
+            store.get(Box::new(|item| if item == 1 { "got 1" } else { "got other" }))
+        );
+        assert_eq!(vec![&2], store.items().collect::<Vec<_>>());
+    }
+}
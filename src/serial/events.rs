@@ -1,47 +1,132 @@
-mod event_holder;
+pub(super) mod activity;
+pub(super) mod event_holder;
 pub(super) mod event_traits;
+pub(super) mod fn_event;
+pub(super) mod occurrence;
 
-use crate::{SimState, SimTime};
-use event_holder::ScheduledEvent;
+use crate::arena::{ArenaBox, EventArena};
+use crate::queue::{EventKey, EventQueueBackend};
+use crate::{EventHandle, SimState, SimTime};
+use event_holder::PendingEvent;
 use event_traits::Event;
 
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::ptr::NonNull;
 
-/// Helper struct to set a Debug impl that hides everything about `BinaryHeap` and `Reverse`
+/// Helper struct to set a Debug impl that hides everything about the backend, and that pairs each key held by the
+/// backend with the event it refers to.
+///
+/// The backend only holds the keys necessary to order events, while the events themselves live in a map keyed by
+/// insertion sequence. Cancelling an event removes it from the map and leaves its key behind in the backend; such stale
+/// keys are discarded as they reach the front of the backend, and so the front of the backend always refers to a
+/// pending event.
+///
+/// Events scheduled by value are moved into an [`EventArena`], which reuses the memory of events that have executed or
+/// been cancelled. Every event should therefore be released back to the arena once the queue is done with it, which is
+/// also how the contents drop any events still pending. An event that is dropped instead, as when unwinding from a
+/// panic during its execution, leaves its memory unused until the arena is dropped.
 #[derive(Default)]
-struct BinaryHeapWrapper<State, Time>
+struct QueueContents<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
-    heap: BinaryHeap<Reverse<ScheduledEvent<State, Time>>>,
+    keys: Backend,
+    events: HashMap<usize, StoredEvent<State, Time, Backend>>,
+    arena: EventArena,
 }
 
-impl<State, Time> Debug for BinaryHeapWrapper<State, Time>
+impl<State, Time, Backend> QueueContents<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Drop an event that has left the queue and reclaim its memory.
+    fn release(&mut self, event: ArenaBox<dyn Event<State, Time, Backend>>) {
+        // SAFETY: every event on the queue was either inserted into this arena or boxed
+        unsafe { self.arena.release(event) };
+    }
+
+    /// Pop keys off the front of the backend until finding one that refers to an event which is still pending.
+    fn discard_cancelled(&mut self) {
+        while let Some(key) = self.keys.peek() {
+            if self.events.contains_key(&key.insertion_sequence()) {
+                return;
+            }
+            self.keys.pop();
+        }
+    }
+
+    /// Pair each key held by the backend with the event it refers to, skipping keys of cancelled events.
+    fn pending(&self) -> impl Iterator<Item = PendingEvent<'_, State, Time, Backend>> {
+        self.keys.iter().filter_map(move |key| {
+            self.events
+                .get(&key.insertion_sequence())
+                .map(|stored| PendingEvent::new(key, &*stored.event))
+        })
+    }
+}
+
+impl<State, Time, Backend> Drop for QueueContents<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn drop(&mut self) {
+        let events: Vec<_> = self.events.drain().map(|(_, stored)| stored.event).collect();
+        for event in events {
+            self.release(event);
+        }
+    }
+}
+
+impl<State, Time, Backend> Debug for QueueContents<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        f.debug_list()
-            .entries(self.heap.iter().map(|holder| &holder.0))
-            .finish()
+        f.debug_list().entries(self.pending()).finish()
     }
 }
 
+/// An event waiting on the queue, alongside the priority it was scheduled with so that rescheduling can preserve it.
+struct StoredEvent<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    event: ArenaBox<dyn Event<State, Time, Backend>>,
+    priority: i32,
+}
+
+/// An event popped from the queue, alongside its execution time and the handle it was scheduled with. The event should
+/// be handed back to [`EventQueue::release()`] once it has executed.
+pub(super) type PoppedEvent<State, Time, Backend> = (ArenaBox<dyn Event<State, Time, Backend>>, Time, EventHandle);
+
 /// Priority queue of scheduled events.
 ///
-/// Events will execute in ascending order of execution time, with ties broken by the order in which they were pushed
-/// onto the queue. This tiebreaker is in addition to any built-in to the implementation of [`SimTime`] used for the
-/// clock as a way to stabilize the observed order of execution.
+/// Events will execute in ascending order of execution time, with ties broken first by descending priority and then by
+/// the order in which they were pushed onto the queue. These tiebreakers are in addition to any built-in to the
+/// implementation of [`SimTime`] used for the clock as a way to stabilize the observed order of execution.
 ///
 /// This struct is generic over the type used to represent clock time for the sake of tracking the current time, as well
-/// over the type used to represent simulation state so that it can work with appropriate event types.
+/// over the type used to represent simulation state so that it can work with appropriate event types, and over the
+/// [`EventQueueBackend`] that orders the events.
+///
+/// Events scheduled by value are stored in memory reused from events that have already executed or been cancelled, so
+/// that a queue which has reached its working size schedules events without calling on the global allocator. Boxed
+/// events keep their existing allocations.
 ///
 /// An [`EventQueue`] provides several different methods for scheduling new events, but does not publicly support
-/// popping; popping events from the queue only occurs during [`Simulation::run()`].
+/// popping; popping events from the queue only occurs during [`Simulation::run()`]. Scheduled events may be cancelled
+/// by presenting the [`EventHandle`] returned when scheduling them.
 ///
 /// # Safety
 ///
@@ -59,38 +144,85 @@ where
 /// [`Simulation::run()`]: crate::serial::Simulation::run
 /// [`Error::BackInTime`]: crate::Error::BackInTime
 #[derive(Default)]
-pub(super) struct EventQueue<State, Time>
+pub(super) struct EventQueue<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
-    events: BinaryHeapWrapper<State, Time>,
+    events: QueueContents<State, Time, Backend>,
     total_events_scheduled: usize,
 }
 
-impl<State, Time> EventQueue<State, Time>
+impl<State, Time, Backend> EventQueue<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
-    /// Initialize an empty queue
-    pub fn new() -> Self {
+    /// Initialize an empty queue that orders its events with the provided backend, which should also be empty.
+    pub fn new(backend: Backend) -> Self {
         Self {
-            events: BinaryHeapWrapper {
-                heap: BinaryHeap::default(),
+            events: QueueContents {
+                keys: backend,
+                events: HashMap::default(),
+                arena: EventArena::new(),
             },
             total_events_scheduled: 0,
         }
     }
 
-    /// Place an event on the queue. By the time we're here, assume all error checking is complete.
-    pub fn schedule_event(&mut self, event: Box<dyn Event<State, Time>>, time: Time) {
+    /// Place an event on the queue, moving it into the queue's arena. By the time we're here, assume all error checking
+    /// is complete.
+    pub fn schedule_event<EventType>(&mut self, event: EventType, time: Time, priority: i32) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event = self.events.arena.insert(event);
+        // SAFETY: unsizing the pointer leaves it referring to the same event
+        let event = unsafe { event.map_ptr(|ptr| -> NonNull<dyn Event<State, Time, Backend>> { ptr }) };
+        self.push(event, time, priority)
+    }
+
+    /// Place a boxed event on the queue. By the time we're here, assume all error checking is complete.
+    pub fn schedule_boxed_event(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        self.push(ArenaBox::from_box(event), time, priority)
+    }
+
+    /// Push a stored event onto the queue, assigning it the next insertion sequence.
+    fn push(&mut self, event: ArenaBox<dyn Event<State, Time, Backend>>, time: Time, priority: i32) -> EventHandle {
         let count = self.increment_event_count();
-        self.events.heap.push(Reverse(ScheduledEvent {
-            execution_time: time,
-            event,
-            insertion_sequence: count,
-        }));
+        self.events.keys.push(EventKey::new(time, priority, count));
+        self.events.events.insert(count, StoredEvent { event, priority });
+        EventHandle::new(count)
+    }
+
+    /// Remove the event referred to by the handle from the queue, if it is still pending. Returns whether there was
+    /// such an event to remove.
+    pub fn cancel_event(&mut self, handle: EventHandle) -> bool {
+        match self.events.events.remove(&handle.insertion_sequence()) {
+            Some(stored) => {
+                self.events.release(stored.event);
+                self.events.discard_cancelled();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Move the event referred to by the handle to a new execution time, if it is still pending. The event receives a
+    /// new insertion sequence as though it were freshly scheduled, and a handle carrying that sequence is returned. The
+    /// event's priority is unchanged.
+    pub fn reschedule_event(&mut self, handle: EventHandle, time: Time) -> Option<EventHandle> {
+        let stored = self.events.events.remove(&handle.insertion_sequence())?;
+        let new_handle = self.push(stored.event, time, stored.priority);
+        self.events.discard_cancelled();
+        Some(new_handle)
     }
 
     /// Helper function to make sure incrementing the internal count of added events occurs the same way across all
@@ -101,21 +233,50 @@ where
         count
     }
 
-    /// Crate-internal function to pop an event from the queue. Updates the current clock time to match the execution
-    /// time of the popped event.
-    pub fn next(&mut self) -> Option<(Box<dyn Event<State, Time>>, Time)> {
-        if let Some(event_holder) = self.events.heap.pop() {
-            Some((event_holder.0.event, event_holder.0.execution_time))
-        } else {
-            None
-        }
+    /// Crate-internal function to pop an event from the queue, alongside its execution time and the handle it was
+    /// scheduled with.
+    pub fn next(&mut self) -> Option<PoppedEvent<State, Time, Backend>> {
+        let key = self.events.keys.pop()?;
+        let event = self
+            .events
+            .events
+            .remove(&key.insertion_sequence())
+            .expect("front of the backend should always refer to a pending event")
+            .event;
+        self.events.discard_cancelled();
+        let handle = key.handle();
+        Some((event, key.into_execution_time(), handle))
+    }
+
+    /// Crate-internal function to drop an event popped from the queue once it has executed, reclaiming its memory for
+    /// events scheduled later.
+    pub fn release(&mut self, event: ArenaBox<dyn Event<State, Time, Backend>>) {
+        self.events.release(event);
+    }
+
+    /// Get the execution time of the event that will be popped next, if any, without modifying the queue.
+    pub fn peek_time(&self) -> Option<&Time> {
+        self.events.keys.peek().map(EventKey::execution_time)
+    }
+
+    /// Get the number of events that are currently waiting on the queue, excluding any that have been cancelled.
+    pub fn pending_count(&self) -> usize {
+        self.events.events.len()
+    }
+
+    /// Collect views of every event currently waiting on the queue, sorted in the order they will execute.
+    pub fn pending_events(&self) -> Vec<PendingEvent<'_, State, Time, Backend>> {
+        let mut pending: Vec<_> = self.events.pending().collect();
+        pending.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+        pending
     }
 }
 
-impl<State, Time> Debug for EventQueue<State, Time>
+impl<State, Time, Backend> Debug for EventQueue<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     /// Formats the value with the given formatter. Scheduled events will be written in an arbitrary order, and the
     /// `total_events_scheduled` is a total over the entire simulation run as opposed to the number currently on the
@@ -128,12 +289,17 @@ where
     }
 }
 
-impl<State, Time> std::fmt::Display for EventQueue<State, Time>
+impl<State, Time, Backend> std::fmt::Display for EventQueue<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
-        write!(formatter, "EventQueue with {} scheduled events", self.events.heap.len(),)
+        write!(
+            formatter,
+            "EventQueue with {} scheduled events",
+            self.events.events.len(),
+        )
     }
 }
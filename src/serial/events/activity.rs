@@ -0,0 +1,76 @@
+use super::event_traits::{Event, OkEvent};
+use crate::queue::EventQueueBackend;
+use crate::serial::Simulation;
+use crate::{ActivityHandle, EventHandle, Interrupted, SimState, SimTime};
+use std::fmt::Formatter;
+
+/// Schedules an activity's interrupt handler at the current time.
+type InterruptHandler<State, Time, Backend> =
+    Box<dyn FnOnce(&mut Simulation<State, Time, Backend>, Interrupted<Time>) -> crate::Result<EventHandle>>;
+
+/// An activity that has yet to complete, as recorded by the simulation so that it can be interrupted.
+pub(crate) struct PendingActivity<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    pub completion: EventHandle,
+    pub completes_at: Time,
+    pub on_interrupt: InterruptHandler<State, Time, Backend>,
+}
+
+impl<State, Time, Backend> std::fmt::Debug for PendingActivity<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("PendingActivity")
+            .field("completion", &self.completion)
+            .field("completes_at", &self.completes_at)
+            .finish()
+    }
+}
+
+/// The completion of an activity, which ends the activity's registration so that it can no longer be interrupted and
+/// then executes the wrapped event.
+///
+/// Presents itself as the wrapped event in its implementation of [`Debug`] and in its [`name()`], in the same way as a
+/// periodic event's occurrences.
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`name()`]: OkEvent::name
+pub(crate) struct Activity<EventType> {
+    pub handle: ActivityHandle,
+    pub event: EventType,
+}
+
+impl<State, Time, Backend, EventType> OkEvent<State, Time, Backend> for Activity<EventType>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+    EventType: Event<State, Time, Backend>,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        simulation.finish_activity(self.handle);
+        if let Err(error) = self.event.execute(simulation) {
+            simulation.defer_error(error);
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.event.name()
+    }
+}
+
+impl<EventType> std::fmt::Debug for Activity<EventType>
+where
+    EventType: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.event.fmt(f)
+    }
+}
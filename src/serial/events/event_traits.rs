@@ -1,3 +1,4 @@
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
 use crate::serial::Simulation;
 use crate::{SimState, SimTime};
 use std::fmt::Debug;
@@ -8,16 +9,22 @@ use std::fmt::Debug;
 /// generic over the types used to represent simulation state and clock time to enable your implementations of each
 /// trait to work together within this framework.
 ///
+/// The final type parameter names the [`EventQueueBackend`] used by the simulation that executes the event. It defaults
+/// to [`BinaryHeapQueue`], and so only needs to be named by events for simulations constructed with another backend.
+///
 /// Requiring implementors to be [`Debug`] enables printing the full contents of a [`Simulation`]'s internal event queue
 /// when necessary.
 ///
-/// Note that desque does not directly support the notion of interrupting events, so if you need that functionality then
-/// you may wish to extend this trait or to otherwise provide a means for your interruptible events to determine whether
-/// they should execute when popped from the queue.
-pub trait Event<State, Time>: Debug
+/// An event that should no longer execute, e.g. a timeout that has been superseded, may be retracted from the queue by
+/// passing the [`EventHandle`] returned when scheduling it to [`Simulation::cancel()`].
+///
+/// [`EventHandle`]: crate::EventHandle
+/// [`Simulation::cancel()`]: Simulation::cancel
+pub trait Event<State, Time, Backend = BinaryHeapQueue<Time>>: Debug
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     /// Update the simulation according to the specific type of event. The simulation will invoke this method during
     /// [`Simulation::run()`] for each scheduled event in sequence. Exclusive access is provided to the simulation while
@@ -45,7 +52,16 @@ where
     /// [`dyn std::error::Error`]: std::error::Error
     /// [`Error`]: crate::Error
     /// [`Error::BadExecution`]: crate::Error::BadExecution
-    fn execute(&mut self, simulation: &mut Simulation<State, Time>) -> crate::Result;
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) -> crate::Result;
+
+    /// Get a name for this kind of event, for use when tracing or otherwise reporting on executed events.
+    ///
+    /// The default implementation returns the name of the implementing type as reported by [`std::any::type_name()`].
+    /// That name is intended for diagnostics only and may vary between compiler versions, so override this method if
+    /// your traces must remain comparable across toolchains, or to distinguish different instances of one type.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
 
 /// An [`Event`] that is guaranteed not to return an [`Error`] on execution.
@@ -61,10 +77,11 @@ where
 /// [`Event::execute()`]: Event::execute
 /// [`OkEvent::execute()`]: OkEvent::execute
 /// [`Error`]: crate::Error
-pub trait OkEvent<State, Time>: Debug
+pub trait OkEvent<State, Time, Backend = BinaryHeapQueue<Time>>: Debug
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     /// Update the simulation according to the specific type of event. The simulation will invoke this method during
     /// [`Simulation::run()`] for each scheduled event in sequence. Exclusive access is provided to the simulation
@@ -73,17 +90,31 @@ where
     /// Note that the simulation's clock time will update before invoking this method.
     ///
     /// [`Simulation::run()`]: Simulation::run
-    fn execute(&mut self, simulation: &mut Simulation<State, Time>);
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>);
+
+    /// Get a name for this kind of event, for use when tracing or otherwise reporting on executed events.
+    ///
+    /// The default implementation returns the name of the implementing type as reported by [`std::any::type_name()`].
+    /// That name is intended for diagnostics only and may vary between compiler versions, so override this method if
+    /// your traces must remain comparable across toolchains, or to distinguish different instances of one type.
+    fn name(&self) -> &str {
+        std::any::type_name::<Self>()
+    }
 }
 
-impl<State, Time, OkEventType> Event<State, Time> for OkEventType
+impl<State, Time, Backend, OkEventType> Event<State, Time, Backend> for OkEventType
 where
     State: SimState<Time>,
     Time: SimTime,
-    OkEventType: OkEvent<State, Time>,
+    Backend: EventQueueBackend<Time>,
+    OkEventType: OkEvent<State, Time, Backend>,
 {
-    fn execute(&mut self, simulation: &mut Simulation<State, Time>) -> crate::Result {
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) -> crate::Result {
         OkEvent::execute(self, simulation);
         Ok(())
     }
+
+    fn name(&self) -> &str {
+        OkEvent::name(self)
+    }
 }
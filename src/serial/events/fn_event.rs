@@ -0,0 +1,117 @@
+use super::event_traits::OkEvent;
+use crate::queue::EventQueueBackend;
+use crate::serial::Simulation;
+use crate::{SimState, SimTime};
+use std::fmt::Formatter;
+
+/// An [`Event`] that executes a closure, for callbacks too small to warrant a named type.
+///
+/// The closure receives exclusive access to the simulation, just as [`Event::execute()`] does, and is consumed when the
+/// event executes. Closures are usually scheduled through [`Simulation::schedule_fn()`] or
+/// [`Simulation::schedule_labelled_fn()`], which can infer the type of the closure's argument; constructing an
+/// [`FnEvent`] directly, e.g. to pass it to [`Simulation::schedule_with_priority()`], requires annotating that type.
+///
+/// This type implements [`OkEvent`] rather than [`Event`] directly, as the blanket implementation of the latter would
+/// otherwise conflict. Errors returned by the closure are still passed back from [`Simulation::run()`] and
+/// [`Simulation::step()`] as though the event itself had failed.
+///
+/// An optional label serves as the event's [`name()`] and appears in its implementation of [`Debug`]. Unlabelled events
+/// fall back to the name of the closure's type, which identifies where the closure was defined but may vary between
+/// compiler versions.
+///
+/// ```
+/// use desque::serial::{FnEvent, Simulation};
+/// use desque::SimState;
+///
+/// struct Counter(u32);
+/// impl SimState<u32> for Counter {}
+///
+/// let mut sim = Simulation::new(Counter(0), 0);
+/// sim.schedule_fn(|sim| {
+///     sim.state_mut().0 += 1;
+///     Ok(())
+/// }, 3)?;
+///
+/// let bump = FnEvent::labelled("bump", |sim: &mut Simulation<Counter, u32>| {
+///     sim.state_mut().0 += 10;
+///     Ok(())
+/// });
+/// assert_eq!("FnEvent(\"bump\")", format!("{:?}", bump));
+/// sim.schedule_with_priority(bump, 3, 1)?;
+///
+/// sim.run()?;
+/// assert_eq!(11, sim.state().0);
+/// # Ok::<(), desque::Error>(())
+/// ```
+///
+/// [`Event`]: super::Event
+/// [`Event::execute()`]: super::Event::execute
+/// [`name()`]: OkEvent::name
+/// [`Simulation::run()`]: Simulation::run
+/// [`Simulation::step()`]: Simulation::step
+/// [`Debug`]: std::fmt::Debug
+/// [`Simulation::schedule_fn()`]: Simulation::schedule_fn
+/// [`Simulation::schedule_labelled_fn()`]: Simulation::schedule_labelled_fn
+/// [`Simulation::schedule_with_priority()`]: Simulation::schedule_with_priority
+pub struct FnEvent<F> {
+    /// The closure to call, taken when the event executes.
+    function: Option<F>,
+    label: Option<&'static str>,
+}
+
+impl<F> FnEvent<F> {
+    /// Wrap a closure as an unlabelled event.
+    pub fn new(function: F) -> Self {
+        Self {
+            function: Some(function),
+            label: None,
+        }
+    }
+
+    /// Wrap a closure as an event with the provided label.
+    pub fn labelled(label: &'static str, function: F) -> Self {
+        Self {
+            function: Some(function),
+            label: Some(label),
+        }
+    }
+
+    /// Get the event's label, if it has one.
+    pub fn label(&self) -> Option<&'static str> {
+        self.label
+    }
+}
+
+impl<State, Time, Backend, F> OkEvent<State, Time, Backend> for FnEvent<F>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+    F: FnOnce(&mut Simulation<State, Time, Backend>) -> crate::Result,
+{
+    /// Call the wrapped closure. Any error it returns is handed to the simulation, which reports it once this event
+    /// finishes executing.
+    ///
+    /// # Panics
+    ///
+    /// The closure can only be called once, so executing the same event a second time will panic. Simulations never do
+    /// so.
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        let function = self.function.take().expect("closure event should only execute once");
+        if let Err(error) = function(simulation) {
+            simulation.defer_error(error);
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.label.unwrap_or_else(std::any::type_name::<F>)
+    }
+}
+
+impl<F> std::fmt::Debug for FnEvent<F> {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_tuple("FnEvent")
+            .field(&self.label.unwrap_or_else(std::any::type_name::<F>))
+            .finish()
+    }
+}
@@ -1,16 +1,43 @@
+use super::events::activity::{Activity, PendingActivity};
+use super::events::occurrence::{Occurrence, Recurring};
 use super::events::EventQueue;
-use super::Event;
-use crate::{SimState, SimTime};
+use super::{Event, FnEvent, PendingEvent};
+use crate::condition::{ConditionHandle, Waiter, WaiterId};
+use crate::observer::{AnyObserver, ObservedEvent, ObserverList};
+use crate::process::{Process, ProcessContext, ProcessEvent, ProcessHandle, Task};
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::resource::{Container, PreemptiveResource, Resource, Store};
+use crate::{
+    ActivityHandle, EventHandle, Interrupted, Observer, ObserverHandle, PeriodicHandle, Recurrence, Signal, SimState,
+    SimTime, StepOutcome,
+};
+
+use std::collections::HashMap;
 
 use std::fmt::{Debug, Formatter};
-use std::ops::Add;
+use std::future::Future;
+use std::ops::{Add, Sub};
+
+/// A predicate on the simulation's state, checked after each event executes.
+type StatePredicate<State> = Box<dyn Fn(&State) -> bool>;
+
+/// Schedules whatever is waiting at the current time.
+type Resumption<State, Time, Backend> = Box<dyn FnOnce(&mut Simulation<State, Time, Backend>) -> crate::Result>;
+
+type SimWaiter<State, Time, Backend> = Waiter<StatePredicate<State>, Resumption<State, Time, Backend>>;
 
 /// Contains the event queue and other state belonging to a simulation.
 ///
 /// The defining struct for a discrete-event simulation in desque. A [`Simulation`] owns both its client-provided state
 /// and its internal event queue, alongside the authoritative current simulation time. The simulation provides both
 /// shared and mutable access to the client-provided state, shared access to the current time, and a variety of methods
-/// to schedule new events on the internal queue.
+/// to schedule new events on the internal queue. Each scheduling method hands back an [`EventHandle`] which may be
+/// passed to [`cancel()`] to retract the event before it executes. Events scheduled for the same time execute in the
+/// order they were scheduled, unless given distinct priorities through [`schedule_with_priority()`].
+///
+/// Events passed by value to the scheduling methods are stored in memory recycled from events that have already
+/// executed or been cancelled, so a simulation whose queue has reached its working size schedules events without
+/// allocating. The `_from_boxed` variants instead keep the allocation of the box they are given.
 ///
 /// The expected workflow for a Simulation is:
 ///
@@ -24,21 +51,47 @@ use std::ops::Add;
 /// > `total_events_scheduled` is over the entirety of the simulation run, as opposed to the number currently in queue.
 ///
 /// [`new()`]: Simulation::new
+/// [`cancel()`]: Simulation::cancel
+/// [`schedule_with_priority()`]: Simulation::schedule_with_priority
 /// [`run()`]: Simulation::run
 /// [`state()`]: Simulation::state
 /// [`state_mut()`]: Simulation::state_mut
 #[derive(Debug, Default)]
-pub struct Simulation<State, Time>
+pub struct Simulation<State, Time, Backend = BinaryHeapQueue<Time>>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     /// A priority queue of events that have been scheduled to execute, ordered ascending by execution time.
-    event_queue: EventQueue<State, Time>,
+    event_queue: EventQueue<State, Time, Backend>,
     /// The current shared state of the Simulation. Exclusive access will be granted to each event that executes.
     state: State,
     /// The current simulation time.
     current_time: Time,
+    /// Observers to notify before and after each event executes.
+    observers: ObserverList<Time, dyn AnyObserver<Time>>,
+    /// The first error raised by a wrapper event, such as a closure, an occurrence or a resumed process, held until
+    /// that event finishes executing so that it can be reported in place of the event's own result.
+    ///
+    /// The wrappers implement `OkEvent` and report through here because they cannot implement `Event` directly:
+    /// they are generic over the simulation's state, so a downstream crate could implement `OkEvent` for them with
+    /// its own state type, which makes any direct implementation conflict with the blanket one.
+    deferred_error: Option<crate::Error>,
+    /// The pending occurrence of each recurrence that has not yet ended.
+    recurrences: HashMap<PeriodicHandle, EventHandle>,
+    /// The number of recurrences started over the simulation's lifetime, used to assign their handles.
+    recurrences_started: usize,
+    /// The pending completion of each activity that may still be interrupted.
+    activities: HashMap<ActivityHandle, PendingActivity<State, Time, Backend>>,
+    /// The number of activities started over the simulation's lifetime, used to assign their handles.
+    activities_started: usize,
+    /// Registered conditions and suspended processes, in the order they began waiting.
+    waiters: Vec<SimWaiter<State, Time, Backend>>,
+    /// The number of conditions registered over the simulation's lifetime, used to assign their handles.
+    conditions_registered: usize,
+    /// The number of processes started over the simulation's lifetime, used to assign their handles.
+    processes_started: usize,
 }
 
 impl<State, Time> Simulation<State, Time>
@@ -47,12 +100,41 @@ where
     Time: SimTime,
 {
     /// Initialize a Simulation instance with the provided starting state and an empty event queue, with clock set to
-    /// the provided starting time.
+    /// the provided starting time. Events are ordered by the default [`BinaryHeapQueue`] backend; use
+    /// [`with_backend()`] to select another.
+    ///
+    /// [`with_backend()`]: Simulation::with_backend
     pub fn new(initial_state: State, start_time: Time) -> Self {
+        Self::with_backend(initial_state, start_time, BinaryHeapQueue::new())
+    }
+}
+
+impl<State, Time, Backend> Simulation<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Initialize a Simulation instance with the provided starting state and an empty event queue ordered by the
+    /// provided backend, with clock set to the provided starting time. The backend should not hold any keys.
+    ///
+    /// See [`queue`] for the available backends and guidance on choosing between them.
+    ///
+    /// [`queue`]: crate::queue
+    pub fn with_backend(initial_state: State, start_time: Time, backend: Backend) -> Self {
         Self {
-            event_queue: EventQueue::new(),
+            event_queue: EventQueue::new(backend),
             state: initial_state,
             current_time: start_time,
+            observers: ObserverList::new(),
+            deferred_error: None,
+            recurrences: HashMap::new(),
+            recurrences_started: 0,
+            activities: HashMap::new(),
+            activities_started: 0,
+            waiters: Vec::new(),
+            conditions_registered: 0,
+            processes_started: 0,
         }
     }
 
@@ -65,6 +147,9 @@ where
     /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
     ///    otherwise return to step 1.
     ///
+    /// Any [`Observer`]s attached through [`add_observer()`] are notified immediately before and after each event
+    /// executes.
+    ///
     /// # Errors
     ///
     /// Errors may occur during execution of events, and if encountered here they will be passed back to the caller,
@@ -78,31 +163,131 @@ where
     ///
     /// [`state.is_complete()`]: SimState::is_complete
     /// [`event.execute()`]: Event::execute
+    /// [`add_observer()`]: Simulation::add_observer
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Error::BadExecution`]: crate::Error::BadExecution
     /// [`BadExecution`]: crate::Error::BadExecution
     /// [`source()`]: crate::Error#method.source
-    // the detected panic in here is a false alarm as the call to unwrap
-    // is immediately preceded by a check that the Option is Some
-    #[allow(clippy::missing_panics_doc)]
     pub fn run(&mut self) -> crate::Result {
-        loop {
-            if self.state.is_complete(self.current_time()) {
-                return Ok(());
-            }
+        while let StepOutcome::Executed(_) = self.step()? {}
+        Ok(())
+    }
 
-            let next_event = self.next_event();
-            if next_event.is_none() {
-                return Ok(());
+    /// Execute at most `event_count` events from the priority queue, one at a time, in ascending order by execution
+    /// time. Returns the number of events that were executed, which will be less than `event_count` only if the
+    /// simulation reached completion or ran out of events first.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once `event_count`
+    /// events have been executed. Each call picks up where the previous call left off, making this method suitable for
+    /// e.g. a warm-up period of fixed length.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing events are passed back to the caller, unchanged, as with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    pub fn run_for(&mut self, event_count: usize) -> crate::Result<usize> {
+        let mut events_executed = 0;
+        while events_executed < event_count {
+            match self.step()? {
+                StepOutcome::Executed(_) => events_executed += 1,
+                StepOutcome::Complete | StepOutcome::QueueEmpty => break,
             }
+        }
+        Ok(events_executed)
+    }
+
+    /// Execute exactly one event from the priority queue, if possible, and report what happened.
+    ///
+    /// Performs a single iteration of the loop described on [`run()`]:
+    ///
+    /// 1. Does [`state.is_complete()`] return true? If so, return [`StepOutcome::Complete`].
+    /// 2. Attempt to pop the next event from the queue. If there isn't one, return [`StepOutcome::QueueEmpty`].
+    /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
+    ///    otherwise return [`StepOutcome::Executed`] with the handle that the event was scheduled with.
+    ///
+    /// As with [`run()`], any attached [`Observer`]s are notified immediately before and after the event executes.
+    ///
+    /// Stepping through a simulation is useful for driving it from a debugger or interactive session, or for inspecting
+    /// the simulation's state between events.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing the event, or while scheduling whatever it leaves ready to resume, are passed
+    /// back to the caller, unchanged, as with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`event.execute()`]: Event::execute
+    /// [`StepOutcome::Complete`]: crate::StepOutcome::Complete
+    /// [`StepOutcome::QueueEmpty`]: crate::StepOutcome::QueueEmpty
+    /// [`StepOutcome::Executed`]: crate::StepOutcome::Executed
+    pub fn step(&mut self) -> crate::Result<StepOutcome> {
+        if self.state.is_complete(self.current_time()) {
+            return Ok(StepOutcome::Complete);
+        }
+
+        match self.event_queue.next() {
+            Some((mut event, time, handle)) => {
+                self.current_time = time;
+                self.observers
+                    .before_event(&ObservedEvent::new(&event, event.name(), &self.current_time, handle));
+                let result = match (event.execute(self), self.deferred_error.take()) {
+                    (Ok(()), Some(error)) => Err(error),
+                    (result, _) => result,
+                };
+                self.observers.after_event(
+                    &ObservedEvent::new(&event, event.name(), &self.current_time, handle),
+                    &result,
+                );
+                self.event_queue.release(event);
+                let result = if self.waiters.is_empty() {
+                    result
+                } else {
+                    result.and_then(|()| self.resume_satisfied())
+                };
+                result.map(|_| StepOutcome::Executed(handle))
+            },
+            None => Ok(StepOutcome::QueueEmpty),
+        }
+    }
 
-            let mut next_event = next_event.expect("next_event should not be None");
-            next_event.execute(self)?;
+    /// Resume each waiter whose predicate now holds, in the order they began waiting.
+    fn resume_satisfied(&mut self) -> crate::Result {
+        let mut index = 0;
+        while index < self.waiters.len() {
+            let satisfied = match &self.waiters[index].predicate {
+                Some(predicate) => predicate(&self.state),
+                None => false,
+            };
+            if satisfied {
+                let waiter = self.waiters.remove(index);
+                (waiter.resume)(self)?;
+            } else {
+                index += 1;
+            }
         }
+        Ok(())
+    }
+
+    /// Remove the waiter with the provided id, if it is still waiting and matches the filter.
+    fn take_waiter(
+        &mut self,
+        id: WaiterId,
+        filter: impl Fn(&SimWaiter<State, Time, Backend>) -> bool,
+    ) -> Option<SimWaiter<State, Time, Backend>> {
+        let position = self
+            .waiters
+            .iter()
+            .position(|waiter| waiter.id == id && filter(waiter))?;
+        Some(self.waiters.remove(position))
     }
 
-    fn next_event(&mut self) -> Option<Box<dyn Event<State, Time>>> {
-        if let Some((event, time)) = self.event_queue.next() {
+    // only needed by tests that pop events without executing them,
+    // as step() works with the handle of the popped event as well
+    #[cfg(test)]
+    fn next_event(&mut self) -> Option<crate::arena::ArenaBox<dyn Event<State, Time, Backend>>> {
+        if let Some((event, time, _)) = self.event_queue.next() {
             self.current_time = time;
             Some(event)
         } else {
@@ -118,9 +303,9 @@ where
     /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule<EventType>(&mut self, event: EventType, time: Time) -> crate::Result
+    pub fn schedule<EventType>(&mut self, event: EventType, time: Time) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
         if time < self.current_time {
             return Err(crate::Error::BackInTime);
@@ -129,10 +314,7 @@ where
         // SAFETY: we've just checked that the desired execution time is either
         // Equal or Greater when compared to the current clock time, so it'll
         // be fine to add to the queue
-        unsafe {
-            self.schedule_unchecked(event, time);
-        }
-        Ok(())
+        unsafe { Ok(self.schedule_unchecked(event, time)) }
     }
 
     /// Schedule the provided event at the specified time. Assumes that the provided time is valid in the context of the
@@ -144,11 +326,11 @@ where
     /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
     /// already enforced at the call site through some other means. For example, adding a strictly positive offset to
     /// the current clock time to get the `time` argument for the call.
-    pub unsafe fn schedule_unchecked<EventType>(&mut self, event: EventType, time: Time)
+    pub unsafe fn schedule_unchecked<EventType>(&mut self, event: EventType, time: Time) -> EventHandle
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
-        self.schedule_unchecked_from_boxed(Box::new(event), time);
+        self.schedule_with_priority_unchecked(event, time, 0)
     }
 
     /// Schedule the provided event at the specified time.
@@ -159,7 +341,11 @@ where
     /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule_from_boxed(&mut self, event: Box<dyn Event<State, Time>>, time: Time) -> crate::Result {
+    pub fn schedule_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+    ) -> crate::Result<EventHandle> {
         if time < self.current_time {
             return Err(crate::Error::BackInTime);
         }
@@ -167,10 +353,7 @@ where
         // SAFETY: we've just checked that the desired execution time is either
         // Equal or Greater when compared to the current clock time, so it'll
         // be fine to add to the queue
-        unsafe {
-            self.schedule_unchecked_from_boxed(event, time);
-        }
-        Ok(())
+        unsafe { Ok(self.schedule_unchecked_from_boxed(event, time)) }
     }
 
     /// Schedule the provided event at the specified time. Assumes that the provided time is valid in the context of the
@@ -182,8 +365,262 @@ where
     /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
     /// already enforced at the call site through some other means. For example, adding a strictly positive offset to
     /// the current clock time to get the `time` argument for the call.
-    pub unsafe fn schedule_unchecked_from_boxed(&mut self, event: Box<dyn Event<State, Time>>, time: Time) {
-        self.event_queue.schedule_event(event, time);
+    pub unsafe fn schedule_unchecked_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+    ) -> EventHandle {
+        self.schedule_with_priority_unchecked_from_boxed(event, time, 0)
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Among events scheduled for the same
+    /// time, those with a higher priority execute first, while events with equal priorities execute in the order they
+    /// were scheduled. Events scheduled without an explicit priority receive a priority of 0.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_priority<EventType>(
+        &mut self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> crate::Result<EventHandle>
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_with_priority_unchecked(event, time, priority)) }
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Assumes that the provided time is
+    /// valid in the context of the client's simulation.
+    ///
+    /// Among events scheduled for the same time, those with a higher priority execute first, while events with equal
+    /// priorities execute in the order they were scheduled.
+    ///
+    /// # Safety
+    ///
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means.
+    pub unsafe fn schedule_with_priority_unchecked<EventType>(
+        &mut self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        self.event_queue.schedule_event(event, time, priority)
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Among events scheduled for the same
+    /// time, those with a higher priority execute first, while events with equal priorities execute in the order they
+    /// were scheduled.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_priority_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_with_priority_unchecked_from_boxed(event, time, priority)) }
+    }
+
+    /// Schedule the provided event at the specified time with the given priority. Assumes that the provided time is
+    /// valid in the context of the client's simulation.
+    ///
+    /// Among events scheduled for the same time, those with a higher priority execute first, while events with equal
+    /// priorities execute in the order they were scheduled.
+    ///
+    /// # Safety
+    ///
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means.
+    pub unsafe fn schedule_with_priority_unchecked_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        self.event_queue.schedule_boxed_event(event, time, priority)
+    }
+
+    /// Hold an error raised by a wrapper event until that event finishes executing. Only the first error deferred
+    /// during an event is reported, as any later ones are likely to be consequences of it.
+    pub(crate) fn defer_error(&mut self, error: crate::Error) {
+        self.deferred_error.get_or_insert(error);
+    }
+
+    /// Schedule the provided closure to execute as an event at the specified time. The closure receives exclusive
+    /// access to the simulation, as [`Event::execute()`] does, and may fail in the same ways.
+    ///
+    /// The scheduled event is an unlabelled [`FnEvent`]; see [`schedule_labelled_fn()`] to name it for tracing.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Event::execute()`]: Event::execute
+    /// [`FnEvent`]: super::FnEvent
+    /// [`schedule_labelled_fn()`]: Simulation::schedule_labelled_fn
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_fn<F>(&mut self, function: F, time: Time) -> crate::Result<EventHandle>
+    where
+        F: FnOnce(&mut Self) -> crate::Result + 'static,
+    {
+        self.schedule(FnEvent::new(function), time)
+    }
+
+    /// Schedule the provided closure to execute as an event at the specified time, labelled for [`Debug`] output and
+    /// for tracing as the event's [`name()`].
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`name()`]: Event::name
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_labelled_fn<F>(
+        &mut self,
+        label: &'static str,
+        function: F,
+        time: Time,
+    ) -> crate::Result<EventHandle>
+    where
+        F: FnOnce(&mut Self) -> crate::Result + 'static,
+    {
+        self.schedule(FnEvent::labelled(label, function), time)
+    }
+
+    /// Cancel a previously scheduled event so that it will not execute. Returns `true` if the event referred to by
+    /// `handle` was still pending and has now been removed from the queue, or `false` if it had already executed or
+    /// been cancelled.
+    ///
+    /// The cancelled event is dropped immediately. Cancelling the completion of an activity started by
+    /// [`start_activity()`] abandons the activity, which can then no longer be interrupted.
+    ///
+    /// [`start_activity()`]: Simulation::start_activity
+    pub fn cancel(&mut self, handle: EventHandle) -> bool {
+        let cancelled = self.event_queue.cancel_event(handle);
+        if cancelled {
+            self.activities.retain(|_, activity| activity.completion != handle);
+        }
+        cancelled
+    }
+
+    /// Stop a recurrence started by [`schedule_periodic()`], cancelling its pending occurrence so that no further
+    /// occurrences execute. Returns `true` if the recurrence was still running, or `false` if it had already ended or
+    /// been stopped.
+    ///
+    /// Stopping a recurrence from within one of its own occurrences prevents the next occurrence from being scheduled.
+    ///
+    /// [`schedule_periodic()`]: Simulation::schedule_periodic
+    pub fn stop_periodic(&mut self, handle: PeriodicHandle) -> bool {
+        match self.recurrences.remove(&handle) {
+            Some(pending) => {
+                self.event_queue.cancel_event(pending);
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Check whether a recurrence is still running.
+    pub(super) fn is_periodic(&self, handle: PeriodicHandle) -> bool {
+        self.recurrences.contains_key(&handle)
+    }
+
+    /// End an activity's registration as it completes.
+    pub(super) fn finish_activity(&mut self, handle: ActivityHandle) {
+        self.activities.remove(&handle);
+    }
+
+    /// Wake a process that suspended itself with [`Yield::Passivate`], scheduling it to resume at the current time.
+    /// Returns `true` if the process referred to by `handle` was passive and has now been scheduled, or `false` if it
+    /// was holding, waiting on a condition or resource, or had already finished.
+    ///
+    /// # Errors
+    ///
+    /// If the process cannot be scheduled at the current time, returns an [`Error::BackInTime`] under the same
+    /// condition as [`schedule_now()`]. The process is no longer passive in that case, and will not resume.
+    ///
+    /// [`Yield::Passivate`]: crate::process::Yield::Passivate
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn activate(&mut self, handle: ProcessHandle) -> crate::Result<bool> {
+        match self.take_waiter(WaiterId::Process(handle), |waiter| waiter.predicate.is_none()) {
+            Some(waiter) => (waiter.resume)(self).map(|()| true),
+            None => Ok(false),
+        }
+    }
+
+    /// Withdraw a condition registered by [`schedule_when()`], so that its event will not be scheduled. Returns `true`
+    /// if the condition was still waiting to hold, or `false` if its event had already been scheduled or the condition
+    /// had already been withdrawn.
+    ///
+    /// [`schedule_when()`]: Simulation::schedule_when
+    pub fn cancel_condition(&mut self, handle: ConditionHandle) -> bool {
+        self.take_waiter(WaiterId::Condition(handle), |_| true).is_some()
+    }
+
+    /// Move a pending event to a new execution time, returning a fresh [`EventHandle`] that refers to the moved event.
+    ///
+    /// The event is treated as though it were newly scheduled for `time` with its original priority: it receives a new
+    /// insertion sequence, and so will execute after any other events of equal priority already scheduled for that same
+    /// time. The handle passed in no longer refers to the event once this method succeeds, so hold on to the returned
+    /// handle instead.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue. If `handle` does not refer to a
+    /// pending event, e.g. because that event has already executed or been cancelled, returns an
+    /// [`Error::EventNotFound`] instead. The completion of an activity started by [`start_activity()`] is not treated
+    /// as pending here either, as [`interrupt()`] relies on the completion time the activity was started with.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    /// [`start_activity()`]: Simulation::start_activity
+    /// [`interrupt()`]: Simulation::interrupt
+    pub fn reschedule(&mut self, handle: EventHandle, time: Time) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+        if self.activities.values().any(|activity| activity.completion == handle) {
+            return Err(crate::Error::EventNotFound);
+        }
+
+        self.event_queue
+            .reschedule_event(handle, time)
+            .ok_or(crate::Error::EventNotFound)
     }
 
     /// Get a shared reference to the simulation state.
@@ -200,15 +637,125 @@ where
     pub fn current_time(&self) -> &Time {
         &self.current_time
     }
+
+    /// Attach an observer that will be notified immediately before and after each event executes, returning a handle
+    /// through which the observer may later be retrieved.
+    ///
+    /// Observers are notified in the order they were attached.
+    pub fn add_observer<ObserverType>(&mut self, observer: ObserverType) -> ObserverHandle<ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.add(Box::new(observer))
+    }
+
+    /// Get a shared reference to a previously attached observer, e.g. to read the results it has gathered. Returns
+    /// `None` if the observer has since been removed.
+    pub fn observer<ObserverType>(&self, handle: &ObserverHandle<ObserverType>) -> Option<&ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.get(handle)
+    }
+
+    /// Get an exclusive reference to a previously attached observer. Returns `None` if the observer has since been
+    /// removed.
+    pub fn observer_mut<ObserverType>(&mut self, handle: &ObserverHandle<ObserverType>) -> Option<&mut ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.get_mut(handle)
+    }
+
+    /// Detach a previously attached observer so that it will no longer be notified of executing events, handing it
+    /// back to the caller. Returns `None` if the observer has already been removed.
+    pub fn remove_observer<ObserverType>(&mut self, handle: ObserverHandle<ObserverType>) -> Option<ObserverType>
+    where
+        ObserverType: Observer<Time> + 'static,
+    {
+        self.observers.remove(handle)
+    }
+
+    /// Get a shared reference to the execution time of the next event on the queue, if any, without executing it.
+    ///
+    /// Cancelled events are never reported here. This method is useful for e.g. deciding how far a continuous process
+    /// may be integrated before the next discrete event interrupts it.
+    pub fn peek_next_time(&self) -> Option<&Time> {
+        self.event_queue.peek_time()
+    }
+
+    /// Get the number of events currently waiting on the queue. Cancelled events are not counted.
+    pub fn pending_event_count(&self) -> usize {
+        self.event_queue.pending_count()
+    }
+
+    /// Iterate over views of every event currently waiting on the queue, in the order they are expected to execute.
+    ///
+    /// Each [`PendingEvent`] provides the event's execution time, the [`EventHandle`] it was scheduled with, and shared
+    /// access to the event itself for printing via [`Debug`]. Cancelled events are skipped.
+    ///
+    /// Note that gathering this view of the queue requires sorting its contents, and so should be avoided in hot loops.
+    ///
+    /// [`PendingEvent`]: super::PendingEvent
+    pub fn pending_events(&self) -> impl Iterator<Item = PendingEvent<'_, State, Time, Backend>> {
+        self.event_queue.pending_events().into_iter()
+    }
 }
 
-impl<State, Time> Simulation<State, Time>
+impl<State, Time, Backend> Simulation<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime + Clone,
+    Backend: EventQueueBackend<Time>,
 {
+    /// Execute every event scheduled at or before `horizon`, one at a time, in ascending order by execution time, then
+    /// advance the clock to `horizon`.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once the next
+    /// event on the queue is scheduled for some time after `horizon`. Events scheduled for exactly `horizon` will
+    /// execute. If the loop stops because the event queue is empty or because the next event lies beyond `horizon`, the
+    /// clock is then set to a clone of `horizon`. If instead [`state.is_complete()`] returns true, the clock is left at
+    /// the execution time of the last event so that it accurately reflects when the simulation finished.
+    ///
+    /// Splitting a run with this method allows for e.g. clearing statistics after a warm-up period, then continuing
+    /// with the measurement phase.
+    ///
+    /// # Errors
+    ///
+    /// If `horizon` is less than the current clock time on `self`, returns an [`Error::BackInTime`] without executing
+    /// any events. Otherwise, errors encountered while executing events are passed back to the caller, unchanged, as
+    /// with [`run()`].
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn run_until(&mut self, horizon: &Time) -> crate::Result {
+        if *horizon < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        loop {
+            match self.event_queue.peek_time() {
+                Some(next_time) if next_time <= horizon => {},
+                _ => break,
+            }
+
+            match self.step()? {
+                StepOutcome::Executed(_) => {},
+                StepOutcome::Complete => return Ok(()),
+                StepOutcome::QueueEmpty => break,
+            }
+        }
+
+        if !self.state.is_complete(self.current_time()) {
+            self.current_time = horizon.clone();
+        }
+        Ok(())
+    }
+
     /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does due to the use of insertion sequence as a tiebreaker.
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
     ///
     /// # Errors
     ///
@@ -217,16 +764,17 @@ where
     /// expected from implementations of [`Clone::clone`] in most cases.
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule_now<EventType>(&mut self, event: EventType) -> crate::Result
+    pub fn schedule_now<EventType>(&mut self, event: EventType) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
         let event_time = self.current_time.clone();
         self.schedule(event, event_time)
     }
 
     /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does due to the use of insertion sequence as a tiebreaker.
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
     ///
     /// # Safety
     ///
@@ -234,15 +782,16 @@ where
     /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
     /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
     /// chosen type, this method will be safe to call.
-    pub unsafe fn schedule_now_unchecked<EventType>(&mut self, event: EventType)
+    pub unsafe fn schedule_now_unchecked<EventType>(&mut self, event: EventType) -> EventHandle
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
-        self.schedule_unchecked(event, self.current_time.clone());
+        self.schedule_unchecked(event, self.current_time.clone())
     }
 
     /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does due to the use of insertion sequence as a tiebreaker.
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
     ///
     /// # Errors
     ///
@@ -251,13 +800,17 @@ where
     /// expected from implementations of [`Clone::clone`] in most cases.
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule_now_from_boxed(&mut self, event: Box<dyn Event<State, Time>>) -> crate::Result {
+    pub fn schedule_now_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+    ) -> crate::Result<EventHandle> {
         let event_time = self.current_time.clone();
         self.schedule_from_boxed(event, event_time)
     }
 
     /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does due to the use of insertion sequence as a tiebreaker.
+    /// execute before this event does, unless scheduled with a negative priority, due to the use of insertion sequence
+    /// as a tiebreaker.
     ///
     /// # Safety
     ///
@@ -265,62 +818,351 @@ where
     /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
     /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
     /// chosen type, this method will be safe to call.
-    pub unsafe fn schedule_now_unchecked_from_boxed(&mut self, event: Box<dyn Event<State, Time>>) {
-        self.schedule_unchecked_from_boxed(event, self.current_time.clone());
+    pub unsafe fn schedule_now_unchecked_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+    ) -> EventHandle {
+        self.schedule_unchecked_from_boxed(event, self.current_time.clone())
     }
-}
 
-impl<State, Time> Simulation<State, Time>
-where
-    State: SimState<Time>,
-    Time: SimTime + Clone + Add<Output = Time>,
-{
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
+    /// Register an event to be scheduled once the provided predicate holds for the simulation's state.
     ///
-    /// # Errors
+    /// Registered predicates are checked after each event executes, in the order they were registered, much as
+    /// [`SimState::is_complete()`] is checked before each event. The first time a predicate is found to hold, its event
+    /// is scheduled at the current time and the condition removed, so an event that should recur must register its
+    /// condition again. A predicate that already holds when registered is only found to do so once the next event
+    /// finishes executing. The returned [`ConditionHandle`] may be passed to [`cancel_condition()`] to withdraw the
+    /// condition beforehand.
     ///
-    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
-    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    /// Should the event fail to be scheduled once its condition holds, under the same condition as [`schedule_now()`],
+    /// the resulting [`Error::BackInTime`] is passed back from the [`step()`] that found the condition to hold.
     ///
+    /// [`SimState::is_complete()`]: SimState::is_complete
+    /// [`cancel_condition()`]: Simulation::cancel_condition
+    /// [`schedule_now()`]: Simulation::schedule_now
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule_with_delay<EventType>(&mut self, event: EventType, delay: Time) -> crate::Result
+    /// [`step()`]: Simulation::step
+    pub fn schedule_when<EventType, Predicate>(&mut self, predicate: Predicate, event: EventType) -> ConditionHandle
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        Predicate: Fn(&State) -> bool + 'static,
     {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule(event, event_time)
+        let handle = ConditionHandle::new(self.conditions_registered);
+        self.conditions_registered += 1;
+        self.waiters.push(Waiter {
+            id: WaiterId::Condition(handle),
+            predicate: Some(Box::new(predicate)),
+            resume: Box::new(move |simulation: &mut Self| simulation.schedule_now(event).map(|_| ())),
+        });
+        handle
     }
 
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
+    /// Request a unit of the selected [`Resource`] in the simulation's state. If a unit is free, the continuation is
+    /// scheduled at the current time and its handle returned. Otherwise, the continuation waits in line until a unit is
+    /// released through [`release_resource()`], and `None` is returned.
     ///
-    /// # Safety
+    /// # Errors
     ///
-    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
-    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
-    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
-    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
-    pub unsafe fn schedule_with_delay_unchecked<EventType>(&mut self, event: EventType, delay: Time)
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
+    ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`release_resource()`]: Simulation::release_resource
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn request_resource<Continuation, Select>(
+        &mut self,
+        resource: Select,
+        continuation: Continuation,
+    ) -> crate::Result<Option<EventHandle>>
     where
-        EventType: Event<State, Time> + 'static,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
     {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule_unchecked(event, event_time);
+        self.request_resource_with_priority(resource, continuation, 0)
     }
 
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
+    /// Request a unit of the selected [`Resource`] with the provided priority, as with [`request_resource()`].
+    /// Priorities only affect the order of waiting requests under [`Discipline::Priority`].
     ///
     /// # Errors
     ///
-    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
-    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
     ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`request_resource()`]: Simulation::request_resource
+    /// [`Discipline::Priority`]: crate::resource::Discipline::Priority
     /// [`Error::BackInTime`]: crate::Error::BackInTime
-    pub fn schedule_with_delay_from_boxed(&mut self, event: Box<dyn Event<State, Time>>, delay: Time) -> crate::Result {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule_from_boxed(event, event_time)
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn request_resource_with_priority<Continuation, Select>(
+        &mut self,
+        resource: Select,
+        continuation: Continuation,
+        priority: i32,
+    ) -> crate::Result<Option<EventHandle>>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
+    {
+        match resource(&mut self.state).request_with_priority(continuation, priority) {
+            Some(granted) => self.schedule_now(granted).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Release a unit of the selected [`Resource`] in the simulation's state. If a request is waiting for a unit, it is
+    /// granted and its continuation scheduled at the current time, and the continuation's handle returned.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if no units of the resource are held, as with [`Resource::release()`].
+    ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`Resource::release()`]: crate::resource::Resource::release
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn release_resource<Continuation, Select>(&mut self, resource: Select) -> crate::Result<Option<EventHandle>>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
+    {
+        match resource(&mut self.state).release() {
+            Some(granted) => self.schedule_now(granted).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Request to add the provided amount to the selected [`Container`] in the simulation's state. Once the amount
+    /// fits, the continuation is scheduled at the current time, along with those of any requests to drain the container
+    /// that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// [`Container`]: crate::resource::Container
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn fill_container<Amount, Continuation, Select>(
+        &mut self,
+        container: Select,
+        amount: Amount,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Amount: Copy + PartialOrd + Add<Output = Amount> + Sub<Output = Amount>,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Container<Amount, Continuation>,
+    {
+        let satisfied = container(&mut self.state).fill(amount, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to remove the provided amount from the selected [`Container`] in the simulation's state. Once enough is
+    /// stored, the continuation is scheduled at the current time, along with those of any requests to fill the
+    /// container that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// [`Container`]: crate::resource::Container
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn drain_container<Amount, Continuation, Select>(
+        &mut self,
+        container: Select,
+        amount: Amount,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Amount: Copy + PartialOrd + Add<Output = Amount> + Sub<Output = Amount>,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Container<Amount, Continuation>,
+    {
+        let satisfied = container(&mut self.state).drain(amount, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to add an item to the selected [`Store`] in the simulation's state. Once there is room, the continuation
+    /// is scheduled at the current time, along with those of any requests to get an item that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// [`Store`]: crate::resource::Store
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn put_item<Item, Continuation, Select>(
+        &mut self,
+        store: Select,
+        item: Item,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Store<Item, Continuation>,
+    {
+        let satisfied = store(&mut self.state).put(item, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to take any item from the selected [`Store`] in the simulation's state. Once the store holds an item,
+    /// the continuation built from it by `deliver` is scheduled at the current time, along with those of any requests
+    /// to put an item that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// [`Store`]: crate::resource::Store
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn get_item<Item, Continuation, Select, Deliver>(&mut self, store: Select, deliver: Deliver) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Store<Item, Continuation>,
+        Deliver: FnOnce(Item) -> Continuation + 'static,
+    {
+        let satisfied = store(&mut self.state).get(Box::new(deliver));
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to take an item accepted by the provided filter from the selected [`Store`] in the simulation's state,
+    /// as with [`get_item()`].
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// [`Store`]: crate::resource::Store
+    /// [`get_item()`]: Simulation::get_item
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn get_item_where<Item, Continuation, Select, Filter, Deliver>(
+        &mut self,
+        store: Select,
+        filter: Filter,
+        deliver: Deliver,
+    ) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Store<Item, Continuation>,
+        Filter: Fn(&Item) -> bool + 'static,
+        Deliver: FnOnce(Item) -> Continuation + 'static,
+    {
+        let satisfied = store(&mut self.state).get_where(Box::new(filter), Box::new(deliver));
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Fire the selected [`Signal`] in the simulation's state, scheduling every continuation waiting on it at the
+    /// current time in the order they were registered. Returns the number of continuations scheduled.
+    ///
+    /// # Errors
+    ///
+    /// If a waiting continuation cannot be scheduled at the current time, returns an [`Error::BackInTime`] under the
+    /// same condition as [`schedule_now()`]. The signal has still fired, and the continuations registered after that
+    /// one are dropped.
+    ///
+    /// [`Signal`]: crate::Signal
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn fire_signal<Continuation, Select>(&mut self, signal: Select) -> crate::Result<usize>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Signal<Continuation>,
+    {
+        let woken = signal(&mut self.state).fire();
+        let count = woken.len();
+        self.schedule_satisfied(woken)?;
+        Ok(count)
+    }
+
+    /// Schedule the continuations of satisfied requests at the current time, in the order they were satisfied.
+    fn schedule_satisfied<Continuation>(&mut self, satisfied: Vec<Continuation>) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+    {
+        for continuation in satisfied {
+            self.schedule_now(continuation)?;
+        }
+        Ok(())
+    }
+}
+
+impl<State, Time, Backend> Simulation<State, Time, Backend>
+where
+    State: SimState<Time>,
+    Time: SimTime + Clone + Add<Output = Time>,
+    Backend: EventQueueBackend<Time>,
+{
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_delay<EventType>(&mut self, event: EventType, delay: Time) -> crate::Result<EventHandle>
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
+    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
+    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
+    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    pub unsafe fn schedule_with_delay_unchecked<EventType>(&mut self, event: EventType, delay: Time) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_unchecked(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_with_delay_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        delay: Time,
+    ) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_from_boxed(event, event_time)
     }
 
     /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
@@ -332,16 +1174,299 @@ where
     /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
     /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
     /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
-    pub unsafe fn schedule_with_delay_unchecked_from_boxed(&mut self, event: Box<dyn Event<State, Time>>, delay: Time) {
+    pub unsafe fn schedule_with_delay_unchecked_from_boxed(
+        &mut self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        delay: Time,
+    ) -> EventHandle {
         let event_time = self.current_time.clone() + delay;
-        self.schedule_unchecked_from_boxed(event, event_time);
+        self.schedule_unchecked_from_boxed(event, event_time)
+    }
+
+    /// Move a pending event so that it executes after the specified delay, returning a fresh [`EventHandle`] that
+    /// refers to the moved event. The event's new execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// As with [`reschedule()`], the event is treated as though it were newly scheduled and the handle passed in no
+    /// longer refers to it once this method succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// with no modifications to the queue. If `handle` does not refer to a pending event, returns an
+    /// [`Error::EventNotFound`] instead.
+    ///
+    /// [`reschedule()`]: Simulation::reschedule
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    pub fn reschedule_with_delay(&mut self, handle: EventHandle, delay: Time) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.reschedule(handle, event_time)
+    }
+
+    /// Schedule an event to recur at a fixed period, starting at the specified time. Each occurrence executes a fresh
+    /// event produced by `event_factory` when the occurrence is scheduled, and the next occurrence is scheduled one
+    /// `period` after the previous one executes. The `recurrence` limits how many occurrences are scheduled, either by
+    /// count or by the latest time at which one may occur.
+    ///
+    /// Only one occurrence is on the queue at any time, and it appears in [`pending_events()`] and to observers as the
+    /// event produced for it. The returned [`PeriodicHandle`] may be passed to [`stop_periodic()`] to end the
+    /// recurrence early. Errors returned by an occurrence's event are passed back from [`run()`] as usual, and do not
+    /// end the recurrence.
+    ///
+    /// # Errors
+    ///
+    /// If `first_time` is less than the current clock time on `self`, or if adding `period` to `first_time` does not
+    /// produce a later time, returns an [`Error::BackInTime`] with no modifications to the queue.
+    ///
+    /// [`pending_events()`]: Simulation::pending_events
+    /// [`stop_periodic()`]: Simulation::stop_periodic
+    /// [`run()`]: Simulation::run
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn schedule_periodic<EventType, Factory>(
+        &mut self,
+        event_factory: Factory,
+        first_time: Time,
+        period: Time,
+        recurrence: Recurrence<Time>,
+    ) -> crate::Result<PeriodicHandle>
+    where
+        Time: 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        Factory: FnMut() -> EventType + 'static,
+    {
+        if first_time < self.current_time || first_time.clone() + period.clone() <= first_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        let handle = PeriodicHandle::new(self.recurrences_started);
+        self.recurrences_started += 1;
+        let recurring = Recurring {
+            handle,
+            factory: event_factory,
+            period,
+            recurrence,
+        };
+        self.schedule_occurrence(recurring, first_time)?;
+        Ok(handle)
+    }
+
+    /// Schedule an event to complete an activity of the provided duration, such as a service or a repair, pairing it
+    /// with a handler to schedule in its place should the activity be interrupted.
+    ///
+    /// The completion event executes one `duration` from now unless [`interrupt()`] is first called with the returned
+    /// [`ActivityHandle`]. In that case, the completion event is cancelled, and `on_interrupt` receives an
+    /// [`Interrupted`] describing how much of the activity remained, from which it builds the handler event to be
+    /// scheduled at the time of the interrupt. The completion event appears in [`pending_events()`] and to observers as
+    /// though it had been scheduled directly.
+    ///
+    /// # Errors
+    ///
+    /// If adding `duration` to the current time produces an earlier time, returns an [`Error::BackInTime`] with no
+    /// modifications to the queue.
+    ///
+    /// [`interrupt()`]: Simulation::interrupt
+    /// [`pending_events()`]: Simulation::pending_events
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn start_activity<EventType, HandlerType, Handler>(
+        &mut self,
+        event: EventType,
+        duration: Time,
+        on_interrupt: Handler,
+    ) -> crate::Result<ActivityHandle>
+    where
+        Time: 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        HandlerType: Event<State, Time, Backend> + 'static,
+        Handler: FnOnce(Interrupted<Time>) -> HandlerType + 'static,
+    {
+        let handle = ActivityHandle::new(self.activities_started);
+        let completes_at = self.current_time.clone() + duration;
+        let completion = self.schedule(Activity { handle, event }, completes_at.clone())?;
+        self.activities_started += 1;
+        self.activities.insert(
+            handle,
+            PendingActivity {
+                completion,
+                completes_at,
+                on_interrupt: Box::new(move |simulation: &mut Self, interrupted| {
+                    simulation.schedule_now(on_interrupt(interrupted))
+                }),
+            },
+        );
+        Ok(handle)
+    }
+
+    /// Interrupt an activity started by [`start_activity()`], cancelling its completion event and scheduling its
+    /// interrupt handler at the current time. The time remaining in the activity is calculated by subtracting the
+    /// current time from the activity's completion time. Returns the handle of the scheduled handler, or `None` if the
+    /// activity had already completed or been interrupted.
+    ///
+    /// # Errors
+    ///
+    /// If the interrupt handler cannot be scheduled at the current time, returns an [`Error::BackInTime`] under the
+    /// same condition as [`schedule_now()`]. The activity's completion has still been cancelled.
+    ///
+    /// [`start_activity()`]: Simulation::start_activity
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    pub fn interrupt(&mut self, handle: ActivityHandle) -> crate::Result<Option<EventHandle>>
+    where
+        Time: Sub<Output = Time>,
+    {
+        let activity = match self.activities.remove(&handle) {
+            Some(activity) => activity,
+            None => return Ok(None),
+        };
+        self.event_queue.cancel_event(activity.completion);
+        let remaining = activity.completes_at - self.current_time.clone();
+        (activity.on_interrupt)(self, Interrupted { remaining }).map(Some)
+    }
+
+    /// Start a process at the specified time, when it will first be [resumed]. Each later resumption is scheduled
+    /// according to the [`Yield`] returned by the previous one, and executes as an event named after the process.
+    ///
+    /// See [`process`] for an overview of modelling with processes.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [resumed]: Process::resume
+    /// [`Yield`]: crate::process::Yield
+    /// [`process`]: crate::process
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn start_process<ProcessType>(&mut self, process: ProcessType, time: Time) -> crate::Result<ProcessHandle>
+    where
+        State: 'static,
+        Time: 'static,
+        Backend: 'static,
+        ProcessType: Process<State, Time, Backend> + 'static,
+    {
+        let handle = ProcessHandle::new(self.processes_started);
+        self.schedule(ProcessEvent::new(handle, process), time)?;
+        self.processes_started += 1;
+        Ok(handle)
+    }
+
+    /// Start an async process at the specified time. The provided function is called immediately with the process's
+    /// [`ProcessContext`], and the future it returns is first polled at `time`, then again each time the process
+    /// resumes. An error returned by the future ends the process and is passed back from [`run()`] as though an event
+    /// had returned it.
+    ///
+    /// See [`process`] for an overview of modelling with processes.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`process`]: crate::process
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn spawn<ProcessFn, Fut>(&mut self, process: ProcessFn, time: Time) -> crate::Result<ProcessHandle>
+    where
+        State: 'static,
+        Time: 'static,
+        Backend: 'static,
+        ProcessFn: FnOnce(ProcessContext<State, Time, Backend>) -> Fut,
+        Fut: Future<Output = crate::Result> + 'static,
+    {
+        self.start_process(Task::new(process), time)
+    }
+
+    /// Request a unit of the selected [`PreemptiveResource`] in the simulation's state, to be held for `service` before
+    /// the continuation is scheduled. Returns `true` if the request was granted immediately, either to a free unit or
+    /// by interrupting a holder of lower priority, or `false` if it must wait.
+    ///
+    /// The selector is stored with the request until its service completes, so it must be a [`Copy`] closure that
+    /// captures nothing from its environment, or a function.
+    ///
+    /// # Errors
+    ///
+    /// If adding `service` to the current time produces an earlier time, returns an [`Error::BackInTime`] without
+    /// changing the resource or interrupting any holder.
+    ///
+    /// [`PreemptiveResource`]: crate::resource::PreemptiveResource
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    pub fn request_preemptive<Continuation, Select>(
+        &mut self,
+        resource: Select,
+        continuation: Continuation,
+        priority: i32,
+        service: Time,
+    ) -> crate::Result<bool>
+    where
+        Time: Sub<Output = Time> + 'static,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: Fn(&mut State) -> &mut PreemptiveResource<Continuation, Time> + Copy + 'static,
+    {
+        crate::resource::request_preemptive(self, resource, continuation, priority, service)
+    }
+
+    /// Park a process until its condition holds or, without a condition, until it is activated.
+    pub(crate) fn suspend_process<ProcessType>(
+        &mut self,
+        handle: ProcessHandle,
+        predicate: Option<StatePredicate<State>>,
+        event: ProcessEvent<ProcessType>,
+    ) where
+        State: 'static,
+        Time: 'static,
+        Backend: 'static,
+        ProcessType: Process<State, Time, Backend> + 'static,
+    {
+        self.waiters.push(Waiter {
+            id: WaiterId::Process(handle),
+            predicate,
+            resume: Box::new(move |simulation: &mut Self| simulation.schedule_now(event).map(|_| ())),
+        });
+    }
+
+    /// Schedule the next occurrence of a recurrence for the provided time if its limit permits, or else end the
+    /// recurrence.
+    pub(super) fn schedule_occurrence<EventType, Factory>(
+        &mut self,
+        mut recurring: Recurring<Factory, Time>,
+        time: Time,
+    ) -> crate::Result
+    where
+        Time: 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        Factory: FnMut() -> EventType + 'static,
+    {
+        let handle = recurring.handle;
+        recurring.recurrence = match recurring.recurrence.clone().admit(&time) {
+            Some(recurrence) => recurrence,
+            None => {
+                self.recurrences.remove(&handle);
+                return Ok(());
+            },
+        };
+
+        let occurrence = Occurrence {
+            event: (recurring.factory)(),
+            recurring: Some(recurring),
+        };
+        match self.schedule(occurrence, time) {
+            Ok(pending) => {
+                self.recurrences.insert(handle, pending);
+                Ok(())
+            },
+            Err(error) => {
+                self.recurrences.remove(&handle);
+                Err(error)
+            },
+        }
     }
 }
 
-impl<State, Time> std::fmt::Display for Simulation<State, Time>
+impl<State, Time, Backend> std::fmt::Display for Simulation<State, Time, Backend>
 where
     State: SimState<Time>,
     Time: SimTime,
+    Backend: EventQueueBackend<Time>,
 {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         write!(f, "Simulation at time {:?}", self.current_time)
@@ -351,7 +1476,9 @@ where
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::process::Yield;
     use crate::serial::OkEvent;
+    use std::collections::VecDeque;
 
     #[derive(Debug)]
     struct State {
@@ -385,6 +1512,20 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
+    struct TestProcess {
+        value: i32,
+        /// The outcome of each resumption in turn, after which the process finishes.
+        steps: VecDeque<crate::Result<Yield<State, i32>>>,
+    }
+
+    impl Process<State, i32> for TestProcess {
+        fn resume(&mut self, simulation: &mut Simulation<State, i32>) -> crate::Result<Yield<State, i32>> {
+            simulation.state_mut().executed_event_values.push(self.value);
+            self.steps.pop_front().unwrap_or(Ok(Yield::Finish))
+        }
+    }
+
     fn setup() -> Simulation<State, i32> {
         let mut sim = Simulation::new(
             State {
@@ -532,4 +1673,614 @@ mod tests {
             "state should match first executed event"
         );
     }
+
+    #[test]
+    fn cancelled_events_do_not_execute() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule(TestEvent { value: 4 }, 1)
+            .expect("should be able to schedule new event");
+        let first_handle = EventHandle::new(0);
+
+        assert!(sim.cancel(handle), "pending event should be cancellable");
+        assert!(sim.cancel(first_handle), "event at top of queue should be cancellable");
+        sim.run().unwrap();
+
+        assert_eq!(
+            vec![3, 2],
+            sim.state().executed_event_values,
+            "cancelled events should not have executed"
+        );
+    }
+
+    #[test]
+    fn cancel_reports_whether_event_was_pending() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule(TestEvent { value: 4 }, 5)
+            .expect("should be able to schedule new event");
+        assert_eq!(3, handle.insertion_sequence(), "handle should wrap insertion sequence");
+
+        assert!(sim.cancel(handle), "pending event should be cancellable");
+        assert!(!sim.cancel(handle), "event should not be cancellable twice");
+
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+        assert!(
+            !sim.cancel(EventHandle::new(0)),
+            "executed event should not be cancellable"
+        );
+    }
+
+    #[test]
+    fn events_are_dropped_once_finished() {
+        #[derive(Debug)]
+        struct TokenEvent {
+            _token: std::rc::Rc<()>,
+        }
+
+        impl OkEvent<State, i32> for TokenEvent {
+            fn execute(&mut self, _: &mut Simulation<State, i32>) {}
+        }
+
+        let token = std::rc::Rc::new(());
+        let mut sim = setup();
+        let cancelled = sim.schedule(TokenEvent { _token: token.clone() }, 0).unwrap();
+        sim.schedule(TokenEvent { _token: token.clone() }, 1).unwrap();
+        sim.schedule_from_boxed(Box::new(TokenEvent { _token: token.clone() }), 5)
+            .unwrap();
+        sim.schedule(TokenEvent { _token: token.clone() }, 6).unwrap();
+        assert_eq!(5, std::rc::Rc::strong_count(&token), "events should hold tokens");
+
+        sim.cancel(cancelled);
+        assert_eq!(
+            4,
+            std::rc::Rc::strong_count(&token),
+            "cancelled event should be dropped"
+        );
+
+        sim.run_until(&4).unwrap();
+        assert_eq!(3, std::rc::Rc::strong_count(&token), "executed event should be dropped");
+
+        drop(sim);
+        assert_eq!(
+            1,
+            std::rc::Rc::strong_count(&token),
+            "pending events should be dropped with simulation"
+        );
+    }
+
+    #[test]
+    fn closures_execute_as_events() {
+        let mut sim = setup();
+        sim.schedule_fn(
+            |sim| {
+                sim.state_mut().executed_event_values.push(10);
+                Ok(())
+            },
+            1,
+        )
+        .unwrap();
+        let handle = sim
+            .schedule_labelled_fn("failure", |_| Err(crate::Error::EventNotFound), 3)
+            .unwrap();
+
+        let pending = sim.pending_events().find(|pending| pending.handle() == handle).unwrap();
+        assert_eq!("failure", pending.event().name(), "label should name event");
+        assert_eq!(
+            "FnEvent(\"failure\")",
+            format!("{:?}", pending.event()),
+            "label should appear in debug output"
+        );
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.run(),
+            "closure's error should be passed back"
+        );
+        assert_eq!(
+            vec![1, 10, 3],
+            sim.state().executed_event_values,
+            "closures should execute in order with other events"
+        );
+    }
+
+    #[test]
+    fn periodic_events_recur_within_limits() {
+        let mut sim = setup();
+        let mut next_value = 10;
+        let factory = move || {
+            next_value += 1;
+            TestEvent { value: next_value }
+        };
+        sim.schedule_periodic(factory, 1, 2, Recurrence::Times(3)).unwrap();
+        sim.schedule_periodic(|| TestEvent { value: 20 }, 3, 3, Recurrence::Until(9))
+            .unwrap();
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.schedule_periodic(|| TestEvent { value: 30 }, 1, 0, Recurrence::Forever),
+            "recurrence should need to advance the clock"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 11, 3, 20, 12, 2, 13, 20, 20],
+            sim.state().executed_event_values,
+            "occurrences should execute each period until reaching their limits"
+        );
+        assert_eq!(9, *sim.current_time(), "no occurrence should follow the end time");
+    }
+
+    #[test]
+    fn periodic_events_can_be_stopped() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule_periodic(|| TestEvent { value: 10 }, 1, 2, Recurrence::Forever)
+            .unwrap();
+
+        sim.run_until(&4).unwrap();
+        assert_eq!(
+            1,
+            sim.pending_event_count(),
+            "recurrence should keep one occurrence pending"
+        );
+        assert!(sim.stop_periodic(handle), "running recurrence should be stoppable");
+        assert!(!sim.stop_periodic(handle), "recurrence should not be stoppable twice");
+        assert_eq!(0, sim.pending_event_count(), "pending occurrence should be cancelled");
+
+        assert_eq!(
+            vec![1, 10, 3, 10, 2],
+            sim.state().executed_event_values,
+            "occurrences should execute until stopped"
+        );
+    }
+
+    #[test]
+    fn processes_hold_and_wait_for_conditions() {
+        let mut sim = setup();
+        let process = TestProcess {
+            value: 10,
+            steps: VecDeque::from([
+                Ok(Yield::Hold(2)),
+                Ok(Yield::wait_for(|state: &State| {
+                    state.executed_event_values.contains(&2)
+                })),
+            ]),
+        };
+        sim.start_process(process, 1).unwrap();
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 10, 3, 10, 2, 10],
+            sim.state().executed_event_values,
+            "process should resume after holding and once its condition holds"
+        );
+        assert_eq!(
+            4,
+            *sim.current_time(),
+            "process should resume when its condition first holds"
+        );
+    }
+
+    #[test]
+    fn passive_processes_resume_when_activated() {
+        let mut sim = setup();
+        let process = TestProcess {
+            value: 20,
+            steps: VecDeque::from([Ok(Yield::Passivate)]),
+        };
+        let handle = sim.start_process(process, 1).unwrap();
+        assert!(
+            !sim.activate(handle).unwrap(),
+            "process should not be activated before it is passive"
+        );
+
+        sim.run_until(&3).unwrap();
+        assert_eq!(
+            1,
+            sim.pending_event_count(),
+            "passive process should not be on the queue"
+        );
+        assert!(sim.activate(handle).unwrap(), "passive process should be activated");
+        assert!(!sim.activate(handle).unwrap(), "process should not be activated twice");
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 20, 3, 20, 2],
+            sim.state().executed_event_values,
+            "activated process should resume at the time of activation"
+        );
+    }
+
+    #[test]
+    fn process_errors_are_reported() {
+        let mut sim = setup();
+        let process = TestProcess {
+            value: 30,
+            steps: VecDeque::from([Err(crate::Error::EventNotFound), Ok(Yield::Hold(1))]),
+        };
+        sim.start_process(process, 1).unwrap();
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.run(),
+            "process's error should be passed back"
+        );
+        assert_eq!(2, sim.pending_event_count(), "failed process should not be resumed");
+    }
+
+    #[test]
+    fn conditions_schedule_events_once_they_hold() {
+        let mut sim = setup();
+        let fired = sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&3),
+            TestEvent { value: 30 },
+        );
+        let withdrawn = sim.schedule_when(|_: &State| true, TestEvent { value: 40 });
+        assert!(sim.cancel_condition(withdrawn), "waiting condition should be withdrawn");
+        assert!(
+            !sim.cancel_condition(withdrawn),
+            "condition should not be withdrawn twice"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 3, 30, 2],
+            sim.state().executed_event_values,
+            "event should execute as soon as its condition holds"
+        );
+        assert!(
+            !sim.cancel_condition(fired),
+            "condition should be removed once it holds"
+        );
+    }
+
+    #[test]
+    fn interrupted_activities_schedule_their_handlers() {
+        let mut sim = setup();
+        let interrupted = sim
+            .start_activity(TestEvent { value: 50 }, 10, |interrupted: Interrupted<i32>| TestEvent {
+                value: interrupted.remaining,
+            })
+            .unwrap();
+        let completed = sim
+            .start_activity(TestEvent { value: 60 }, 3, |_: Interrupted<i32>| TestEvent {
+                value: -1,
+            })
+            .unwrap();
+        sim.schedule_fn(
+            move |sim| {
+                assert!(
+                    sim.interrupt(completed)?.is_none(),
+                    "completed activity should not be interrupted"
+                );
+                assert!(
+                    sim.interrupt(interrupted)?.is_some(),
+                    "pending activity should be interrupted"
+                );
+                Ok(())
+            },
+            4,
+        )
+        .unwrap();
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 3, 60, 2, 6],
+            sim.state().executed_event_values,
+            "handler should replace completion and receive the remaining duration"
+        );
+        assert_eq!(4, *sim.current_time(), "cancelled completion should not execute");
+    }
+
+    #[test]
+    fn activities_end_with_their_cancelled_completions() {
+        let mut sim = setup();
+        let activity = sim
+            .start_activity(TestEvent { value: 50 }, 10, |_: Interrupted<i32>| TestEvent {
+                value: -1,
+            })
+            .unwrap();
+        let completion = sim
+            .pending_events()
+            .find(|pending| *pending.execution_time() == 10)
+            .unwrap()
+            .handle();
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.reschedule(completion, 12),
+            "activity completion should not be rescheduled"
+        );
+        assert!(sim.cancel(completion), "activity completion should be cancelled");
+        assert_eq!(
+            Ok(None),
+            sim.interrupt(activity),
+            "activity should not be interrupted once its completion is cancelled"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(vec![1, 3, 2], sim.state().executed_event_values);
+    }
+
+    #[test]
+    fn failed_events_do_not_resume_conditions() {
+        let mut sim = setup();
+        let condition = sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&99),
+            TestEvent { value: 30 },
+        );
+        sim.schedule_fn(
+            |sim| {
+                sim.state_mut().executed_event_values.push(99);
+                Err(crate::Error::EventNotFound)
+            },
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(Err(crate::Error::EventNotFound), sim.run());
+        assert!(
+            sim.cancel_condition(condition),
+            "condition should not be checked after a failed event"
+        );
+        sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&99),
+            TestEvent { value: 30 },
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 99, 3, 30, 2],
+            sim.state().executed_event_values,
+            "condition should next be checked after the following event"
+        );
+    }
+
+    #[test]
+    fn rescheduled_events_execute_at_new_time() {
+        let mut sim = setup();
+        let first_handle = EventHandle::new(0);
+        let new_handle = sim
+            .reschedule(first_handle, 3)
+            .expect("pending event should be reschedulable");
+        assert_ne!(first_handle, new_handle, "rescheduling should produce a fresh handle");
+        assert!(
+            !sim.cancel(first_handle),
+            "old handle should no longer refer to the event"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![3, 1, 2],
+            sim.state().executed_event_values,
+            "rescheduled event did not execute at its new time"
+        );
+    }
+
+    #[test]
+    fn priorities_break_ties_before_insertion_order() {
+        let mut sim = setup();
+        sim.schedule_with_priority(TestEvent { value: 5 }, 4, -1).unwrap();
+        let handle = sim.schedule_with_priority(TestEvent { value: 6 }, 4, 1).unwrap();
+        sim.schedule_with_priority(TestEvent { value: 7 }, 4, 1).unwrap();
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.schedule_with_priority(TestEvent { value: 8 }, -1, 1),
+            "priority should not allow scheduling into the past"
+        );
+
+        sim.reschedule(handle, 2)
+            .expect("pending event should be reschedulable");
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 6, 3, 7, 2, 5],
+            sim.state().executed_event_values,
+            "events did not execute in priority order"
+        );
+    }
+
+    #[test]
+    fn reschedule_rejects_invalid_requests() {
+        let mut sim = setup();
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.reschedule(EventHandle::new(0), 5),
+            "executed event should not be reschedulable"
+        );
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.reschedule_with_delay(EventHandle::new(2), -1),
+            "event should not be reschedulable into the past"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 3, 2],
+            sim.state().executed_event_values,
+            "rejected reschedule should leave event in place"
+        );
+        assert_eq!(
+            4,
+            *sim.current_time(),
+            "event should have executed at its original time"
+        );
+    }
+
+    #[test]
+    fn step_reports_progress() {
+        let mut sim = setup();
+        assert_eq!(
+            Ok(StepOutcome::Executed(EventHandle::new(0))),
+            sim.step(),
+            "step should execute first event"
+        );
+        assert_eq!(
+            vec![1],
+            sim.state().executed_event_values,
+            "only one event should execute"
+        );
+
+        sim.schedule(CompletionEvent {}, 2).unwrap();
+        assert_eq!(Ok(StepOutcome::Executed(EventHandle::new(1))), sim.step());
+        assert_eq!(Ok(StepOutcome::Executed(EventHandle::new(3))), sim.step());
+        assert_eq!(Ok(StepOutcome::Complete), sim.step(), "step should detect completion");
+
+        let mut sim = Simulation::new(
+            State {
+                executed_event_values: Vec::new(),
+                complete: false,
+            },
+            0,
+        );
+        assert_eq!(
+            Ok(StepOutcome::QueueEmpty),
+            sim.step(),
+            "step should detect empty queue"
+        );
+    }
+
+    #[test]
+    fn run_for_stops_after_event_count() {
+        let mut sim = setup();
+        assert_eq!(Ok(2), sim.run_for(2), "should execute requested number of events");
+        assert_eq!(vec![1, 3], sim.state().executed_event_values);
+        assert_eq!(2, *sim.current_time(), "clock should match last executed event");
+
+        assert_eq!(Ok(1), sim.run_for(5), "should stop early when queue empties");
+        assert_eq!(vec![1, 3, 2], sim.state().executed_event_values);
+    }
+
+    #[test]
+    fn run_until_stops_at_horizon() {
+        let mut sim = setup();
+        sim.run_until(&2).expect("simulation should run to horizon");
+        assert_eq!(
+            vec![1, 3],
+            sim.state().executed_event_values,
+            "events at or before horizon should execute"
+        );
+        assert_eq!(2, *sim.current_time(), "clock should be at horizon");
+
+        sim.run_until(&3).expect("simulation should run to horizon");
+        assert_eq!(vec![1, 3], sim.state().executed_event_values, "no events lie in (2, 3]");
+        assert_eq!(3, *sim.current_time(), "clock should advance to horizon without events");
+
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.run_until(&1),
+            "horizon in the past should be rejected"
+        );
+
+        sim.schedule(CompletionEvent {}, 3).unwrap();
+        sim.run_until(&10).expect("simulation should run until complete");
+        assert_eq!(
+            vec![1, 3],
+            sim.state().executed_event_values,
+            "completion should stop run"
+        );
+        assert_eq!(3, *sim.current_time(), "clock should stay at completion time");
+    }
+
+    #[test]
+    fn pending_events_can_be_inspected() {
+        let mut sim = setup();
+        let handle = sim.schedule(TestEvent { value: 4 }, 2).unwrap();
+        sim.cancel(EventHandle::new(0));
+
+        assert_eq!(Some(&2), sim.peek_next_time(), "next time should skip cancelled event");
+        assert_eq!(3, sim.pending_event_count(), "cancelled event should not be counted");
+
+        let pending: Vec<_> = sim
+            .pending_events()
+            .map(|pending| (*pending.execution_time(), pending.handle()))
+            .collect();
+        assert_eq!(
+            vec![(2, EventHandle::new(1)), (2, handle), (4, EventHandle::new(2))],
+            pending,
+            "pending events should be listed in execution order"
+        );
+
+        let first_pending = format!("{:?}", sim.pending_events().next().unwrap());
+        assert_eq!(
+            "ScheduledEvent { event: TestEvent { value: 3 }, execution_time: 2, priority: 0, insertion_sequence: 1 }",
+            first_pending,
+            "pending event should print its contents"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(None, sim.peek_next_time(), "queue should be empty after run");
+        assert_eq!(0, sim.pending_event_count(), "queue should be empty after run");
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        records: Vec<String>,
+    }
+
+    impl Observer<i32> for RecordingObserver {
+        fn before_event(&mut self, event: &ObservedEvent<'_, i32>) {
+            self.records.push(format!(
+                "before {:?} at {} ({})",
+                event.event(),
+                event.execution_time(),
+                event.handle().insertion_sequence()
+            ));
+        }
+
+        fn after_event(&mut self, event: &ObservedEvent<'_, i32>, result: &crate::Result) {
+            self.records.push(format!(
+                "after {:?} at {}: {:?}",
+                event.event(),
+                event.execution_time(),
+                result
+            ));
+        }
+    }
+
+    #[test]
+    fn observers_are_notified_around_each_event() {
+        let mut sim = setup();
+        let handle = sim.add_observer(RecordingObserver::default());
+        let unused_handle = sim.add_observer(RecordingObserver::default());
+        sim.run_for(2).unwrap();
+
+        assert_eq!(
+            vec![
+                "before TestEvent { value: 1 } at 0 (0)",
+                "after TestEvent { value: 1 } at 0: Ok(())",
+                "before TestEvent { value: 3 } at 2 (1)",
+                "after TestEvent { value: 3 } at 2: Ok(())",
+            ],
+            sim.observer(&handle).expect("observer should be attached").records,
+            "observer should record events in execution order"
+        );
+
+        sim.observer_mut(&handle).unwrap().records.clear();
+        let removed = sim
+            .remove_observer(unused_handle)
+            .expect("observer should be removable");
+        assert_eq!(4, removed.records.len(), "removed observer should keep its records");
+        assert!(
+            sim.observer(&unused_handle).is_none(),
+            "removed observer should be gone"
+        );
+        assert!(
+            sim.remove_observer(unused_handle).is_none(),
+            "observer should not be removed twice"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            2,
+            sim.observer(&handle).unwrap().records.len(),
+            "remaining observer should still be notified"
+        );
+    }
 }
@@ -0,0 +1,111 @@
+/// A broadcast that wakes everything waiting on it at once, such as power returning to a factory floor.
+///
+/// A signal is stored in the simulation's state. Event code waits on it by registering a continuation through
+/// [`wait()`], and another event fires it through [`serial::Simulation::fire_signal()`] or
+/// [`threadsafe::Simulation::fire_signal()`], which schedules every waiting continuation at the current time in the
+/// order they were registered. Firing a signal wakes only the continuations waiting at the time, so a signal can be
+/// waited on and fired any number of times.
+///
+/// ```
+/// use desque::serial::{OkEvent, Simulation};
+/// use desque::{SimState, Signal};
+///
+/// struct Factory {
+///     power_restored: Signal<Restart>,
+///     restarted: Vec<(&'static str, u32)>,
+/// }
+///
+/// impl SimState<u32> for Factory {}
+///
+/// #[derive(Debug)]
+/// struct Restart(&'static str);
+///
+/// impl OkEvent<Factory, u32> for Restart {
+///     fn execute(&mut self, sim: &mut Simulation<Factory, u32>) {
+///         let now = *sim.current_time();
+///         sim.state_mut().restarted.push((self.0, now));
+///     }
+/// }
+///
+/// let mut factory = Factory {
+///     power_restored: Signal::new(),
+///     restarted: Vec::new(),
+/// };
+/// factory.power_restored.wait(Restart("lathe"));
+/// factory.power_restored.wait(Restart("press"));
+///
+/// let mut sim = Simulation::new(factory, 0);
+/// sim.schedule_fn(|sim| sim.fire_signal(|factory| &mut factory.power_restored).map(|_| ()), 5)?;
+/// sim.run()?;
+///
+/// assert_eq!(vec![("lathe", 5), ("press", 5)], sim.state().restarted);
+/// # Ok::<(), desque::Error>(())
+/// ```
+///
+/// [`wait()`]: Signal::wait
+/// [`serial::Simulation::fire_signal()`]: crate::serial::Simulation::fire_signal
+/// [`threadsafe::Simulation::fire_signal()`]: crate::threadsafe::Simulation::fire_signal
+#[derive(Debug, Clone)]
+pub struct Signal<Continuation> {
+    waiting: Vec<Continuation>,
+    /// The number of times the signal has fired over its lifetime.
+    times_fired: usize,
+}
+
+impl<Continuation> Signal<Continuation> {
+    /// Create a signal with nothing waiting on it.
+    pub fn new() -> Self {
+        Self {
+            waiting: Vec::new(),
+            times_fired: 0,
+        }
+    }
+
+    /// Register a continuation to be executed the next time the signal fires.
+    pub fn wait(&mut self, continuation: Continuation) {
+        self.waiting.push(continuation);
+    }
+
+    /// Fire the signal, handing back every waiting continuation in the order they were registered.
+    ///
+    /// The simulations' `fire_signal()` methods call this method and schedule the returned continuations; calling it
+    /// directly leaves scheduling to the caller.
+    pub fn fire(&mut self) -> Vec<Continuation> {
+        self.times_fired += 1;
+        std::mem::take(&mut self.waiting)
+    }
+
+    /// Iterate over the waiting continuations, in the order they were registered.
+    pub fn waiting(&self) -> impl Iterator<Item = &Continuation> {
+        self.waiting.iter()
+    }
+
+    /// Get the number of times the signal has fired.
+    pub fn times_fired(&self) -> usize {
+        self.times_fired
+    }
+}
+
+impl<Continuation> Default for Signal<Continuation> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn firing_wakes_current_waiters_in_order() {
+        let mut signal = Signal::new();
+        signal.wait(1);
+        signal.wait(2);
+        assert_eq!(vec![1, 2], signal.fire());
+
+        signal.wait(3);
+        assert_eq!(vec![3], signal.fire(), "earlier waiters should not be woken again");
+        assert!(signal.fire().is_empty());
+        assert_eq!(3, signal.times_fired());
+    }
+}
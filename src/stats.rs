@@ -0,0 +1,53 @@
+//! For collecting output statistics from inside a running simulation.
+//!
+//! The collectors in this module are meant to be stored in the simulation's state and updated by events as they
+//! execute. Each update takes constant time and space, no matter how many observations a collector has seen, so models
+//! can record every customer or job without keeping the raw observations around.
+//!
+//! A [`Tally`] summarizes a series of independent observations, such as the time each customer spends in line:
+//!
+//! ```
+//! use desque::serial::{OkEvent, Simulation};
+//! use desque::stats::Tally;
+//! use desque::SimState;
+//!
+//! struct Shop {
+//!     waits: Tally,
+//! }
+//!
+//! impl SimState<u32> for Shop {}
+//!
+//! #[derive(Debug)]
+//! struct StartService {
+//!     arrived_at: u32,
+//! }
+//!
+//! impl OkEvent<Shop, u32> for StartService {
+//!     fn execute(&mut self, sim: &mut Simulation<Shop, u32>) {
+//!         let wait = sim.current_time() - self.arrived_at;
+//!         sim.state_mut().waits.record(f64::from(wait));
+//!     }
+//! }
+//!
+//! let mut sim = Simulation::new(Shop { waits: Tally::new() }, 0);
+//! sim.schedule(StartService { arrived_at: 0 }, 2)?;
+//! sim.schedule(StartService { arrived_at: 1 }, 5)?;
+//! sim.run()?;
+//!
+//! let waits = &sim.state().waits;
+//! assert_eq!(2, waits.count());
+//! assert_eq!(Some(3.0), waits.mean());
+//! assert_eq!(Some(2.0), waits.variance());
+//! # Ok::<(), desque::Error>(())
+//! ```
+//!
+//! A [`TimeWeighted`] accumulator instead summarizes a level that persists over simulated time, such as the length of
+//! a queue or the number of busy servers, weighting each level by how long it held.
+
+mod histogram;
+mod tally;
+mod time_weighted;
+
+pub use histogram::Histogram;
+pub use tally::Tally;
+pub use time_weighted::TimeWeighted;
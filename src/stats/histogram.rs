@@ -0,0 +1,107 @@
+/// Counts observations in equal-width bins spanning a fixed range.
+///
+/// Observations below the range's lower bound are counted as underflow, and observations at or above its upper bound
+/// are counted as overflow, so that every observation is accounted for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Histogram {
+    lower: f64,
+    bin_width: f64,
+    counts: Vec<usize>,
+    underflow: usize,
+    overflow: usize,
+}
+
+impl Histogram {
+    /// Create a histogram that divides the range from `lower` (inclusive) to `upper` (exclusive) into `bins` bins of
+    /// equal width.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `bins` is zero or if `lower` is not less than `upper`.
+    pub fn new(lower: f64, upper: f64, bins: usize) -> Self {
+        assert!(bins > 0, "histogram should have at least one bin");
+        assert!(
+            lower < upper,
+            "histogram's lower bound should be less than its upper bound"
+        );
+
+        Self {
+            lower,
+            bin_width: (upper - lower) / bins as f64,
+            counts: vec![0; bins],
+            underflow: 0,
+            overflow: 0,
+        }
+    }
+
+    /// Count an observation in the bin that contains it.
+    pub fn record(&mut self, value: f64) {
+        if value < self.lower {
+            self.underflow += 1;
+            return;
+        }
+
+        let bin = ((value - self.lower) / self.bin_width) as usize;
+        match self.counts.get_mut(bin) {
+            Some(count) => *count += 1,
+            None => self.overflow += 1,
+        }
+    }
+
+    /// Get the number of observations counted in each bin, from lowest to highest.
+    pub fn counts(&self) -> &[usize] {
+        &self.counts
+    }
+
+    /// Get the range of values covered by the bin at `index`, as its lower (inclusive) and upper (exclusive) bounds.
+    pub fn bin_bounds(&self, index: usize) -> (f64, f64) {
+        let lower = self.lower + self.bin_width * index as f64;
+        (lower, lower + self.bin_width)
+    }
+
+    /// Get the width of each bin.
+    pub fn bin_width(&self) -> f64 {
+        self.bin_width
+    }
+
+    /// Get the number of observations below the histogram's range.
+    pub fn underflow(&self) -> usize {
+        self.underflow
+    }
+
+    /// Get the number of observations at or above the upper bound of the histogram's range.
+    pub fn overflow(&self) -> usize {
+        self.overflow
+    }
+
+    /// Discard every count, keeping the histogram's bins.
+    pub fn reset(&mut self) {
+        self.counts.iter_mut().for_each(|count| *count = 0);
+        self.underflow = 0;
+        self.overflow = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn observations_land_in_their_bins() {
+        let mut histogram = Histogram::new(0.0, 10.0, 5);
+        for value in [-1.0, 0.0, 1.9, 2.0, 9.99, 10.0, 25.0] {
+            histogram.record(value);
+        }
+
+        assert_eq!(&[2, 1, 0, 0, 1], histogram.counts());
+        assert_eq!(1, histogram.underflow());
+        assert_eq!(2, histogram.overflow(), "upper bound should be exclusive");
+        assert_eq!((4.0, 6.0), histogram.bin_bounds(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "at least one bin")]
+    fn histogram_requires_bins() {
+        Histogram::new(0.0, 1.0, 0);
+    }
+}
@@ -0,0 +1,151 @@
+use super::Histogram;
+
+/// Summarizes a series of observations, such as the time each customer spends in line.
+///
+/// Recording an observation updates the running count, sum, minimum, and maximum along with the mean and variance,
+/// which are maintained with Welford's algorithm to avoid the loss of precision that comes from subtracting large sums
+/// of squares. A tally may also count its observations in a [`Histogram`].
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Tally {
+    count: usize,
+    sum: f64,
+    mean: f64,
+    /// The sum of squared differences from the current mean.
+    squared_deviations: f64,
+    min: f64,
+    max: f64,
+    histogram: Option<Histogram>,
+}
+
+impl Tally {
+    /// Create a tally with no observations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a tally with no observations that also counts its observations in the given histogram.
+    pub fn with_histogram(histogram: Histogram) -> Self {
+        Self {
+            histogram: Some(histogram),
+            ..Self::default()
+        }
+    }
+
+    /// Record an observation.
+    pub fn record(&mut self, value: f64) {
+        if self.count == 0 {
+            self.min = value;
+            self.max = value;
+        } else {
+            self.min = self.min.min(value);
+            self.max = self.max.max(value);
+        }
+
+        self.count += 1;
+        self.sum += value;
+        let deviation = value - self.mean;
+        self.mean += deviation / self.count as f64;
+        self.squared_deviations += deviation * (value - self.mean);
+
+        if let Some(histogram) = &mut self.histogram {
+            histogram.record(value);
+        }
+    }
+
+    /// Get the number of observations recorded.
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    /// Get the sum of the observations recorded.
+    pub fn sum(&self) -> f64 {
+        self.sum
+    }
+
+    /// Get the mean of the observations, or [`None`] if none have been recorded.
+    pub fn mean(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.mean)
+    }
+
+    /// Get the sample variance of the observations, or [`None`] if fewer than two have been recorded.
+    pub fn variance(&self) -> Option<f64> {
+        (self.count > 1).then(|| self.squared_deviations / (self.count - 1) as f64)
+    }
+
+    /// Get the sample standard deviation of the observations, or [`None`] if fewer than two have been recorded.
+    pub fn std_dev(&self) -> Option<f64> {
+        self.variance().map(f64::sqrt)
+    }
+
+    /// Get the smallest observation, or [`None`] if none have been recorded.
+    pub fn min(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.min)
+    }
+
+    /// Get the largest observation, or [`None`] if none have been recorded.
+    pub fn max(&self) -> Option<f64> {
+        (self.count > 0).then_some(self.max)
+    }
+
+    /// Get the histogram counting the observations, if the tally was created with one.
+    pub fn histogram(&self) -> Option<&Histogram> {
+        self.histogram.as_ref()
+    }
+
+    /// Discard every observation, such as at the end of a warm-up period, keeping the tally's histogram bins.
+    pub fn reset(&mut self) {
+        let mut histogram = self.histogram.take();
+        if let Some(histogram) = &mut histogram {
+            histogram.reset();
+        }
+        *self = Self {
+            histogram,
+            ..Self::default()
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_tally_reports_nothing() {
+        let tally = Tally::new();
+        assert_eq!(0, tally.count());
+        assert_eq!(None, tally.mean());
+        assert_eq!(None, tally.variance());
+        assert_eq!(None, tally.min());
+        assert_eq!(None, tally.max());
+    }
+
+    #[test]
+    fn tally_summarizes_observations() {
+        let mut tally = Tally::with_histogram(Histogram::new(0.0, 10.0, 2));
+        for value in [2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0] {
+            tally.record(value);
+        }
+
+        assert_eq!(8, tally.count());
+        assert_eq!(40.0, tally.sum());
+        assert_eq!(Some(5.0), tally.mean());
+        assert_eq!(Some(32.0 / 7.0), tally.variance());
+        assert_eq!(Some(2.0), tally.min());
+        assert_eq!(Some(9.0), tally.max());
+        assert_eq!(&[4, 4], tally.histogram().unwrap().counts());
+
+        tally.record(1e9 + 1.0);
+        tally.reset();
+        assert_eq!(Tally::with_histogram(Histogram::new(0.0, 10.0, 2)), tally);
+    }
+
+    #[test]
+    fn variance_is_stable_for_large_offsets() {
+        let mut tally = Tally::new();
+        for value in [4.0, 7.0, 13.0, 16.0] {
+            tally.record(1e9 + value);
+        }
+
+        assert_eq!(Some(30.0), tally.variance());
+    }
+}
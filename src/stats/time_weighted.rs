@@ -0,0 +1,218 @@
+use crate::NumericTime;
+
+/// Summarizes a level that persists over simulated time, such as the length of a queue or the number of busy servers.
+///
+/// Each update supplies the new level along with the current time, and the accumulator weights the previous level by
+/// how long it held. Reports take the current time as well, so that they account for the current level up to that
+/// time. Times are converted to numbers of time units through [`NumericTime`], and should never run backwards from
+/// one call to the next.
+///
+/// ```
+/// use desque::stats::TimeWeighted;
+///
+/// let mut queue_length = TimeWeighted::new(0.0, &0_u32);
+/// queue_length.update(2.0, &4);
+/// queue_length.update(1.0, &6);
+///
+/// assert_eq!(Some(0.8), queue_length.time_average(&10));
+/// assert_eq!(2.0, queue_length.max());
+/// assert_eq!(Some(0.4), queue_length.fraction_at(0.0, &10));
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeWeighted {
+    level: f64,
+    start: f64,
+    last_update: f64,
+    /// The integral of the level from the start to the last update.
+    area: f64,
+    min: f64,
+    max: f64,
+    /// The time spent at each level up to the last update, sorted by level. Always includes the current level.
+    durations: Vec<(f64, f64)>,
+}
+
+impl TimeWeighted {
+    /// Create an accumulator that starts at the given level and time.
+    pub fn new<Time>(level: f64, start: &Time) -> Self
+    where
+        Time: NumericTime,
+    {
+        let start = start.to_f64();
+        Self {
+            level,
+            start,
+            last_update: start,
+            area: 0.0,
+            min: level,
+            max: level,
+            durations: vec![(level, 0.0)],
+        }
+    }
+
+    /// Change the level at the given time, crediting the previous level with the time since the last update.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `now` is earlier than the time of the last update.
+    pub fn update<Time>(&mut self, level: f64, now: &Time)
+    where
+        Time: NumericTime,
+    {
+        let now = now.to_f64();
+        let elapsed = self.elapsed_since_update(now);
+        self.area += self.level * elapsed;
+        *self.duration_at_mut(self.level) += elapsed;
+
+        self.level = level;
+        self.last_update = now;
+        self.min = self.min.min(level);
+        self.max = self.max.max(level);
+        self.duration_at_mut(level);
+    }
+
+    /// Get the current level.
+    pub fn level(&self) -> f64 {
+        self.level
+    }
+
+    /// Get the lowest level held since the start.
+    pub fn min(&self) -> f64 {
+        self.min
+    }
+
+    /// Get the highest level held since the start.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Get the time-weighted average of the level from the start to `now`, or [`None`] if no time has elapsed.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `now` is earlier than the time of the last update.
+    pub fn time_average<Time>(&self, now: &Time) -> Option<f64>
+    where
+        Time: NumericTime,
+    {
+        let now = now.to_f64();
+        let area = self.area + self.level * self.elapsed_since_update(now);
+        self.total_duration(now).map(|duration| area / duration)
+    }
+
+    /// Get the fraction of the time from the start to `now` spent at the given level, or [`None`] if no time has
+    /// elapsed.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `now` is earlier than the time of the last update.
+    pub fn fraction_at<Time>(&self, level: f64, now: &Time) -> Option<f64>
+    where
+        Time: NumericTime,
+    {
+        self.fractions(now)?
+            .find(|(held, _)| held.total_cmp(&level).is_eq())
+            .map(|(_, fraction)| fraction)
+            .or(Some(0.0))
+    }
+
+    /// Iterate over every level held since the start, from lowest to highest, alongside the fraction of the time from
+    /// the start to `now` spent at that level, or get [`None`] if no time has elapsed.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if `now` is earlier than the time of the last update.
+    pub fn fractions<Time>(&self, now: &Time) -> Option<impl Iterator<Item = (f64, f64)> + '_>
+    where
+        Time: NumericTime,
+    {
+        let now = now.to_f64();
+        let since_update = self.elapsed_since_update(now);
+        let total = self.total_duration(now)?;
+
+        Some(self.durations.iter().map(move |&(level, duration)| {
+            let duration = if level.total_cmp(&self.level).is_eq() {
+                duration + since_update
+            } else {
+                duration
+            };
+            (level, duration / total)
+        }))
+    }
+
+    /// Discard the history of the level, such as at the end of a warm-up period, so that the accumulator starts over at
+    /// the current level from `now`.
+    pub fn reset<Time>(&mut self, now: &Time)
+    where
+        Time: NumericTime,
+    {
+        *self = Self::new(self.level, now);
+    }
+
+    fn elapsed_since_update(&self, now: f64) -> f64 {
+        assert!(
+            now >= self.last_update,
+            "time-weighted statistics should not be given a time before their last update"
+        );
+        now - self.last_update
+    }
+
+    fn total_duration(&self, now: f64) -> Option<f64> {
+        let duration = now - self.start;
+        (duration > 0.0).then_some(duration)
+    }
+
+    /// Get the time spent at the given level, adding the level with no time spent if it has not yet been held.
+    fn duration_at_mut(&mut self, level: f64) -> &mut f64 {
+        let index = match self.durations.binary_search_by(|(held, _)| held.total_cmp(&level)) {
+            Ok(index) => index,
+            Err(index) => {
+                self.durations.insert(index, (level, 0.0));
+                index
+            },
+        };
+        &mut self.durations[index].1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_are_weighted_by_duration() {
+        let mut busy_servers = TimeWeighted::new(1.0, &10_u64);
+        assert_eq!(None, busy_servers.time_average(&10));
+
+        busy_servers.update(3.0, &12);
+        busy_servers.update(0.0, &13);
+        busy_servers.update(0.0, &14);
+
+        assert_eq!(Some(5.0 / 6.0), busy_servers.time_average(&16));
+        assert_eq!(0.0, busy_servers.min());
+        assert_eq!(3.0, busy_servers.max());
+        assert_eq!(
+            vec![(0.0, 0.5), (1.0, 2.0 / 6.0), (3.0, 1.0 / 6.0)],
+            busy_servers.fractions(&16).unwrap().collect::<Vec<_>>()
+        );
+        assert_eq!(Some(0.0), busy_servers.fraction_at(2.0, &16));
+    }
+
+    #[test]
+    fn reset_starts_over_from_current_level() {
+        let mut queue_length = TimeWeighted::new(0.0, &0_i32);
+        queue_length.update(4.0, &5);
+        queue_length.update(2.0, &10);
+        queue_length.reset(&20);
+
+        assert_eq!(2.0, queue_length.max());
+        assert_eq!(Some(2.0), queue_length.time_average(&30));
+        assert_eq!(Some(1.0), queue_length.fraction_at(2.0, &30));
+    }
+
+    #[test]
+    #[should_panic(expected = "before their last update")]
+    fn updates_should_not_run_backwards() {
+        let mut level = TimeWeighted::new(0.0, &5_u32);
+        level.update(1.0, &4);
+    }
+}
@@ -0,0 +1,23 @@
+use crate::EventHandle;
+
+/// Reports what happened when a simulation was asked to execute a single event.
+///
+/// Returned by [`serial::Simulation::step()`] and [`threadsafe::Simulation::step()`], each of which performs exactly
+/// one iteration of the loop inside the corresponding `run()` method. Only the [`Executed`] variant indicates that the
+/// simulation made progress; the other two variants correspond to the conditions under which `run()` would return.
+///
+/// [`serial::Simulation::step()`]: crate::serial::Simulation::step
+/// [`threadsafe::Simulation::step()`]: crate::threadsafe::Simulation::step
+/// [`Executed`]: StepOutcome::Executed
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum StepOutcome {
+    /// The next event was popped from the queue and executed without error. The wrapped handle is the one that was
+    /// returned when the event was scheduled.
+    Executed(EventHandle),
+    /// [`SimState::is_complete()`] reported that the simulation has finished, so no event was executed.
+    ///
+    /// [`SimState::is_complete()`]: crate::SimState::is_complete
+    Complete,
+    /// The event queue held no pending events, so no event was executed.
+    QueueEmpty,
+}
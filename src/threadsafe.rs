@@ -12,5 +12,7 @@
 mod events;
 mod simulation;
 
+pub use events::event_holder::{PendingEvent, PendingEvents};
 pub use events::event_traits::{Event, OkEvent};
+pub use events::fn_event::FnEvent;
 pub use simulation::Simulation;
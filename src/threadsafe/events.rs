@@ -1,59 +1,148 @@
-mod event_holder;
+pub(super) mod event_holder;
 pub(super) mod event_traits;
+pub(super) mod fn_event;
+pub(super) mod occurrence;
 
-use crate::{SimState, SimTime};
-use event_holder::ScheduledEvent;
+use crate::arena::{ArenaBox, EventArena};
+use crate::queue::{EventKey, EventQueueBackend};
+use crate::{EventHandle, SimState, SimTime};
+use event_holder::{PendingEvent, PendingEvents};
 use event_traits::Event;
-use std::cmp::Reverse;
-use std::collections::BinaryHeap;
+
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
+use std::ptr::NonNull;
 use std::sync::atomic;
 use std::sync::Mutex;
 
-/// Helper struct to set a Debug impl that hides everything about `BinaryHeap` and `Reverse`
-struct BinaryHeapWrapper<State, Time>
+/// Helper struct to set a Debug impl that hides everything about the backend, and that pairs each key held by the
+/// backend with the event it refers to.
+///
+/// The backend only holds the keys necessary to order events, while the events themselves live in a map keyed by
+/// insertion sequence. Cancelling an event removes it from the map and leaves its key behind in the backend; such stale
+/// keys are discarded as they reach the front of the backend, and so the front of the backend always refers to a
+/// pending event.
+///
+/// Events scheduled by value are moved into an [`EventArena`], which reuses the memory of events that have executed or
+/// been cancelled. Every event should therefore be released back to the arena once the queue is done with it, which is
+/// also how the contents drop any events still pending. An event that is dropped instead, as when unwinding from a
+/// panic during its execution, leaves its memory unused until the arena is dropped.
+struct QueueContents<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    keys: Backend,
+    events: HashMap<usize, StoredEvent<State, Time, Backend>>,
+    arena: EventArena,
+}
+
+impl<State, Time, Backend> QueueContents<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    /// Drop an event that has left the queue and reclaim its memory.
+    fn release(&mut self, event: ArenaBox<dyn Event<State, Time, Backend>>) {
+        // SAFETY: every event on the queue was either inserted into this arena or boxed
+        unsafe { self.arena.release(event) };
+    }
+
+    /// Pop keys off the front of the backend until finding one that refers to an event which is still pending.
+    fn discard_cancelled(&mut self) {
+        while let Some(key) = self.keys.peek() {
+            if self.events.contains_key(&key.insertion_sequence()) {
+                return;
+            }
+            self.keys.pop();
+        }
+    }
+
+    /// Pair each key held by the backend with the event it refers to, skipping keys of cancelled events.
+    fn pending(&self) -> impl Iterator<Item = PendingEvent<'_, State, Time, Backend>> {
+        self.keys.iter().filter_map(move |key| {
+            self.events
+                .get(&key.insertion_sequence())
+                .map(|stored| PendingEvent::new(key, &*stored.event))
+        })
+    }
+}
+
+impl<State, Time, Backend> Drop for QueueContents<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    heap: BinaryHeap<Reverse<ScheduledEvent<State, Time>>>,
+    fn drop(&mut self) {
+        let events: Vec<_> = self.events.drain().map(|(_, stored)| stored.event).collect();
+        for event in events {
+            self.release(event);
+        }
+    }
 }
 
-impl<State, Time> Debug for BinaryHeapWrapper<State, Time>
+impl<State, Time, Backend> Debug for QueueContents<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        f.debug_list()
-            .entries(self.heap.iter().map(|holder| &holder.0))
-            .finish()
+        f.debug_list().entries(self.pending()).finish()
     }
 }
 
-impl<State, Time> Default for BinaryHeapWrapper<State, Time>
+impl<State, Time, Backend> Default for QueueContents<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+    Backend: Default,
 {
     fn default() -> Self {
         Self {
-            heap: BinaryHeap::default(),
+            keys: Backend::default(),
+            events: HashMap::default(),
+            arena: EventArena::new(),
         }
     }
 }
 
+/// An event waiting on the queue, alongside the priority it was scheduled with so that rescheduling can preserve it.
+struct StoredEvent<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    event: ArenaBox<dyn Event<State, Time, Backend>>,
+    priority: i32,
+}
+
+/// An event popped from the queue, alongside its execution time and the handle it was scheduled with. The event should
+/// be handed back to [`EventQueue::release()`] once it has executed.
+pub(super) type PoppedEvent<State, Time, Backend> = (ArenaBox<dyn Event<State, Time, Backend>>, Time, EventHandle);
+
 /// Priority queue of scheduled events.
 ///
-/// Events will execute in ascending order of execution time, with ties broken by the order in which they were pushed
-/// onto the queue. This tiebreaker is in addition to any built-in to the implementation of [`SimTime`] used for the
-/// clock as a way to stabilize the observed order of execution.
+/// Events will execute in ascending order of execution time, with ties broken first by descending priority and then by
+/// the order in which they were pushed onto the queue. These tiebreakers are in addition to any built-in to the
+/// implementation of [`SimTime`] used for the clock as a way to stabilize the observed order of execution.
 ///
 /// This struct is generic over the type used to represent clock time for the sake of tracking the current time, as well
-/// as over the type used to represent simulation state so that it can work with appropriate event types.
+/// as over the type used to represent simulation state so that it can work with appropriate event types, and over the
+/// [`EventQueueBackend`] that orders the events.
+///
+/// Events scheduled by value are stored in memory reused from events that have already executed or been cancelled, so
+/// that a queue which has reached its working size schedules events without calling on the global allocator. Boxed
+/// events keep their existing allocations.
 ///
 /// A [`EventQueue`] provides several different methods for scheduling new events, but does not publicly support
-/// popping; popping events from the queue only occurs during [`Simulation::run()`].
+/// popping; popping events from the queue only occurs during [`Simulation::run()`]. Scheduled events may be cancelled
+/// by presenting the [`EventHandle`] returned when scheduling them.
 ///
 /// # Safety
 ///
@@ -71,85 +160,239 @@ where
 /// # Synchronization
 ///
 /// All synchronization is handled via a [`Mutex`] around the underlying priority queue. This [`Mutex`] is locked for
-/// all forms of the [`schedule()`] method to enqueue new events, when popping an event to advance the simulation, and
-/// for checking the queue's length in the implementation of [`std::fmt::Display`]. None of these methods expose the
-/// resulting [`MutexGuard`], and so it is also unlocked before the simulation makes additional progress.
+/// all forms of the [`schedule()`] method to enqueue new events, when cancelling an event, when popping an event to
+/// advance the simulation, when peeking at or counting pending events, and for checking the queue's length in the
+/// implementation of [`std::fmt::Display`]. None of these methods expose the resulting [`MutexGuard`], and so it is
+/// also unlocked before the simulation makes additional progress. The one exception is the view of pending events,
+/// which holds the [`MutexGuard`] until it is dropped so that it can lend out references to the events.
 ///
 /// # Panics
 ///
-/// All forms of [`schedule()`] and the implementation of [`std::fmt::Display`] are capable of panicking if the
-/// [`Mutex`] becomes poisoned. This poisoning is unlikely to occur, however, as it is always unlocked before returning
-/// control to client code.
+/// All forms of [`schedule()`], cancellation, and the implementation of [`std::fmt::Display`] are capable of panicking
+/// if the [`Mutex`] becomes poisoned. This poisoning is unlikely to occur, however, as it is always unlocked before
+/// returning control to client code.
 ///
 /// [`Simulation::run()`]: super::Simulation::run
 /// [`Error::BackInTime`]: crate::Error::BackInTime
 /// [`schedule()`]: EventQueue::schedule
 /// [`MutexGuard`]: std::sync::MutexGuard
 #[derive(Debug, Default)]
-pub(super) struct EventQueue<State, Time>
+pub(super) struct EventQueue<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    events: Mutex<BinaryHeapWrapper<State, Time>>,
+    events: Mutex<QueueContents<State, Time, Backend>>,
     /// Using an atomic here allows for interior mutability, but synchronization is actually controlled by the mutex on
     /// the `events` field. This value will only mutate with that mutex locked, and so can use entirely Relaxed ordering
     events_added: atomic::AtomicUsize,
 }
 
-impl<State, Time> EventQueue<State, Time>
+impl<State, Time, Backend> EventQueue<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    /// Construct a new, empty [`EventQueue`]
-    pub fn new() -> Self {
+    /// Construct a new, empty [`EventQueue`] that orders its events with the provided backend, which should also be
+    /// empty.
+    pub fn new(backend: Backend) -> Self {
         Self {
-            events: Mutex::default(),
+            events: Mutex::new(QueueContents {
+                keys: backend,
+                events: HashMap::default(),
+                arena: EventArena::new(),
+            }),
             events_added: atomic::AtomicUsize::new(0),
         }
     }
 
-    /// Place an event on the queue. By the time we're here, assume all error checking is complete.
-    pub fn schedule_event(&self, event: Box<dyn Event<State, Time>>, time: Time) {
+    /// Place an event on the queue, moving it into the queue's arena. By the time we're here, assume all error checking
+    /// is complete.
+    pub fn schedule_event<EventType>(&self, event: EventType, time: Time, priority: i32) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let mut events_guard = self
+            .events
+            .lock()
+            .expect("event queue mutex should not have been poisoned");
+
+        let event = events_guard.arena.insert(event);
+        // SAFETY: unsizing the pointer leaves it referring to the same event
+        let event = unsafe { event.map_ptr(|ptr| -> NonNull<dyn Event<State, Time, Backend>> { ptr }) };
+        self.push(&mut events_guard, event, time, priority)
+    }
+
+    /// Place a boxed event on the queue. By the time we're here, assume all error checking is complete.
+    pub fn schedule_boxed_event(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        let mut events_guard = self
+            .events
+            .lock()
+            .expect("event queue mutex should not have been poisoned");
+
+        self.push(&mut events_guard, ArenaBox::from_box(event), time, priority)
+    }
+
+    /// Push a stored event onto the locked queue, assigning it the next insertion sequence.
+    fn push(
+        &self,
+        events_guard: &mut QueueContents<State, Time, Backend>,
+        event: ArenaBox<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        let insertion_sequence = self.events_added.fetch_add(1, atomic::Ordering::Relaxed);
+        events_guard
+            .keys
+            .push(EventKey::new(time, priority, insertion_sequence));
+        events_guard
+            .events
+            .insert(insertion_sequence, StoredEvent { event, priority });
+        EventHandle::new(insertion_sequence)
+    }
+
+    /// Remove the event referred to by the handle from the queue, if it is still pending. Returns whether there was
+    /// such an event to remove.
+    pub fn cancel_event(&self, handle: EventHandle) -> bool {
+        let mut events_guard = self
+            .events
+            .lock()
+            .expect("event queue mutex should not have been poisoned");
+
+        match events_guard.events.remove(&handle.insertion_sequence()) {
+            Some(stored) => {
+                events_guard.release(stored.event);
+                events_guard.discard_cancelled();
+                true
+            },
+            None => false,
+        }
+    }
+
+    /// Move the event referred to by the handle to a new execution time, if it is still pending. The event receives a
+    /// new insertion sequence as though it were freshly scheduled, and a handle carrying that sequence is returned. The
+    /// event's priority is unchanged.
+    pub fn reschedule_event(&self, handle: EventHandle, time: Time) -> Option<EventHandle> {
         let mut events_guard = self
             .events
             .lock()
             .expect("event queue mutex should not have been poisoned");
 
-        events_guard.heap.push(Reverse(ScheduledEvent {
-            execution_time: time,
-            event,
-            insertion_sequence: self.events_added.fetch_add(1, atomic::Ordering::Relaxed),
-        }));
+        let stored = events_guard.events.remove(&handle.insertion_sequence())?;
+        let insertion_sequence = self.events_added.fetch_add(1, atomic::Ordering::Relaxed);
+        events_guard
+            .keys
+            .push(EventKey::new(time, stored.priority, insertion_sequence));
+        events_guard.events.insert(insertion_sequence, stored);
+        events_guard.discard_cancelled();
+        Some(EventHandle::new(insertion_sequence))
     }
 
-    /// Crate-internal function to pop an event from the queue. Updates the current clock time to match the execution
-    /// time of the popped event.
+    /// Crate-internal function to pop an event from the queue, alongside its execution time and the handle it was
+    /// scheduled with.
     ///
     /// # Panics
     ///
     /// If the [`Mutex`] protecting the underlying priority queue implementation has been poisoned by another thread
     /// panicking while it is locked, this method will also panic.
-    pub(crate) fn next(&mut self) -> Option<(Box<dyn Event<State, Time>>, Time)> {
-        if let Some(event_holder) = self
+    pub(crate) fn next(&mut self) -> Option<PoppedEvent<State, Time, Backend>> {
+        let mut events_guard = self
             .events
             .lock()
+            .expect("event queue mutex should not have been poisoned");
+
+        let key = events_guard.keys.pop()?;
+        let event = events_guard
+            .events
+            .remove(&key.insertion_sequence())
+            .expect("front of the backend should always refer to a pending event")
+            .event;
+        events_guard.discard_cancelled();
+        let handle = key.handle();
+        Some((event, key.into_execution_time(), handle))
+    }
+
+    /// Crate-internal function to drop an event popped from the queue once it has executed, reclaiming its memory for
+    /// events scheduled later. Exclusive access to the queue allows for bypassing the [`Mutex`] rather than locking it.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] protecting the underlying priority queue implementation has been poisoned by another thread
+    /// panicking while it is locked, this method will also panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(crate) fn release(&mut self, event: ArenaBox<dyn Event<State, Time, Backend>>) {
+        self.events
+            .get_mut()
             .expect("event queue mutex should not have been poisoned")
-            .heap
-            .pop()
-        {
-            Some((event_holder.0.event, event_holder.0.execution_time))
-        } else {
-            None
-        }
+            .release(event);
+    }
+
+    /// Get a copy of the execution time of the event that will be popped next, if any, without modifying the queue.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] protecting the underlying priority queue implementation has been poisoned by another thread
+    /// panicking while it is locked, this method will also panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(crate) fn peek_time(&self) -> Option<Time>
+    where
+        Time: Clone,
+    {
+        self.events
+            .lock()
+            .expect("event queue mutex should not have been poisoned")
+            .keys
+            .peek()
+            .map(|key| key.execution_time().clone())
+    }
+
+    /// Get the number of events that are currently waiting on the queue, excluding any that have been cancelled.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] protecting the underlying priority queue implementation has been poisoned by another thread
+    /// panicking while it is locked, this method will also panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(crate) fn pending_count(&self) -> usize {
+        self.events
+            .lock()
+            .expect("event queue mutex should not have been poisoned")
+            .events
+            .len()
+    }
+
+    /// Lock the queue to view every event currently waiting on it.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] protecting the underlying priority queue implementation has been poisoned by another thread
+    /// panicking while it is locked, this method will also panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(crate) fn pending_events(&self) -> PendingEvents<'_, State, Time, Backend> {
+        PendingEvents::new(
+            self.events
+                .lock()
+                .expect("event queue mutex should not have been poisoned"),
+        )
     }
 }
 
-impl<State, Time> std::fmt::Display for EventQueue<State, Time>
+impl<State, Time, Backend> std::fmt::Display for EventQueue<State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
     fn fmt(&self, formatter: &mut Formatter) -> std::fmt::Result {
         write!(
@@ -158,7 +401,7 @@ where
             self.events
                 .lock()
                 .expect("event queue mutex should not have been poisoned")
-                .heap
+                .events
                 .len(),
         )
     }
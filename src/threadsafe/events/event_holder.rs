@@ -1,74 +1,144 @@
+use super::QueueContents;
+use crate::queue::{BinaryHeapQueue, EventKey, EventQueueBackend};
 use crate::threadsafe::Event;
-use crate::{SimState, SimTime};
-use std::cmp::Ordering;
+use crate::{EventHandle, SimState, SimTime};
 use std::fmt::Formatter;
+use std::sync::MutexGuard;
 
-/// Helper struct for the event queue. This struct holds a [`Box`] to the event itself alongside the data necessary to
-/// sort events within the priority queue, namely the execution time and a record of the event's insertion sequence.
+/// A view of an event that is waiting on a [`Simulation`]'s queue.
 ///
-/// The implementation of [`Ord`] on this struct cares first about the execution time, giving full control of event
-/// ordering to client code, comparing the insertion sequences only to break ties.
-pub(super) struct ScheduledEvent<State, Time>
+/// Produced by [`Simulation::pending_events()`] to support inspecting the queue's contents without executing any
+/// events, e.g. to assert on which events a test scenario has scheduled. Each view provides shared access to the event
+/// itself alongside its execution time, its priority, and the [`EventHandle`] it was scheduled with. The
+/// implementation of [`Debug`] prints all four.
+///
+/// [`Simulation`]: crate::threadsafe::Simulation
+/// [`Simulation::pending_events()`]: crate::threadsafe::Simulation::pending_events
+/// [`Debug`]: std::fmt::Debug
+pub struct PendingEvent<'a, State, Time, Backend = BinaryHeapQueue<Time>>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    key: &'a EventKey<Time>,
+    event: &'a dyn Event<State, Time, Backend>,
+}
+
+impl<'a, State, Time, Backend> PendingEvent<'a, State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    pub execution_time: Time,
-    pub event: Box<dyn Event<State, Time>>,
-    pub insertion_sequence: usize,
+    /// Pair the key used to order an event on the queue with the event itself.
+    pub(super) fn new(key: &'a EventKey<Time>, event: &'a dyn Event<State, Time, Backend>) -> Self {
+        Self { key, event }
+    }
+
+    /// Get the key used to order the event on the queue.
+    pub(super) fn key(&self) -> &'a EventKey<Time> {
+        self.key
+    }
+
+    /// Get a shared reference to the time at which the event is scheduled to execute.
+    pub fn execution_time(&self) -> &'a Time {
+        self.key.execution_time()
+    }
+
+    /// Get the priority the event was scheduled with, which breaks ties between events scheduled for the same time.
+    pub fn priority(&self) -> i32 {
+        self.key.priority()
+    }
+
+    /// Get the handle that was returned when the event was scheduled.
+    pub fn handle(&self) -> EventHandle {
+        self.key.handle()
+    }
+
+    /// Get a shared reference to the event itself.
+    pub fn event(&self) -> &'a dyn Event<State, Time, Backend> {
+        self.event
+    }
 }
 
-impl<State, Time> PartialEq<Self> for ScheduledEvent<State, Time>
+impl<State, Time, Backend> std::fmt::Debug for PendingEvent<'_, State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    fn eq(&self, other: &Self) -> bool {
-        self.insertion_sequence == other.insertion_sequence && self.execution_time == other.execution_time
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ScheduledEvent")
+            .field("event", &self.event)
+            .field("execution_time", self.key.execution_time())
+            .field("priority", &self.key.priority())
+            .field("insertion_sequence", &self.key.insertion_sequence())
+            .finish()
     }
 }
 
-impl<State, Time> Eq for ScheduledEvent<State, Time>
+/// A locked view of every event waiting on a [`Simulation`]'s queue.
+///
+/// Produced by [`Simulation::pending_events()`], and iterated over by reference to produce a [`PendingEvent`] for each
+/// event in the order they are expected to execute. The queue's [`Mutex`] stays locked for as long as this view is
+/// held, so other threads block on scheduling or cancelling events until it is dropped, and doing either from the
+/// holding thread will deadlock or panic.
+///
+/// [`Simulation`]: crate::threadsafe::Simulation
+/// [`Simulation::pending_events()`]: crate::threadsafe::Simulation::pending_events
+/// [`Mutex`]: std::sync::Mutex
+pub struct PendingEvents<'a, State, Time, Backend = BinaryHeapQueue<Time>>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
+    contents: MutexGuard<'a, QueueContents<State, Time, Backend>>,
 }
 
-impl<State, Time> PartialOrd<Self> for ScheduledEvent<State, Time>
+impl<'a, State, Time, Backend> PendingEvents<'a, State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    /// Wrap the locked contents of the queue.
+    pub(super) fn new(contents: MutexGuard<'a, QueueContents<State, Time, Backend>>) -> Self {
+        Self { contents }
+    }
+
+    /// Iterate over views of every pending event, in the order they are expected to execute.
+    ///
+    /// Note that gathering these views requires sorting the queue's contents, and so should be avoided in hot loops.
+    pub fn iter(&self) -> std::vec::IntoIter<PendingEvent<'_, State, Time, Backend>> {
+        let mut pending: Vec<_> = self.contents.pending().collect();
+        pending.sort_unstable_by(|a, b| a.key().cmp(b.key()));
+        pending.into_iter()
     }
 }
 
-impl<State, Time> Ord for ScheduledEvent<State, Time>
+impl<'s, State, Time, Backend> IntoIterator for &'s PendingEvents<'_, State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
-    fn cmp(&self, other: &Self) -> Ordering {
-        let comparison = self.execution_time.cmp(&other.execution_time);
-        match comparison {
-            Ordering::Equal => self.insertion_sequence.cmp(&other.insertion_sequence),
-            _ => comparison,
-        }
+    type Item = PendingEvent<'s, State, Time, Backend>;
+    type IntoIter = std::vec::IntoIter<PendingEvent<'s, State, Time, Backend>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
     }
 }
 
-impl<State, Time> std::fmt::Debug for ScheduledEvent<State, Time>
+impl<State, Time, Backend> std::fmt::Debug for PendingEvents<'_, State, Time, Backend>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        f.debug_struct("ScheduledEvent")
-            .field("event", &self.event)
-            .field("execution_time", &self.execution_time)
-            .field("insertion_sequence", &self.insertion_sequence)
-            .finish()
+        f.debug_list().entries(self.iter()).finish()
     }
 }
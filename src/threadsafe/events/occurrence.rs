@@ -0,0 +1,64 @@
+use super::event_traits::{Event, OkEvent};
+use crate::queue::EventQueueBackend;
+use crate::threadsafe::Simulation;
+use crate::{PeriodicHandle, Recurrence, SimState, SimTime};
+use std::fmt::Formatter;
+use std::ops::Add;
+
+/// The parts of a recurrence that carry over from one occurrence to the next.
+pub(crate) struct Recurring<Factory, Time> {
+    pub handle: PeriodicHandle,
+    pub factory: Factory,
+    pub period: Time,
+    /// The limit on occurrences after the one currently scheduled.
+    pub recurrence: Recurrence<Time>,
+}
+
+/// One occurrence of a periodic event, which executes the event produced for it and then schedules the next.
+///
+/// Presents itself as the wrapped event in its implementation of [`Debug`] and in its [`name()`], so that periodic
+/// events appear in traces and queue listings as though they had been scheduled directly.
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`name()`]: OkEvent::name
+pub(crate) struct Occurrence<EventType, Factory, Time> {
+    pub event: EventType,
+    /// Taken when the occurrence executes, to be handed to the next one.
+    pub recurring: Option<Recurring<Factory, Time>>,
+}
+
+impl<State, Time, Backend, EventType, Factory> OkEvent<State, Time, Backend> for Occurrence<EventType, Factory, Time>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync + Clone + Add<Output = Time> + 'static,
+    Backend: EventQueueBackend<Time> + Send,
+    EventType: Event<State, Time, Backend> + 'static,
+    Factory: FnMut() -> EventType + Send + 'static,
+{
+    fn execute(&mut self, simulation: &mut Simulation<State, Time, Backend>) {
+        if let Err(error) = self.event.execute(simulation) {
+            simulation.defer_error(error);
+        }
+
+        let recurring = self.recurring.take().expect("occurrence should only execute once");
+        if simulation.is_periodic(recurring.handle) {
+            let next_time = simulation.current_time().clone() + recurring.period.clone();
+            if let Err(error) = simulation.schedule_occurrence(recurring, next_time) {
+                simulation.defer_error(error);
+            }
+        }
+    }
+
+    fn name(&self) -> &str {
+        self.event.name()
+    }
+}
+
+impl<EventType, Factory, Time> std::fmt::Debug for Occurrence<EventType, Factory, Time>
+where
+    EventType: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        self.event.fmt(f)
+    }
+}
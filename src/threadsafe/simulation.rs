@@ -1,13 +1,38 @@
+use super::events::occurrence::{Occurrence, Recurring};
 use super::events::EventQueue;
-use super::Event;
-use crate::{SimState, SimTime};
+use super::{Event, FnEvent, PendingEvents};
+use crate::condition::{ConditionHandle, Waiter, WaiterId};
+use crate::observer::{AnyObserver, ObservedEvent, ObserverList};
+use crate::queue::{BinaryHeapQueue, EventQueueBackend};
+use crate::resource::{Container, Resource, SyncStore};
+use crate::{
+    EventHandle, Observer, ObserverHandle, PeriodicHandle, Recurrence, Signal, SimState, SimTime, StepOutcome,
+};
+use std::collections::HashMap;
 use std::fmt::{Debug, Formatter};
-use std::ops::Add;
+use std::ops::{Add, Sub};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// A predicate on the simulation's state, checked after each event executes.
+type StatePredicate<State> = Box<dyn Fn(&State) -> bool + Send + Sync>;
+
+/// Schedules whatever is waiting at the current time.
+type Resumption<State, Time, Backend> = Box<dyn FnOnce(&mut Simulation<State, Time, Backend>) -> crate::Result + Send>;
+
+type SimWaiter<State, Time, Backend> = Waiter<StatePredicate<State>, Resumption<State, Time, Backend>>;
 
 /// Contains the event queue and other state belonging to a simulation.
 ///
 /// This form of simulation behaves very similarly to the [`serial::Simulation`], but is easier to share across thread
-/// boundaries for the sake of enabling events to divide-and-conquer parts of their execution.
+/// boundaries for the sake of enabling events to divide-and-conquer parts of their execution. As with the
+/// [`serial::Simulation`], each scheduling method hands back an [`EventHandle`] which may be passed to [`cancel()`] to
+/// retract the event before it executes. Events scheduled for the same time execute in the order they were scheduled,
+/// unless given distinct priorities through [`schedule_with_priority()`].
+///
+/// Events passed by value to the scheduling methods are stored in memory recycled from events that have already
+/// executed or been cancelled, so a simulation whose queue has reached its working size schedules events without
+/// allocating. The `_from_boxed` variants instead keep the allocation of the box they are given.
 ///
 /// The expected workflow for a Simulation is:
 ///
@@ -23,21 +48,41 @@ use std::ops::Add;
 /// > `total_events_scheduled` is over the entirety of the simulation run, as opposed to the number currently in queue.
 ///
 /// [`serial::Simulation`]: crate::serial::Simulation
+/// [`cancel()`]: Simulation::cancel
+/// [`schedule_with_priority()`]: Simulation::schedule_with_priority
 /// [`run()`]: Simulation::run
 /// [`state()`]: Simulation::state
 /// [`state_mut()`]: Simulation::state_mut
 #[derive(Debug, Default)]
-pub struct Simulation<State, Time>
+pub struct Simulation<State, Time, Backend = BinaryHeapQueue<Time>>
 where
     State: SimState<Time> + Sync,
     Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
 {
     /// A priority queue of events that have been scheduled to execute, ordered ascending by execution time.
-    event_queue: EventQueue<State, Time>,
+    event_queue: EventQueue<State, Time, Backend>,
     /// The current shared state of the Simulation. Exclusive access will be granted to each event that executes.
     state: State,
     /// The current simulation time.
     current_time: Time,
+    /// Observers to notify before and after each event executes.
+    observers: ObserverList<Time, dyn AnyObserver<Time> + Send + Sync>,
+    /// The first error raised by a wrapper event, such as a closure, an occurrence or a resumed process, held until
+    /// that event finishes executing so that it can be reported in place of the event's own result.
+    ///
+    /// The wrappers implement `OkEvent` and report through here because they cannot implement `Event` directly:
+    /// they are generic over the simulation's state, so a downstream crate could implement `OkEvent` for them with
+    /// its own state type, which makes any direct implementation conflict with the blanket one.
+    deferred_error: Option<crate::Error>,
+    /// The pending occurrence of each recurrence that has not yet ended.
+    recurrences: Mutex<HashMap<PeriodicHandle, EventHandle>>,
+    /// The number of recurrences started over the simulation's lifetime, used to assign their handles.
+    recurrences_started: AtomicUsize,
+    /// Registered conditions, in the order they were registered.
+    waiters: Mutex<Vec<SimWaiter<State, Time, Backend>>>,
+    /// The number of conditions registered over the simulation's lifetime, used to assign their handles.
+    conditions_registered: AtomicUsize,
 }
 
 impl<State, Time> Simulation<State, Time>
@@ -46,12 +91,38 @@ where
     Time: SimTime + Send + Sync,
 {
     /// Initialize a Simulation instance with the provided starting state and an empty event queue, with clock set to
-    /// the provided starting time.
+    /// the provided starting time. Events are ordered by the default [`BinaryHeapQueue`] backend; use
+    /// [`with_backend()`] to select another.
+    ///
+    /// [`with_backend()`]: Simulation::with_backend
     pub fn new(initial_state: State, start_time: Time) -> Self {
+        Self::with_backend(initial_state, start_time, BinaryHeapQueue::new())
+    }
+}
+
+impl<State, Time, Backend> Simulation<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    /// Initialize a Simulation instance with the provided starting state and an empty event queue ordered by the
+    /// provided backend, with clock set to the provided starting time. The backend should not hold any keys.
+    ///
+    /// See [`queue`] for the available backends and guidance on choosing between them.
+    ///
+    /// [`queue`]: crate::queue
+    pub fn with_backend(initial_state: State, start_time: Time, backend: Backend) -> Self {
         Self {
-            event_queue: EventQueue::new(),
+            event_queue: EventQueue::new(backend),
             state: initial_state,
             current_time: start_time,
+            observers: ObserverList::new(),
+            deferred_error: None,
+            recurrences: Mutex::new(HashMap::new()),
+            recurrences_started: AtomicUsize::new(0),
+            waiters: Mutex::new(Vec::new()),
+            conditions_registered: AtomicUsize::new(0),
         }
     }
 
@@ -64,6 +135,9 @@ where
     /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
     ///    otherwise return to step 1.
     ///
+    /// Any [`Observer`]s attached through [`add_observer()`] are notified immediately before and after each event
+    /// executes.
+    ///
     /// # Errors
     ///
     /// Errors may occur during execution of events, and if encountered here they will be passed back to the caller,
@@ -82,29 +156,109 @@ where
     ///
     /// [`state.is_complete()`]: SimState::is_complete
     /// [`event.execute()`]: Event::execute
+    /// [`add_observer()`]: Simulation::add_observer
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Error::BadExecution`]: crate::Error::BadExecution
     /// [`BadExecution`]: crate::Error::BadExecution
     /// [`source()`]: crate::Error#method.source
     /// [`Mutex`]: std::sync::Mutex
     pub fn run(&mut self) -> crate::Result {
-        loop {
-            if self.state.is_complete(self.current_time()) {
-                return Ok(());
-            }
+        while let StepOutcome::Executed(_) = self.step()? {}
+        Ok(())
+    }
 
-            let next_event = self.next_event();
-            if next_event.is_none() {
-                return Ok(());
+    /// Execute at most `event_count` events from the priority queue, one at a time, in ascending order by execution
+    /// time. Returns the number of events that were executed, which will be less than `event_count` only if the
+    /// simulation reached completion or ran out of events first.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once `event_count`
+    /// events have been executed. Each call picks up where the previous call left off, making this method suitable for
+    /// e.g. a warm-up period of fixed length.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing events are passed back to the caller, unchanged, as with [`run()`].
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue to find the next event that
+    /// should be executed on each loop iteration. If that [`Mutex`] ever becomes poisoned, this method will panic.
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn run_for(&mut self, event_count: usize) -> crate::Result<usize> {
+        let mut events_executed = 0;
+        while events_executed < event_count {
+            match self.step()? {
+                StepOutcome::Executed(_) => events_executed += 1,
+                StepOutcome::Complete | StepOutcome::QueueEmpty => break,
             }
+        }
+        Ok(events_executed)
+    }
+
+    /// Execute exactly one event from the priority queue, if possible, and report what happened.
+    ///
+    /// Performs a single iteration of the loop described on [`run()`]:
+    ///
+    /// 1. Does [`state.is_complete()`] return true? If so, return [`StepOutcome::Complete`].
+    /// 2. Attempt to pop the next event from the queue. If there isn't one, return [`StepOutcome::QueueEmpty`].
+    /// 3. Pass `&mut self` to [`event.execute()`]. If execution results in an error, forward it to the caller;
+    ///    otherwise return [`StepOutcome::Executed`] with the handle that the event was scheduled with.
+    ///
+    /// As with [`run()`], any attached [`Observer`]s are notified immediately before and after the event executes.
+    ///
+    /// Stepping through a simulation is useful for driving it from a debugger or interactive session, or for inspecting
+    /// the simulation's state between events.
+    ///
+    /// # Errors
+    ///
+    /// Errors encountered while executing the event, or while scheduling whatever it leaves ready to resume, are passed
+    /// back to the caller, unchanged, as with [`run()`].
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue to find the next event that
+    /// should be executed. If that [`Mutex`] ever becomes poisoned, this method will panic.
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`event.execute()`]: Event::execute
+    /// [`StepOutcome::Complete`]: crate::StepOutcome::Complete
+    /// [`StepOutcome::QueueEmpty`]: crate::StepOutcome::QueueEmpty
+    /// [`StepOutcome::Executed`]: crate::StepOutcome::Executed
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn step(&mut self) -> crate::Result<StepOutcome> {
+        if self.state.is_complete(self.current_time()) {
+            return Ok(StepOutcome::Complete);
+        }
 
-            let mut next_event = next_event.expect("next_event should not be None");
-            next_event.execute(self)?;
+        match self.event_queue.next() {
+            Some((mut event, time, handle)) => {
+                self.current_time = time;
+                self.observers
+                    .before_event(&ObservedEvent::new(&event, event.name(), &self.current_time, handle));
+                let result = match (event.execute(self), self.deferred_error.take()) {
+                    (Ok(()), Some(error)) => Err(error),
+                    (result, _) => result,
+                };
+                self.observers.after_event(
+                    &ObservedEvent::new(&event, event.name(), &self.current_time, handle),
+                    &result,
+                );
+                self.event_queue.release(event);
+                let result = result.and_then(|()| self.resume_satisfied());
+                result.map(|_| StepOutcome::Executed(handle))
+            },
+            None => Ok(StepOutcome::QueueEmpty),
         }
     }
 
-    fn next_event(&mut self) -> Option<Box<dyn Event<State, Time>>> {
-        if let Some((event, time)) = self.event_queue.next() {
+    // only needed by tests that pop events without executing them,
+    // as step() works with the handle of the popped event as well
+    #[cfg(test)]
+    fn next_event(&mut self) -> Option<crate::arena::ArenaBox<dyn Event<State, Time, Backend>>> {
+        if let Some((event, time, _)) = self.event_queue.next() {
             self.current_time = time;
             Some(event)
         } else {
@@ -126,9 +280,9 @@ where
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule<EventType>(&self, event: EventType, time: Time) -> crate::Result
+    pub fn schedule<EventType>(&self, event: EventType, time: Time) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
         if time < self.current_time {
             return Err(crate::Error::BackInTime);
@@ -137,10 +291,7 @@ where
         // SAFETY: we've just checked that the desired execution time is either
         // Equal or Greater when compared to the current clock time, so it'll
         // be fine to add to the queue
-        unsafe {
-            self.schedule_unchecked(event, time);
-        }
-        Ok(())
+        unsafe { Ok(self.schedule_unchecked(event, time)) }
     }
 
     /// Schedule the provided event at the specified time. Assumes that the provided time is valid in the context of the
@@ -159,11 +310,11 @@ where
     /// becomes poisoned, this method will panic.
     ///
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_unchecked<EventType>(&self, event: EventType, time: Time)
+    pub unsafe fn schedule_unchecked<EventType>(&self, event: EventType, time: Time) -> EventHandle
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
-        self.schedule_unchecked_from_boxed(Box::new(event), time);
+        self.schedule_with_priority_unchecked(event, time, 0)
     }
 
     /// Schedule the provided event at the specified time.
@@ -180,7 +331,11 @@ where
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule_from_boxed(&self, event: Box<dyn Event<State, Time>>, time: Time) -> crate::Result {
+    pub fn schedule_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+    ) -> crate::Result<EventHandle> {
         if time < self.current_time {
             return Err(crate::Error::BackInTime);
         }
@@ -188,10 +343,7 @@ where
         // SAFETY: we've just checked that the desired execution time is either
         // Equal or Greater when compared to the current clock time, so it'll
         // be fine to add to the queue
-        unsafe {
-            self.schedule_unchecked_from_boxed(event, time);
-        }
-        Ok(())
+        unsafe { Ok(self.schedule_unchecked_from_boxed(event, time)) }
     }
 
     /// Schedule the provided event at the specified time. Assumes that the provided time is valid in the context of the
@@ -210,39 +362,22 @@ where
     /// becomes poisoned, this method will panic.
     ///
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_unchecked_from_boxed(&self, event: Box<dyn Event<State, Time>>, time: Time) {
-        self.event_queue.schedule_event(event, time);
-    }
-
-    /// Get a shared reference to the simulation state.
-    pub fn state(&self) -> &State {
-        &self.state
+    pub unsafe fn schedule_unchecked_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+    ) -> EventHandle {
+        self.schedule_with_priority_unchecked_from_boxed(event, time, 0)
     }
 
-    /// Get an exclusive reference to the simulation state.
-    pub fn state_mut(&mut self) -> &mut State {
-        &mut self.state
-    }
-
-    /// Get a shared reference to the current simulation time.
-    pub fn current_time(&self) -> &Time {
-        &self.current_time
-    }
-}
-
-impl<State, Time> Simulation<State, Time>
-where
-    State: SimState<Time> + Sync,
-    Time: SimTime + Send + Sync + Clone,
-{
-    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does.
+    /// Schedule the provided event at the specified time with the given priority. Among events scheduled for the same
+    /// time, those with a higher priority execute first, while events with equal priorities execute in the order they
+    /// were scheduled. Events scheduled without an explicit priority receive a priority of 0.
     ///
     /// # Errors
     ///
-    /// If the result of calling [`Clone::clone`] on the current sim time results in a new value that is somehow less
-    /// than the current sim time, this method will return an [`Error::BackInTime`]. Note that such behavior is not
-    /// expected from implementations of [`Clone::clone`] in most cases.
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// # Panics
     ///
@@ -251,23 +386,36 @@ where
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule_now<EventType>(&self, event: EventType) -> crate::Result
+    pub fn schedule_with_priority<EventType>(
+        &self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
-        let event_time = self.current_time.clone();
-        self.schedule(event, event_time)
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_with_priority_unchecked(event, time, priority)) }
     }
 
-    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does.
+    /// Schedule the provided event at the specified time with the given priority. Assumes that the provided time is
+    /// valid in the context of the client's simulation.
+    ///
+    /// Among events scheduled for the same time, those with a higher priority execute first, while events with equal
+    /// priorities execute in the order they were scheduled.
     ///
     /// # Safety
     ///
-    /// This method cannot directly trigger undefined behaviors, but relies on client implementations of
-    /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
-    /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
-    /// chosen type, this method will be safe to call.
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means.
     ///
     /// # Panics
     ///
@@ -275,21 +423,26 @@ where
     /// becomes poisoned, this method will panic.
     ///
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_now_unchecked<EventType>(&self, event: EventType)
+    pub unsafe fn schedule_with_priority_unchecked<EventType>(
+        &self,
+        event: EventType,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle
     where
-        EventType: Event<State, Time> + 'static,
+        EventType: Event<State, Time, Backend> + 'static,
     {
-        self.schedule_unchecked(event, self.current_time.clone());
+        self.event_queue.schedule_event(event, time, priority)
     }
 
-    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does.
+    /// Schedule the provided event at the specified time with the given priority. Among events scheduled for the same
+    /// time, those with a higher priority execute first, while events with equal priorities execute in the order they
+    /// were scheduled.
     ///
     /// # Errors
     ///
-    /// If the result of calling [`Clone::clone`] on the current sim time results in a new value that is somehow less
-    /// than the current sim time, this method will return an [`Error::BackInTime`]. Note that such behavior is not
-    /// expected from implementations of [`Clone::clone`] in most cases.
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// # Panics
     ///
@@ -298,20 +451,33 @@ where
     ///
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule_now_from_boxed(&self, event: Box<dyn Event<State, Time>>) -> crate::Result {
-        let event_time = self.current_time.clone();
-        self.schedule_from_boxed(event, event_time)
+    pub fn schedule_with_priority_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        // SAFETY: we've just checked that the desired execution time is either
+        // Equal or Greater when compared to the current clock time, so it'll
+        // be fine to add to the queue
+        unsafe { Ok(self.schedule_with_priority_unchecked_from_boxed(event, time, priority)) }
     }
 
-    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
-    /// execute before this event does.
+    /// Schedule the provided event at the specified time with the given priority. Assumes that the provided time is
+    /// valid in the context of the client's simulation.
+    ///
+    /// Among events scheduled for the same time, those with a higher priority execute first, while events with equal
+    /// priorities execute in the order they were scheduled.
     ///
     /// # Safety
     ///
-    /// This method cannot directly trigger undefined behaviors, but relies on client implementations of
-    /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
-    /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
-    /// chosen type, this method will be safe to call.
+    /// While this method cannot trigger undefined behaviors, scheduling an event for a time in the past is likely to be
+    /// a logical bug in client code. Generally, this method should only be invoked if the condition `time >= clock` is
+    /// already enforced at the call site through some other means.
     ///
     /// # Panics
     ///
@@ -319,157 +485,1080 @@ where
     /// becomes poisoned, this method will panic.
     ///
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_now_unchecked_from_boxed(&self, event: Box<dyn Event<State, Time>>) {
-        self.schedule_unchecked_from_boxed(event, self.current_time.clone());
+    pub unsafe fn schedule_with_priority_unchecked_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        time: Time,
+        priority: i32,
+    ) -> EventHandle {
+        self.event_queue.schedule_boxed_event(event, time, priority)
     }
-}
 
-impl<State, Time> Simulation<State, Time>
-where
-    State: SimState<Time> + Sync,
-    Time: SimTime + Send + Sync + Clone + Add<Output = Time>,
-{
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
+    /// Hold an error raised by a wrapper event until that event finishes executing. Only the first error deferred
+    /// during an event is reported, as any later ones are likely to be consequences of it.
+    pub(super) fn defer_error(&mut self, error: crate::Error) {
+        self.deferred_error.get_or_insert(error);
+    }
+
+    /// Schedule the provided closure to execute as an event at the specified time. The closure receives exclusive
+    /// access to the simulation, as [`Event::execute()`] does, and may fail in the same ways.
+    ///
+    /// The scheduled event is an unlabelled [`FnEvent`]; see [`schedule_labelled_fn()`] to name it for tracing.
     ///
     /// # Errors
     ///
-    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
-    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// # Panics
     ///
     /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
     /// becomes poisoned, this method will panic.
     ///
+    /// [`Event::execute()`]: Event::execute
+    /// [`FnEvent`]: super::FnEvent
+    /// [`schedule_labelled_fn()`]: Simulation::schedule_labelled_fn
     /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule_with_delay<EventType>(&self, event: EventType, delay: Time) -> crate::Result
+    pub fn schedule_fn<F>(&self, function: F, time: Time) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        F: FnOnce(&mut Self) -> crate::Result + Send + 'static,
     {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule(event, event_time)
+        self.schedule(FnEvent::new(function), time)
     }
 
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
+    /// Schedule the provided closure to execute as an event at the specified time, labelled for [`Debug`] output and
+    /// for tracing as the event's [`name()`].
     ///
-    /// # Safety
+    /// # Errors
     ///
-    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
-    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
-    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
-    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue.
     ///
     /// # Panics
     ///
     /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
     /// becomes poisoned, this method will panic.
     ///
+    /// [`name()`]: Event::name
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_with_delay_unchecked<EventType>(&self, event: EventType, delay: Time)
+    pub fn schedule_labelled_fn<F>(&self, label: &'static str, function: F, time: Time) -> crate::Result<EventHandle>
     where
-        EventType: Event<State, Time> + 'static,
+        F: FnOnce(&mut Self) -> crate::Result + Send + 'static,
     {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule_unchecked(event, event_time);
+        self.schedule(FnEvent::labelled(label, function), time)
     }
 
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
-    ///
-    /// # Errors
+    /// Cancel a previously scheduled event so that it will not execute. Returns `true` if the event referred to by
+    /// `handle` was still pending and has now been removed from the queue, or `false` if it had already executed or
+    /// been cancelled.
     ///
-    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
-    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    /// The cancelled event is dropped immediately.
     ///
     /// # Panics
     ///
     /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
     /// becomes poisoned, this method will panic.
     ///
-    /// [`Error::BackInTime`]: crate::Error::BackInTime
     /// [`Mutex`]: std::sync::Mutex
-    pub fn schedule_with_delay_from_boxed(&self, event: Box<dyn Event<State, Time>>, delay: Time) -> crate::Result {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule_from_boxed(event, event_time)
+    pub fn cancel(&self, handle: EventHandle) -> bool {
+        self.event_queue.cancel_event(handle)
     }
 
-    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
-    /// `self.current_time().clone() + delay`.
-    ///
-    /// # Safety
+    /// Stop a recurrence started by [`schedule_periodic()`], cancelling its pending occurrence so that no further
+    /// occurrences execute. Returns `true` if the recurrence was still running, or `false` if it had already ended or
+    /// been stopped.
     ///
-    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
-    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
-    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
-    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    /// Stopping a recurrence from within one of its own occurrences prevents the next occurrence from being scheduled.
     ///
     /// # Panics
     ///
-    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
-    /// becomes poisoned, this method will panic.
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue, as well as another on the
+    /// simulation's running recurrences. If either [`Mutex`] ever becomes poisoned, this method will panic.
     ///
+    /// [`schedule_periodic()`]: Simulation::schedule_periodic
     /// [`Mutex`]: std::sync::Mutex
-    pub unsafe fn schedule_with_delay_unchecked_from_boxed(&self, event: Box<dyn Event<State, Time>>, delay: Time) {
-        let event_time = self.current_time.clone() + delay;
-        self.schedule_unchecked_from_boxed(event, event_time);
+    pub fn stop_periodic(&self, handle: PeriodicHandle) -> bool {
+        let pending = self
+            .recurrences
+            .lock()
+            .expect("recurrence mutex should not have been poisoned")
+            .remove(&handle);
+        match pending {
+            Some(pending) => {
+                self.event_queue.cancel_event(pending);
+                true
+            },
+            None => false,
+        }
     }
-}
 
-impl<State, Time> std::fmt::Display for Simulation<State, Time>
-where
-    State: SimState<Time> + Sync,
-    Time: SimTime + Send + Sync,
-{
-    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "Simulation at time {:?}", self.current_time())
+    /// Withdraw a condition registered by [`schedule_when()`], so that its event will not be scheduled. Returns `true`
+    /// if the condition was still waiting to hold, or `false` if its event had already been scheduled or the condition
+    /// had already been withdrawn.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the registered conditions is poisoned.
+    ///
+    /// [`schedule_when()`]: Simulation::schedule_when
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn cancel_condition(&self, handle: ConditionHandle) -> bool {
+        let mut waiters = self
+            .waiters
+            .lock()
+            .expect("condition mutex should not have been poisoned");
+        match waiters
+            .iter()
+            .position(|waiter| waiter.id == WaiterId::Condition(handle))
+        {
+            Some(index) => {
+                waiters.remove(index);
+                true
+            },
+            None => false,
+        }
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::threadsafe::OkEvent;
 
-    #[derive(Debug)]
-    struct State {
-        executed_event_values: Vec<i32>,
-        complete: bool,
-    }
-    impl SimState<i32> for State {
-        fn is_complete(&self, _: &i32) -> bool {
-            self.complete
+    /// Resume each waiter whose predicate now holds, in the order they were registered.
+    fn resume_satisfied(&mut self) -> crate::Result {
+        let mut index = 0;
+        loop {
+            let waiters = self
+                .waiters
+                .get_mut()
+                .expect("condition mutex should not have been poisoned");
+            if index >= waiters.len() {
+                return Ok(());
+            }
+            let satisfied = match &waiters[index].predicate {
+                Some(predicate) => predicate(&self.state),
+                None => false,
+            };
+            if satisfied {
+                let waiter = waiters.remove(index);
+                (waiter.resume)(self)?;
+            } else {
+                index += 1;
+            }
         }
     }
 
-    #[derive(Debug)]
-    struct TestEvent {
-        value: i32,
+    /// Check whether a recurrence is still running. Exclusive access allows for bypassing the [`Mutex`] rather than
+    /// locking it.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(super) fn is_periodic(&mut self, handle: PeriodicHandle) -> bool {
+        self.recurrences
+            .get_mut()
+            .expect("recurrence mutex should not have been poisoned")
+            .contains_key(&handle)
     }
 
-    impl Event<State, i32> for TestEvent {
-        fn execute(&mut self, sim: &mut Simulation<State, i32>) -> crate::Result {
-            sim.state_mut().executed_event_values.push(self.value);
-            Ok(())
+    /// Move a pending event to a new execution time, returning a fresh [`EventHandle`] that refers to the moved event.
+    ///
+    /// The event is treated as though it were newly scheduled for `time` with its original priority: it receives a new
+    /// insertion sequence, and so will execute after any other events of equal priority already scheduled for that same
+    /// time. The handle passed in no longer refers to the event once this method succeeds, so hold on to the returned
+    /// handle instead.
+    ///
+    /// # Errors
+    ///
+    /// If `time` is less than the current clock time on `self`, returns an [`Error::BackInTime`] to indicate the likely
+    /// presence of a logical bug at the call site, with no modifications to the queue. If `handle` does not refer to a
+    /// pending event, e.g. because that event has already executed or been cancelled, returns an
+    /// [`Error::EventNotFound`] instead.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn reschedule(&self, handle: EventHandle, time: Time) -> crate::Result<EventHandle> {
+        if time < self.current_time {
+            return Err(crate::Error::BackInTime);
         }
+
+        self.event_queue
+            .reschedule_event(handle, time)
+            .ok_or(crate::Error::EventNotFound)
     }
 
-    #[derive(Debug)]
-    struct CompletionEvent {}
+    /// Get a shared reference to the simulation state.
+    pub fn state(&self) -> &State {
+        &self.state
+    }
 
-    impl OkEvent<State, i32> for CompletionEvent {
-        fn execute(&mut self, sim: &mut Simulation<State, i32>) {
-            sim.state_mut().complete = true;
-        }
+    /// Get an exclusive reference to the simulation state.
+    pub fn state_mut(&mut self) -> &mut State {
+        &mut self.state
     }
 
-    fn setup() -> Simulation<State, i32> {
-        let sim = Simulation::new(
-            State {
-                executed_event_values: Vec::with_capacity(3),
-                complete: false,
+    /// Get a shared reference to the current simulation time.
+    pub fn current_time(&self) -> &Time {
+        &self.current_time
+    }
+
+    /// Attach an observer that will be notified immediately before and after each event executes, returning a handle
+    /// through which the observer may later be retrieved.
+    ///
+    /// Observers are notified in the order they were attached. The [`threadsafe::Simulation`] requires observers to be
+    /// [`Send`] and [`Sync`] so that the simulation itself remains shareable across threads.
+    ///
+    /// [`threadsafe::Simulation`]: Simulation
+    pub fn add_observer<ObserverType>(&mut self, observer: ObserverType) -> ObserverHandle<ObserverType>
+    where
+        ObserverType: Observer<Time> + Send + Sync + 'static,
+    {
+        self.observers.add(Box::new(observer))
+    }
+
+    /// Get a shared reference to a previously attached observer, e.g. to read the results it has gathered. Returns
+    /// `None` if the observer has since been removed.
+    pub fn observer<ObserverType>(&self, handle: &ObserverHandle<ObserverType>) -> Option<&ObserverType>
+    where
+        ObserverType: Observer<Time> + Send + Sync + 'static,
+    {
+        self.observers.get(handle)
+    }
+
+    /// Get an exclusive reference to a previously attached observer. Returns `None` if the observer has since been
+    /// removed.
+    pub fn observer_mut<ObserverType>(&mut self, handle: &ObserverHandle<ObserverType>) -> Option<&mut ObserverType>
+    where
+        ObserverType: Observer<Time> + Send + Sync + 'static,
+    {
+        self.observers.get_mut(handle)
+    }
+
+    /// Detach a previously attached observer so that it will no longer be notified of executing events, handing it
+    /// back to the caller. Returns `None` if the observer has already been removed.
+    pub fn remove_observer<ObserverType>(&mut self, handle: ObserverHandle<ObserverType>) -> Option<ObserverType>
+    where
+        ObserverType: Observer<Time> + Send + Sync + 'static,
+    {
+        self.observers.remove(handle)
+    }
+
+    /// Get a copy of the execution time of the next event on the queue, if any, without executing it.
+    ///
+    /// Cancelled events are never reported here. This method is useful for e.g. deciding how far a continuous process
+    /// may be integrated before the next discrete event interrupts it. The time is copied out rather than borrowed, as
+    /// other threads may reorder the queue as soon as its [`Mutex`] is unlocked.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] on the internal event queue ever becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn peek_next_time(&self) -> Option<Time>
+    where
+        Time: Clone,
+    {
+        self.event_queue.peek_time()
+    }
+
+    /// Get the number of events currently waiting on the queue. Cancelled events are not counted.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn pending_event_count(&self) -> usize {
+        self.event_queue.pending_count()
+    }
+
+    /// Lock the queue to view every event currently waiting on it. Iterating over the returned [`PendingEvents`] by
+    /// reference produces views in the order the events are expected to execute.
+    ///
+    /// Each [`PendingEvent`] provides the event's execution time, the [`EventHandle`] it was scheduled with, and shared
+    /// access to the event itself for printing via [`Debug`]. Cancelled events are skipped. The queue stays locked
+    /// until the [`PendingEvents`] is dropped, so drop it before scheduling or cancelling any events.
+    ///
+    /// # Panics
+    ///
+    /// If the [`Mutex`] on the internal event queue ever becomes poisoned, this method will panic.
+    ///
+    /// [`PendingEvents`]: super::PendingEvents
+    /// [`PendingEvent`]: super::PendingEvent
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn pending_events(&self) -> PendingEvents<'_, State, Time, Backend> {
+        self.event_queue.pending_events()
+    }
+}
+
+impl<State, Time, Backend> Simulation<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync + Clone,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    /// Execute every event scheduled at or before `horizon`, one at a time, in ascending order by execution time, then
+    /// advance the clock to `horizon`.
+    ///
+    /// This method follows the same loop as [`run()`], with an additional check that stops the loop once the next
+    /// event on the queue is scheduled for some time after `horizon`. Events scheduled for exactly `horizon` will
+    /// execute. If the loop stops because the event queue is empty or because the next event lies beyond `horizon`, the
+    /// clock is then set to a clone of `horizon`. If instead [`state.is_complete()`] returns true, the clock is left at
+    /// the execution time of the last event so that it accurately reflects when the simulation finished.
+    ///
+    /// Splitting a run with this method allows for e.g. clearing statistics after a warm-up period, then continuing
+    /// with the measurement phase.
+    ///
+    /// # Errors
+    ///
+    /// If `horizon` is less than the current clock time on `self`, returns an [`Error::BackInTime`] without executing
+    /// any events. Otherwise, errors encountered while executing events are passed back to the caller, unchanged, as
+    /// with [`run()`].
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue to find the next event that
+    /// should be executed on each loop iteration. If that [`Mutex`] ever becomes poisoned, this method will panic.
+    ///
+    /// [`run()`]: Simulation::run
+    /// [`state.is_complete()`]: SimState::is_complete
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn run_until(&mut self, horizon: &Time) -> crate::Result {
+        if *horizon < self.current_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        loop {
+            match self.event_queue.peek_time() {
+                Some(next_time) if next_time <= *horizon => {},
+                _ => break,
+            }
+
+            match self.step()? {
+                StepOutcome::Executed(_) => {},
+                StepOutcome::Complete => return Ok(()),
+                StepOutcome::QueueEmpty => break,
+            }
+        }
+
+        if !self.state.is_complete(self.current_time()) {
+            self.current_time = horizon.clone();
+        }
+        Ok(())
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does.
+    ///
+    /// # Errors
+    ///
+    /// If the result of calling [`Clone::clone`] on the current sim time results in a new value that is somehow less
+    /// than the current sim time, this method will return an [`Error::BackInTime`]. Note that such behavior is not
+    /// expected from implementations of [`Clone::clone`] in most cases.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_now<EventType>(&self, event: EventType) -> crate::Result<EventHandle>
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event_time = self.current_time.clone();
+        self.schedule(event, event_time)
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on client implementations of
+    /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
+    /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
+    /// chosen type, this method will be safe to call.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub unsafe fn schedule_now_unchecked<EventType>(&self, event: EventType) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        self.schedule_unchecked(event, self.current_time.clone())
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does.
+    ///
+    /// # Errors
+    ///
+    /// If the result of calling [`Clone::clone`] on the current sim time results in a new value that is somehow less
+    /// than the current sim time, this method will return an [`Error::BackInTime`]. Note that such behavior is not
+    /// expected from implementations of [`Clone::clone`] in most cases.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_now_from_boxed(&self, event: Box<dyn Event<State, Time, Backend>>) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone();
+        self.schedule_from_boxed(event, event_time)
+    }
+
+    /// Schedule the provided event to execute at the current sim time. Events previously scheduled for "now" will still
+    /// execute before this event does.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on client implementations of
+    /// [`Clone::clone`] producing new values of [`SimTime`] that are not less than the cloned receiver (i.e. the
+    /// current simulation time). If `my_sim_time.clone().cmp(my_sim_time) != Ordering::Less` is always true for your
+    /// chosen type, this method will be safe to call.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub unsafe fn schedule_now_unchecked_from_boxed(&self, event: Box<dyn Event<State, Time, Backend>>) -> EventHandle {
+        self.schedule_unchecked_from_boxed(event, self.current_time.clone())
+    }
+
+    /// Register an event to be scheduled once the provided predicate holds for the simulation's state.
+    ///
+    /// Registered predicates are checked after each event executes, in the order they were registered, much as
+    /// [`SimState::is_complete()`] is checked before each event. The first time a predicate is found to hold, its event
+    /// is scheduled at the current time and the condition removed, so an event that should recur must register its
+    /// condition again. A predicate that already holds when registered is only found to do so once the next event
+    /// finishes executing. The returned [`ConditionHandle`] may be passed to [`cancel_condition()`] to withdraw the
+    /// condition beforehand.
+    ///
+    /// Should the event fail to be scheduled once its condition holds, under the same condition as [`schedule_now()`],
+    /// the resulting [`Error::BackInTime`] is passed back from the [`step()`] that found the condition to hold.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the registered conditions is poisoned.
+    ///
+    /// [`SimState::is_complete()`]: SimState::is_complete
+    /// [`cancel_condition()`]: Simulation::cancel_condition
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`step()`]: Simulation::step
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_when<EventType, Predicate>(&self, predicate: Predicate, event: EventType) -> ConditionHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+        Predicate: Fn(&State) -> bool + Send + Sync + 'static,
+    {
+        let handle = ConditionHandle::new(self.conditions_registered.fetch_add(1, Ordering::Relaxed));
+        let waiter: SimWaiter<State, Time, Backend> = Waiter {
+            id: WaiterId::Condition(handle),
+            predicate: Some(Box::new(predicate)),
+            resume: Box::new(move |simulation: &mut Self| simulation.schedule_now(event).map(|_| ())),
+        };
+        self.waiters
+            .lock()
+            .expect("condition mutex should not have been poisoned")
+            .push(waiter);
+        handle
+    }
+
+    /// Request a unit of the selected [`Resource`] in the simulation's state. If a unit is free, the continuation is
+    /// scheduled at the current time and its handle returned. Otherwise, the continuation waits in line until a unit is
+    /// released through [`release_resource()`], and `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`release_resource()`]: Simulation::release_resource
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn request_resource<Continuation, Select>(
+        &mut self,
+        resource: Select,
+        continuation: Continuation,
+    ) -> crate::Result<Option<EventHandle>>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
+    {
+        self.request_resource_with_priority(resource, continuation, 0)
+    }
+
+    /// Request a unit of the selected [`Resource`] with the provided priority, as with [`request_resource()`].
+    /// Priorities only affect the order of waiting requests under [`Discipline::Priority`].
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`request_resource()`]: Simulation::request_resource
+    /// [`Discipline::Priority`]: crate::resource::Discipline::Priority
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn request_resource_with_priority<Continuation, Select>(
+        &mut self,
+        resource: Select,
+        continuation: Continuation,
+        priority: i32,
+    ) -> crate::Result<Option<EventHandle>>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
+    {
+        match resource(&mut self.state).request_with_priority(continuation, priority) {
+            Some(granted) => self.schedule_now(granted).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Release a unit of the selected [`Resource`] in the simulation's state. If a request is waiting for a unit, it is
+    /// granted and its continuation scheduled at the current time, and the continuation's handle returned.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a granted request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`].
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if no units of the resource are held, as with [`Resource::release()`]. It will also panic
+    /// if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Resource`]: crate::resource::Resource
+    /// [`Resource::release()`]: crate::resource::Resource::release
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn release_resource<Continuation, Select>(&mut self, resource: Select) -> crate::Result<Option<EventHandle>>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Resource<Continuation>,
+    {
+        match resource(&mut self.state).release() {
+            Some(granted) => self.schedule_now(granted).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Request to add the provided amount to the selected [`Container`] in the simulation's state. Once the amount
+    /// fits, the continuation is scheduled at the current time, along with those of any requests to drain the container
+    /// that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Container`]: crate::resource::Container
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn fill_container<Amount, Continuation, Select>(
+        &mut self,
+        container: Select,
+        amount: Amount,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Amount: Copy + PartialOrd + Add<Output = Amount> + Sub<Output = Amount>,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Container<Amount, Continuation>,
+    {
+        let satisfied = container(&mut self.state).fill(amount, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to remove the provided amount from the selected [`Container`] in the simulation's state. Once enough is
+    /// stored, the continuation is scheduled at the current time, along with those of any requests to fill the
+    /// container that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Container`]: crate::resource::Container
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn drain_container<Amount, Continuation, Select>(
+        &mut self,
+        container: Select,
+        amount: Amount,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Amount: Copy + PartialOrd + Add<Output = Amount> + Sub<Output = Amount>,
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Container<Amount, Continuation>,
+    {
+        let satisfied = container(&mut self.state).drain(amount, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to add an item to the selected [`SyncStore`] in the simulation's state. Once there is room, the
+    /// continuation is scheduled at the current time, along with those of any requests to get an item that can then be
+    /// satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`SyncStore`]: crate::resource::SyncStore
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn put_item<Item, Continuation, Select>(
+        &mut self,
+        store: Select,
+        item: Item,
+        continuation: Continuation,
+    ) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut SyncStore<Item, Continuation>,
+    {
+        let satisfied = store(&mut self.state).put(item, continuation);
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to take any item from the selected [`SyncStore`] in the simulation's state. Once the store holds an
+    /// item, the continuation built from it by `deliver` is scheduled at the current time, along with those of any
+    /// requests to put an item that can then be satisfied.
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`SyncStore`]: crate::resource::SyncStore
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn get_item<Item, Continuation, Select, Deliver>(&mut self, store: Select, deliver: Deliver) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut SyncStore<Item, Continuation>,
+        Deliver: FnOnce(Item) -> Continuation + Send + Sync + 'static,
+    {
+        let satisfied = store(&mut self.state).get(Box::new(deliver));
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Request to take an item accepted by the provided filter from the selected [`SyncStore`] in the simulation's
+    /// state, as with [`get_item()`].
+    ///
+    /// # Errors
+    ///
+    /// If the continuation of a satisfied request cannot be scheduled at the current time, returns an
+    /// [`Error::BackInTime`] under the same condition as [`schedule_now()`], leaving the continuations of requests
+    /// satisfied after it unscheduled.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`SyncStore`]: crate::resource::SyncStore
+    /// [`get_item()`]: Simulation::get_item
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn get_item_where<Item, Continuation, Select, Filter, Deliver>(
+        &mut self,
+        store: Select,
+        filter: Filter,
+        deliver: Deliver,
+    ) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut SyncStore<Item, Continuation>,
+        Filter: Fn(&Item) -> bool + Send + Sync + 'static,
+        Deliver: FnOnce(Item) -> Continuation + Send + Sync + 'static,
+    {
+        let satisfied = store(&mut self.state).get_where(Box::new(filter), Box::new(deliver));
+        self.schedule_satisfied(satisfied)
+    }
+
+    /// Fire the selected [`Signal`] in the simulation's state, scheduling every continuation waiting on it at the
+    /// current time in the order they were registered. Returns the number of continuations scheduled.
+    ///
+    /// # Errors
+    ///
+    /// If a waiting continuation cannot be scheduled at the current time, returns an [`Error::BackInTime`] under the
+    /// same condition as [`schedule_now()`]. The signal has still fired, and the continuations registered after that
+    /// one are dropped.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if the [`Mutex`] protecting the event queue is poisoned.
+    ///
+    /// [`Signal`]: crate::Signal
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`schedule_now()`]: Simulation::schedule_now
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn fire_signal<Continuation, Select>(&mut self, signal: Select) -> crate::Result<usize>
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+        Select: FnOnce(&mut State) -> &mut Signal<Continuation>,
+    {
+        let woken = signal(&mut self.state).fire();
+        let count = woken.len();
+        self.schedule_satisfied(woken)?;
+        Ok(count)
+    }
+
+    /// Schedule the continuations of satisfied requests at the current time, in the order they were satisfied.
+    fn schedule_satisfied<Continuation>(&self, satisfied: Vec<Continuation>) -> crate::Result
+    where
+        Continuation: Event<State, Time, Backend> + 'static,
+    {
+        for continuation in satisfied {
+            self.schedule_now(continuation)?;
+        }
+        Ok(())
+    }
+}
+
+impl<State, Time, Backend> Simulation<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync + Clone + Add<Output = Time>,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_with_delay<EventType>(&self, event: EventType, delay: Time) -> crate::Result<EventHandle>
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
+    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
+    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
+    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub unsafe fn schedule_with_delay_unchecked<EventType>(&self, event: EventType, delay: Time) -> EventHandle
+    where
+        EventType: Event<State, Time, Backend> + 'static,
+    {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_unchecked(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// to indicate the likely presence of a logical bug at the call site, with no modifications to the queue.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_with_delay_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        delay: Time,
+    ) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_from_boxed(event, event_time)
+    }
+
+    /// Schedule the provided event after the specified delay. The event's execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// # Safety
+    ///
+    /// This method cannot directly trigger undefined behaviors, but relies on the provided `delay` being "nonnegative;"
+    /// in other words that `self.current_time().cmp(self.current_time().clone() + delay) != Ordering::Greater` should
+    /// always be true. If you are certain that is true for your type, this method will be safe to call. Alternatively,
+    /// you may call this method to intentionally schedule an event in the past if your use case truly calls for that.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub unsafe fn schedule_with_delay_unchecked_from_boxed(
+        &self,
+        event: Box<dyn Event<State, Time, Backend>>,
+        delay: Time,
+    ) -> EventHandle {
+        let event_time = self.current_time.clone() + delay;
+        self.schedule_unchecked_from_boxed(event, event_time)
+    }
+
+    /// Move a pending event so that it executes after the specified delay, returning a fresh [`EventHandle`] that
+    /// refers to the moved event. The event's new execution time will be equal to the result of
+    /// `self.current_time().clone() + delay`.
+    ///
+    /// As with [`reschedule()`], the event is treated as though it were newly scheduled and the handle passed in no
+    /// longer refers to it once this method succeeds.
+    ///
+    /// # Errors
+    ///
+    /// If the calculated execution time is less than the current clock time on `self`, returns an [`Error::BackInTime`]
+    /// with no modifications to the queue. If `handle` does not refer to a pending event, returns an
+    /// [`Error::EventNotFound`] instead.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue. If that [`Mutex`] ever
+    /// becomes poisoned, this method will panic.
+    ///
+    /// [`reschedule()`]: Simulation::reschedule
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Error::EventNotFound`]: crate::Error::EventNotFound
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn reschedule_with_delay(&self, handle: EventHandle, delay: Time) -> crate::Result<EventHandle> {
+        let event_time = self.current_time.clone() + delay;
+        self.reschedule(handle, event_time)
+    }
+
+    /// Schedule an event to recur at a fixed period, starting at the specified time. Each occurrence executes a fresh
+    /// event produced by `event_factory` when the occurrence is scheduled, and the next occurrence is scheduled one
+    /// `period` after the previous one executes. The `recurrence` limits how many occurrences are scheduled, either by
+    /// count or by the latest time at which one may occur.
+    ///
+    /// Only one occurrence is on the queue at any time, and it appears in [`pending_events()`] and to observers as the
+    /// event produced for it. The returned [`PeriodicHandle`] may be passed to [`stop_periodic()`] to end the
+    /// recurrence early. Errors returned by an occurrence's event are passed back from [`run()`] as usual, and do not
+    /// end the recurrence.
+    ///
+    /// # Errors
+    ///
+    /// If `first_time` is less than the current clock time on `self`, or if adding `period` to `first_time` does not
+    /// produce a later time, returns an [`Error::BackInTime`] with no modifications to the queue.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue, as well as another on the
+    /// simulation's running recurrences. If either [`Mutex`] ever becomes poisoned, this method will panic.
+    ///
+    /// [`pending_events()`]: Simulation::pending_events
+    /// [`stop_periodic()`]: Simulation::stop_periodic
+    /// [`run()`]: Simulation::run
+    /// [`Error::BackInTime`]: crate::Error::BackInTime
+    /// [`Mutex`]: std::sync::Mutex
+    pub fn schedule_periodic<EventType, Factory>(
+        &self,
+        event_factory: Factory,
+        first_time: Time,
+        period: Time,
+        recurrence: Recurrence<Time>,
+    ) -> crate::Result<PeriodicHandle>
+    where
+        Time: 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        Factory: FnMut() -> EventType + Send + 'static,
+    {
+        if first_time < self.current_time || first_time.clone() + period.clone() <= first_time {
+            return Err(crate::Error::BackInTime);
+        }
+
+        let handle = PeriodicHandle::new(self.recurrences_started.fetch_add(1, Ordering::Relaxed));
+        let recurring = Recurring {
+            handle,
+            factory: event_factory,
+            period,
+            recurrence,
+        };
+        self.schedule_occurrence(recurring, first_time)?;
+        Ok(handle)
+    }
+
+    /// Schedule the next occurrence of a recurrence for the provided time if its limit permits, or else end the
+    /// recurrence.
+    ///
+    /// # Panics
+    ///
+    /// This method requires the ability to lock the [`Mutex`] on the internal event queue, as well as another on the
+    /// simulation's running recurrences. If either [`Mutex`] ever becomes poisoned, this method will panic.
+    ///
+    /// [`Mutex`]: std::sync::Mutex
+    pub(super) fn schedule_occurrence<EventType, Factory>(
+        &self,
+        mut recurring: Recurring<Factory, Time>,
+        time: Time,
+    ) -> crate::Result
+    where
+        Time: 'static,
+        EventType: Event<State, Time, Backend> + 'static,
+        Factory: FnMut() -> EventType + Send + 'static,
+    {
+        let handle = recurring.handle;
+        recurring.recurrence = match recurring.recurrence.clone().admit(&time) {
+            Some(recurrence) => recurrence,
+            None => {
+                self.recurrences
+                    .lock()
+                    .expect("recurrence mutex should not have been poisoned")
+                    .remove(&handle);
+                return Ok(());
+            },
+        };
+
+        let occurrence = Occurrence {
+            event: (recurring.factory)(),
+            recurring: Some(recurring),
+        };
+        // lock the recurrences only once the event queue has been unlocked, so that the two are never held at once
+        let scheduled = self.schedule(occurrence, time);
+        let mut recurrences = self
+            .recurrences
+            .lock()
+            .expect("recurrence mutex should not have been poisoned");
+        match scheduled {
+            Ok(pending) => {
+                recurrences.insert(handle, pending);
+                Ok(())
+            },
+            Err(error) => {
+                recurrences.remove(&handle);
+                Err(error)
+            },
+        }
+    }
+}
+
+impl<State, Time, Backend> std::fmt::Display for Simulation<State, Time, Backend>
+where
+    State: SimState<Time> + Sync,
+    Time: SimTime + Send + Sync,
+    Backend: EventQueueBackend<Time> + Send,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "Simulation at time {:?}", self.current_time())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::threadsafe::OkEvent;
+
+    #[derive(Debug)]
+    struct State {
+        executed_event_values: Vec<i32>,
+        complete: bool,
+    }
+    impl SimState<i32> for State {
+        fn is_complete(&self, _: &i32) -> bool {
+            self.complete
+        }
+    }
+
+    #[derive(Debug)]
+    struct TestEvent {
+        value: i32,
+    }
+
+    impl Event<State, i32> for TestEvent {
+        fn execute(&mut self, sim: &mut Simulation<State, i32>) -> crate::Result {
+            sim.state_mut().executed_event_values.push(self.value);
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct CompletionEvent {}
+
+    impl OkEvent<State, i32> for CompletionEvent {
+        fn execute(&mut self, sim: &mut Simulation<State, i32>) {
+            sim.state_mut().complete = true;
+        }
+    }
+
+    fn setup() -> Simulation<State, i32> {
+        let sim = Simulation::new(
+            State {
+                executed_event_values: Vec::with_capacity(3),
+                complete: false,
             },
             0,
         );
@@ -612,4 +1701,441 @@ mod tests {
             "state should match first executed event"
         );
     }
+
+    #[test]
+    fn cancelled_events_do_not_execute() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule(TestEvent { value: 4 }, 1)
+            .expect("should be able to schedule new event");
+        let first_handle = EventHandle::new(0);
+
+        assert!(sim.cancel(handle), "pending event should be cancellable");
+        assert!(sim.cancel(first_handle), "event at top of queue should be cancellable");
+        sim.run().unwrap();
+
+        assert_eq!(
+            vec![3, 2],
+            sim.state().executed_event_values,
+            "cancelled events should not have executed"
+        );
+    }
+
+    #[test]
+    fn cancel_reports_whether_event_was_pending() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule(TestEvent { value: 4 }, 5)
+            .expect("should be able to schedule new event");
+        assert_eq!(3, handle.insertion_sequence(), "handle should wrap insertion sequence");
+
+        assert!(sim.cancel(handle), "pending event should be cancellable");
+        assert!(!sim.cancel(handle), "event should not be cancellable twice");
+
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+        assert!(
+            !sim.cancel(EventHandle::new(0)),
+            "executed event should not be cancellable"
+        );
+    }
+
+    #[test]
+    fn closures_execute_as_events() {
+        let mut sim = setup();
+        sim.schedule_fn(
+            |sim| {
+                sim.state_mut().executed_event_values.push(10);
+                Ok(())
+            },
+            1,
+        )
+        .unwrap();
+        let handle = sim
+            .schedule_labelled_fn("failure", |_| Err(crate::Error::EventNotFound), 3)
+            .unwrap();
+
+        {
+            let pending_events = sim.pending_events();
+            let pending = pending_events
+                .iter()
+                .find(|pending| pending.handle() == handle)
+                .unwrap();
+            assert_eq!("failure", pending.event().name(), "label should name event");
+            assert_eq!(
+                "FnEvent(\"failure\")",
+                format!("{:?}", pending.event()),
+                "label should appear in debug output"
+            );
+        }
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.run(),
+            "closure's error should be passed back"
+        );
+        assert_eq!(
+            vec![1, 10, 3],
+            sim.state().executed_event_values,
+            "closures should execute in order with other events"
+        );
+    }
+
+    #[test]
+    fn periodic_events_recur_within_limits() {
+        let mut sim = setup();
+        let mut next_value = 10;
+        let factory = move || {
+            next_value += 1;
+            TestEvent { value: next_value }
+        };
+        sim.schedule_periodic(factory, 1, 2, Recurrence::Times(3)).unwrap();
+        sim.schedule_periodic(|| TestEvent { value: 20 }, 3, 3, Recurrence::Until(9))
+            .unwrap();
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.schedule_periodic(|| TestEvent { value: 30 }, 1, 0, Recurrence::Forever),
+            "recurrence should need to advance the clock"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 11, 3, 20, 12, 2, 13, 20, 20],
+            sim.state().executed_event_values,
+            "occurrences should execute each period until reaching their limits"
+        );
+        assert_eq!(9, *sim.current_time(), "no occurrence should follow the end time");
+    }
+
+    #[test]
+    fn periodic_events_can_be_stopped() {
+        let mut sim = setup();
+        let handle = sim
+            .schedule_periodic(|| TestEvent { value: 10 }, 1, 2, Recurrence::Forever)
+            .unwrap();
+
+        sim.run_until(&4).unwrap();
+        assert_eq!(
+            1,
+            sim.pending_event_count(),
+            "recurrence should keep one occurrence pending"
+        );
+        assert!(sim.stop_periodic(handle), "running recurrence should be stoppable");
+        assert!(!sim.stop_periodic(handle), "recurrence should not be stoppable twice");
+        assert_eq!(0, sim.pending_event_count(), "pending occurrence should be cancelled");
+
+        assert_eq!(
+            vec![1, 10, 3, 10, 2],
+            sim.state().executed_event_values,
+            "occurrences should execute until stopped"
+        );
+    }
+
+    #[test]
+    fn conditions_schedule_events_once_they_hold() {
+        let mut sim = setup();
+        let fired = sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&3),
+            TestEvent { value: 30 },
+        );
+        let withdrawn = sim.schedule_when(|_: &State| true, TestEvent { value: 40 });
+        assert!(sim.cancel_condition(withdrawn), "waiting condition should be withdrawn");
+        assert!(
+            !sim.cancel_condition(withdrawn),
+            "condition should not be withdrawn twice"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 3, 30, 2],
+            sim.state().executed_event_values,
+            "event should execute as soon as its condition holds"
+        );
+        assert!(
+            !sim.cancel_condition(fired),
+            "condition should be removed once it holds"
+        );
+    }
+
+    #[test]
+    fn failed_events_do_not_resume_conditions() {
+        let mut sim = setup();
+        let condition = sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&99),
+            TestEvent { value: 30 },
+        );
+        sim.schedule_fn(
+            |sim| {
+                sim.state_mut().executed_event_values.push(99);
+                Err(crate::Error::EventNotFound)
+            },
+            1,
+        )
+        .unwrap();
+
+        assert_eq!(Err(crate::Error::EventNotFound), sim.run());
+        assert!(
+            sim.cancel_condition(condition),
+            "condition should not be checked after a failed event"
+        );
+        sim.schedule_when(
+            |state: &State| state.executed_event_values.contains(&99),
+            TestEvent { value: 30 },
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 99, 3, 30, 2],
+            sim.state().executed_event_values,
+            "condition should next be checked after the following event"
+        );
+    }
+
+    #[test]
+    fn rescheduled_events_execute_at_new_time() {
+        let mut sim = setup();
+        let first_handle = EventHandle::new(0);
+        let new_handle = sim
+            .reschedule(first_handle, 3)
+            .expect("pending event should be reschedulable");
+        assert_ne!(first_handle, new_handle, "rescheduling should produce a fresh handle");
+        assert!(
+            !sim.cancel(first_handle),
+            "old handle should no longer refer to the event"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![3, 1, 2],
+            sim.state().executed_event_values,
+            "rescheduled event did not execute at its new time"
+        );
+    }
+
+    #[test]
+    fn priorities_break_ties_before_insertion_order() {
+        let mut sim = setup();
+        sim.schedule_with_priority(TestEvent { value: 5 }, 4, -1).unwrap();
+        let handle = sim.schedule_with_priority(TestEvent { value: 6 }, 4, 1).unwrap();
+        sim.schedule_with_priority(TestEvent { value: 7 }, 4, 1).unwrap();
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.schedule_with_priority(TestEvent { value: 8 }, -1, 1),
+            "priority should not allow scheduling into the past"
+        );
+
+        sim.reschedule(handle, 2)
+            .expect("pending event should be reschedulable");
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 6, 3, 7, 2, 5],
+            sim.state().executed_event_values,
+            "events did not execute in priority order"
+        );
+    }
+
+    #[test]
+    fn reschedule_rejects_invalid_requests() {
+        let mut sim = setup();
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+        let mut next_event = sim.next_event().expect("should be able to pop scheduled event");
+        next_event.execute(&mut sim).expect("event should execute normally");
+
+        assert_eq!(
+            Err(crate::Error::EventNotFound),
+            sim.reschedule(EventHandle::new(0), 5),
+            "executed event should not be reschedulable"
+        );
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.reschedule_with_delay(EventHandle::new(2), -1),
+            "event should not be reschedulable into the past"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            vec![1, 3, 2],
+            sim.state().executed_event_values,
+            "rejected reschedule should leave event in place"
+        );
+        assert_eq!(
+            4,
+            *sim.current_time(),
+            "event should have executed at its original time"
+        );
+    }
+
+    #[test]
+    fn step_reports_progress() {
+        let mut sim = setup();
+        assert_eq!(
+            Ok(StepOutcome::Executed(EventHandle::new(0))),
+            sim.step(),
+            "step should execute first event"
+        );
+        assert_eq!(
+            vec![1],
+            sim.state().executed_event_values,
+            "only one event should execute"
+        );
+
+        sim.schedule(CompletionEvent {}, 2).unwrap();
+        assert_eq!(Ok(StepOutcome::Executed(EventHandle::new(1))), sim.step());
+        assert_eq!(Ok(StepOutcome::Executed(EventHandle::new(3))), sim.step());
+        assert_eq!(Ok(StepOutcome::Complete), sim.step(), "step should detect completion");
+
+        let mut sim = Simulation::new(
+            State {
+                executed_event_values: Vec::new(),
+                complete: false,
+            },
+            0,
+        );
+        assert_eq!(
+            Ok(StepOutcome::QueueEmpty),
+            sim.step(),
+            "step should detect empty queue"
+        );
+    }
+
+    #[test]
+    fn run_for_stops_after_event_count() {
+        let mut sim = setup();
+        assert_eq!(Ok(2), sim.run_for(2), "should execute requested number of events");
+        assert_eq!(vec![1, 3], sim.state().executed_event_values);
+        assert_eq!(2, *sim.current_time(), "clock should match last executed event");
+
+        assert_eq!(Ok(1), sim.run_for(5), "should stop early when queue empties");
+        assert_eq!(vec![1, 3, 2], sim.state().executed_event_values);
+    }
+
+    #[test]
+    fn run_until_stops_at_horizon() {
+        let mut sim = setup();
+        sim.run_until(&2).expect("simulation should run to horizon");
+        assert_eq!(
+            vec![1, 3],
+            sim.state().executed_event_values,
+            "events at or before horizon should execute"
+        );
+        assert_eq!(2, *sim.current_time(), "clock should be at horizon");
+
+        sim.run_until(&3).expect("simulation should run to horizon");
+        assert_eq!(vec![1, 3], sim.state().executed_event_values, "no events lie in (2, 3]");
+        assert_eq!(3, *sim.current_time(), "clock should advance to horizon without events");
+
+        assert_eq!(
+            Err(crate::Error::BackInTime),
+            sim.run_until(&1),
+            "horizon in the past should be rejected"
+        );
+
+        sim.schedule(CompletionEvent {}, 3).unwrap();
+        sim.run_until(&10).expect("simulation should run until complete");
+        assert_eq!(
+            vec![1, 3],
+            sim.state().executed_event_values,
+            "completion should stop run"
+        );
+        assert_eq!(3, *sim.current_time(), "clock should stay at completion time");
+    }
+
+    #[test]
+    fn pending_events_can_be_inspected() {
+        let mut sim = setup();
+        let handle = sim.schedule(TestEvent { value: 4 }, 2).unwrap();
+        sim.cancel(EventHandle::new(0));
+
+        assert_eq!(Some(2), sim.peek_next_time(), "next time should skip cancelled event");
+        assert_eq!(3, sim.pending_event_count(), "cancelled event should not be counted");
+
+        let pending: Vec<_> = sim
+            .pending_events()
+            .iter()
+            .map(|pending| (*pending.execution_time(), pending.handle()))
+            .collect();
+        assert_eq!(
+            vec![(2, EventHandle::new(1)), (2, handle), (4, EventHandle::new(2))],
+            pending,
+            "pending events should be listed in execution order"
+        );
+
+        let first_pending = format!("{:?}", sim.pending_events().iter().next().unwrap());
+        assert_eq!(
+            "ScheduledEvent { event: TestEvent { value: 3 }, execution_time: 2, priority: 0, insertion_sequence: 1 }",
+            first_pending,
+            "pending event should print its contents"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(None, sim.peek_next_time(), "queue should be empty after run");
+        assert_eq!(0, sim.pending_event_count(), "queue should be empty after run");
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingObserver {
+        records: Vec<String>,
+    }
+
+    impl Observer<i32> for RecordingObserver {
+        fn before_event(&mut self, event: &ObservedEvent<'_, i32>) {
+            self.records.push(format!(
+                "before {:?} at {} ({})",
+                event.event(),
+                event.execution_time(),
+                event.handle().insertion_sequence()
+            ));
+        }
+
+        fn after_event(&mut self, event: &ObservedEvent<'_, i32>, result: &crate::Result) {
+            self.records.push(format!(
+                "after {:?} at {}: {:?}",
+                event.event(),
+                event.execution_time(),
+                result
+            ));
+        }
+    }
+
+    #[test]
+    fn observers_are_notified_around_each_event() {
+        let mut sim = setup();
+        let handle = sim.add_observer(RecordingObserver::default());
+        let unused_handle = sim.add_observer(RecordingObserver::default());
+        sim.run_for(2).unwrap();
+
+        assert_eq!(
+            vec![
+                "before TestEvent { value: 1 } at 0 (0)",
+                "after TestEvent { value: 1 } at 0: Ok(())",
+                "before TestEvent { value: 3 } at 2 (1)",
+                "after TestEvent { value: 3 } at 2: Ok(())",
+            ],
+            sim.observer(&handle).expect("observer should be attached").records,
+            "observer should record events in execution order"
+        );
+
+        sim.observer_mut(&handle).unwrap().records.clear();
+        let removed = sim
+            .remove_observer(unused_handle)
+            .expect("observer should be removable");
+        assert_eq!(4, removed.records.len(), "removed observer should keep its records");
+        assert!(
+            sim.observer(&unused_handle).is_none(),
+            "removed observer should be gone"
+        );
+        assert!(
+            sim.remove_observer(unused_handle).is_none(),
+            "observer should not be removed twice"
+        );
+
+        sim.run().unwrap();
+        assert_eq!(
+            2,
+            sim.observer(&handle).unwrap().records.len(),
+            "remaining observer should still be notified"
+        );
+    }
 }
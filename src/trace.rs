@@ -0,0 +1,30 @@
+//! For recording the events a simulation executes and checking later runs against those recordings.
+//!
+//! The types in this module are [`Observer`]s, and so may be attached to either a [`serial::Simulation`] or a
+//! [`threadsafe::Simulation`] through its `add_observer()` method. A [`TraceRecorder`] writes one line of JSON for each
+//! executed event, describing its execution time, insertion sequence, name, and [`Debug`] representation. As the format
+//! has one event per line with a fixed order of fields, two traces of the same model run with the same seed can be
+//! compared with any line-oriented diff tool.
+//!
+//! A recorded trace may also be read back with [`read_trace()`] and handed to a [`TraceReplay`], which checks a new run
+//! of the simulation against it event by event. The replay reports the first point at which the two runs diverge, which
+//! is helpful for demonstrating that a refactor of event code has preserved the model's behavior.
+//!
+//! For visual inspection of a run, a [`ChromeTraceExporter`] writes the Chrome Trace Event format with simulated time
+//! as the timestamp axis, which Perfetto and `chrome://tracing` can display. Events may annotate this timeline with
+//! spans, markers, and counters of their own.
+//!
+//! [`Observer`]: crate::Observer
+//! [`serial::Simulation`]: crate::serial::Simulation
+//! [`threadsafe::Simulation`]: crate::threadsafe::Simulation
+//! [`Debug`]: std::fmt::Debug
+
+mod chrome;
+mod record;
+mod recorder;
+mod replay;
+
+pub use chrome::ChromeTraceExporter;
+pub use record::{read_trace, TraceRecord};
+pub use recorder::TraceRecorder;
+pub use replay::{TraceDivergence, TraceReplay};
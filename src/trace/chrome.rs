@@ -0,0 +1,365 @@
+use super::record::push_json_string;
+use crate::{NumericTime, ObservedEvent, Observer};
+use std::fmt::{Formatter, Write as _};
+use std::io::Write;
+
+/// The track on which markers for executed events are placed.
+const EVENT_TRACK: u64 = 0;
+
+/// An [`Observer`] that writes a simulation's timeline in the Chrome Trace Event format, for viewing in Perfetto or
+/// `chrome://tracing`.
+///
+/// Simulated time serves as the timestamp axis. These viewers interpret timestamps as microseconds, so each unit of
+/// simulated time is drawn as one microsecond unless a different ratio is provided to [`set_time_scale()`].
+///
+/// By default, the exporter places an instant marker on track 0 for every executed event, named by the event's `name()`
+/// method and carrying its [`Debug`] representation. Events may also annotate the timeline themselves by looking up the
+/// exporter with the simulation's `observer_mut()` method, e.g. to open and close a span while an entity is in service:
+///
+/// * [`begin_span()`] and [`end_span()`] open and close a span on the given track at the current simulation time.
+/// * [`span()`] records a complete span with explicit start and end times, such as a service whose completion time is
+///   already known when it begins.
+/// * [`instant()`] places a marker on the given track at the current simulation time.
+/// * [`counter()`] records the value of a named quantity, such as a queue length, at the current simulation time.
+///
+/// Tracks are identified by number, typically an entity's ID, and may be labelled with [`name_track()`]. The current
+/// simulation time used by these methods is the execution time of the event most recently passed to this observer.
+///
+/// Records are streamed to the underlying writer as they occur, forming a JSON array. As with a [`TraceRecorder`], the
+/// first error encountered while writing is stored rather than reported; call [`finish()`] to close the array, flush
+/// the writer, and retrieve any such error.
+///
+/// ```
+/// # use desque::serial::{OkEvent, Simulation};
+/// # use desque::trace::ChromeTraceExporter;
+/// # use desque::{ObserverHandle, SimState};
+/// #[derive(Debug)]
+/// struct State {
+///     exporter: Option<ObserverHandle<ChromeTraceExporter<Vec<u8>>>>,
+/// }
+/// # impl SimState<u32> for State {}
+///
+/// #[derive(Debug)]
+/// struct StartService {
+///     customer: u64,
+/// }
+///
+/// impl OkEvent<State, u32> for StartService {
+///     fn execute(&mut self, sim: &mut Simulation<State, u32>) {
+///         if let Some(exporter) = sim.state().exporter.and_then(|handle| sim.observer_mut(&handle)) {
+///             exporter.begin_span(self.customer, "service");
+///         }
+///         sim.schedule_with_delay(EndService { customer: self.customer }, 5).unwrap();
+///     }
+/// }
+///
+/// #[derive(Debug)]
+/// struct EndService {
+///     customer: u64,
+/// }
+///
+/// impl OkEvent<State, u32> for EndService {
+///     fn execute(&mut self, sim: &mut Simulation<State, u32>) {
+///         if let Some(exporter) = sim.state().exporter.and_then(|handle| sim.observer_mut(&handle)) {
+///             exporter.end_span(self.customer, "service");
+///         }
+///     }
+/// }
+///
+/// let mut sim = Simulation::new(State { exporter: None }, 0);
+/// let exporter = sim.add_observer(ChromeTraceExporter::in_memory());
+/// sim.state_mut().exporter = Some(exporter);
+/// sim.schedule(StartService { customer: 1 }, 2)?;
+/// sim.run()?;
+///
+/// let json = sim.remove_observer(exporter).unwrap().finish()?;
+/// let json = String::from_utf8(json)?;
+/// assert!(json.contains(r#""ph":"B","ts":2,"pid":1,"tid":1"#));
+/// assert!(json.contains(r#""ph":"E","ts":7,"pid":1,"tid":1"#));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`TraceRecorder`]: super::TraceRecorder
+/// [`set_time_scale()`]: ChromeTraceExporter::set_time_scale
+/// [`begin_span()`]: ChromeTraceExporter::begin_span
+/// [`end_span()`]: ChromeTraceExporter::end_span
+/// [`span()`]: ChromeTraceExporter::span
+/// [`instant()`]: ChromeTraceExporter::instant
+/// [`counter()`]: ChromeTraceExporter::counter
+/// [`name_track()`]: ChromeTraceExporter::name_track
+/// [`finish()`]: ChromeTraceExporter::finish
+pub struct ChromeTraceExporter<Writer>
+where
+    Writer: Write,
+{
+    writer: Writer,
+    time_scale: f64,
+    current_timestamp: f64,
+    mark_events: bool,
+    event_track_named: bool,
+    records_written: usize,
+    error: Option<std::io::Error>,
+}
+
+impl<Writer> ChromeTraceExporter<Writer>
+where
+    Writer: Write,
+{
+    /// Construct an exporter that writes its trace to the provided writer.
+    pub fn new(writer: Writer) -> Self {
+        Self {
+            writer,
+            time_scale: 1.0,
+            current_timestamp: 0.0,
+            mark_events: true,
+            event_track_named: false,
+            records_written: 0,
+            error: None,
+        }
+    }
+
+    /// Set the number of microseconds on the exported timeline that correspond to one unit of simulated time. For
+    /// example, a simulation whose clock counts minutes might use a scale of `60_000_000.0` so that viewers label the
+    /// timeline in real units.
+    ///
+    /// Changing the scale part of the way through a run will distort the timeline.
+    pub fn set_time_scale(&mut self, micros_per_time_unit: f64) {
+        self.time_scale = micros_per_time_unit;
+    }
+
+    /// Set whether to place a marker on track 0 for every executed event. Enabled by default.
+    pub fn set_event_markers(&mut self, enabled: bool) {
+        self.mark_events = enabled;
+    }
+
+    /// Label a track in the viewer. Tracks are created as needed, so naming them is optional.
+    pub fn name_track(&mut self, track: u64, name: &str) {
+        self.event_track_named |= track == EVENT_TRACK;
+        let mut record = String::from("{\"name\":\"thread_name\",\"ph\":\"M\"");
+        push_ids(&mut record, track);
+        record.push_str(",\"args\":{\"name\":");
+        push_json_string(&mut record, name);
+        record.push_str("}}");
+        self.write_record(&record);
+    }
+
+    /// Open a span on the given track at the current simulation time. Spans on the same track must nest, each one
+    /// closed by a call to [`end_span()`] before any span that encloses it.
+    ///
+    /// [`end_span()`]: ChromeTraceExporter::end_span
+    pub fn begin_span(&mut self, track: u64, name: &str) {
+        let record = self.timed_record(name, "B", self.current_timestamp, track);
+        self.write_record(&(record + "}"));
+    }
+
+    /// Close the innermost open span on the given track at the current simulation time.
+    pub fn end_span(&mut self, track: u64, name: &str) {
+        let record = self.timed_record(name, "E", self.current_timestamp, track);
+        self.write_record(&(record + "}"));
+    }
+
+    /// Record a span on the given track that runs from `start` until `end`. Either time may differ from the current
+    /// simulation time.
+    pub fn span<Time>(&mut self, track: u64, name: &str, start: &Time, end: &Time)
+    where
+        Time: NumericTime,
+    {
+        let start = start.to_f64() * self.time_scale;
+        let duration = end.to_f64() * self.time_scale - start;
+        let mut record = self.timed_record(name, "X", start, track);
+        record.push_str(",\"dur\":");
+        push_number(&mut record, duration);
+        record.push('}');
+        self.write_record(&record);
+    }
+
+    /// Place a marker on the given track at the current simulation time.
+    pub fn instant(&mut self, track: u64, name: &str) {
+        let record = self.timed_record(name, "i", self.current_timestamp, track);
+        self.write_record(&(record + ",\"s\":\"t\"}"));
+    }
+
+    /// Record the value of a named quantity at the current simulation time. Viewers draw each name as a separate chart
+    /// that holds its value until the next update.
+    pub fn counter(&mut self, name: &str, value: f64) {
+        let mut record = self.timed_record(name, "C", self.current_timestamp, EVENT_TRACK);
+        record.push_str(",\"args\":{\"value\":");
+        push_number(&mut record, value);
+        record.push_str("}}");
+        self.write_record(&record);
+    }
+
+    /// Get the number of records successfully written so far.
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Get a shared reference to the first error encountered while writing, if any.
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Get a shared reference to the underlying writer.
+    pub fn get_ref(&self) -> &Writer {
+        &self.writer
+    }
+
+    /// Close the JSON array, flush the underlying writer, and hand it back to the caller.
+    ///
+    /// # Errors
+    ///
+    /// If an error was encountered while writing records, closing the array, or flushing the writer, it is returned
+    /// instead of the writer.
+    pub fn finish(mut self) -> std::io::Result<Writer> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        let closing = if self.records_written == 0 { "[]\n" } else { "\n]\n" };
+        self.writer.write_all(closing.as_bytes())?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+
+    /// Start a record with the fields common to every timed entry, leaving the object open for further fields.
+    fn timed_record(&self, name: &str, phase: &str, timestamp: f64, track: u64) -> String {
+        let mut record = String::from("{\"name\":");
+        push_json_string(&mut record, name);
+        // writing to a String cannot fail
+        let _ = write!(record, ",\"ph\":\"{}\",\"ts\":", phase);
+        push_number(&mut record, timestamp);
+        push_ids(&mut record, track);
+        record
+    }
+
+    fn write_record(&mut self, record: &str) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let separator = if self.records_written == 0 { "[\n" } else { ",\n" };
+        let result = self
+            .writer
+            .write_all(separator.as_bytes())
+            .and_then(|_| self.writer.write_all(record.as_bytes()));
+        match result {
+            Ok(()) => self.records_written += 1,
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+impl ChromeTraceExporter<Vec<u8>> {
+    /// Construct an exporter that collects its trace in memory.
+    pub fn in_memory() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<Time, Writer> Observer<Time> for ChromeTraceExporter<Writer>
+where
+    Time: NumericTime,
+    Writer: Write,
+{
+    fn before_event(&mut self, event: &ObservedEvent<'_, Time>) {
+        self.current_timestamp = event.execution_time().to_f64() * self.time_scale;
+        if !self.mark_events {
+            return;
+        }
+
+        if !self.event_track_named {
+            self.name_track(EVENT_TRACK, "events");
+        }
+
+        let mut record = self.timed_record(event.name(), "i", self.current_timestamp, EVENT_TRACK);
+        // writing to a String cannot fail
+        let _ = write!(
+            record,
+            ",\"s\":\"t\",\"args\":{{\"sequence\":{},\"event\":",
+            event.handle().insertion_sequence()
+        );
+        push_json_string(&mut record, &format!("{:?}", event.event()));
+        record.push_str("}}");
+        self.write_record(&record);
+    }
+}
+
+impl<Writer> std::fmt::Debug for ChromeTraceExporter<Writer>
+where
+    Writer: Write,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("ChromeTraceExporter")
+            .field("time_scale", &self.time_scale)
+            .field("current_timestamp", &self.current_timestamp)
+            .field("mark_events", &self.mark_events)
+            .field("records_written", &self.records_written)
+            .field("error", &self.error)
+            .finish()
+    }
+}
+
+/// Append the process and thread IDs that place a record on the given track.
+fn push_ids(record: &mut String, track: u64) {
+    // writing to a String cannot fail
+    let _ = write!(record, ",\"pid\":1,\"tid\":{}", track);
+}
+
+/// Append a number to a record, substituting zero for values that JSON cannot represent.
+fn push_number(record: &mut String, value: f64) {
+    let value = if value.is_finite() { value } else { 0.0 };
+    // writing to a String cannot fail
+    let _ = write!(record, "{}", value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::EventHandle;
+
+    fn finish_to_string(exporter: ChromeTraceExporter<Vec<u8>>) -> String {
+        let json = exporter.finish().expect("in-memory trace should not fail");
+        String::from_utf8(json).expect("trace should be valid UTF-8")
+    }
+
+    #[test]
+    fn empty_trace_is_empty_array() {
+        let exporter = ChromeTraceExporter::in_memory();
+        assert_eq!(
+            "[]\n",
+            finish_to_string(exporter),
+            "trace should be an empty JSON array"
+        );
+    }
+
+    #[test]
+    fn records_are_placed_on_scaled_timeline() {
+        let mut exporter = ChromeTraceExporter::in_memory();
+        exporter.set_time_scale(1000.0);
+        exporter.name_track(3, "server \"A\"");
+
+        let event = ObservedEvent::new(&"a", "A", &2_u32, EventHandle::new(4));
+        exporter.before_event(&event);
+        exporter.begin_span(3, "service");
+        exporter.counter("queue", 1.5);
+        exporter.span(3, "setup", &2_u32, &5_u32);
+        exporter.instant(3, "alarm");
+
+        let expected = [
+            r#"{"name":"thread_name","ph":"M","pid":1,"tid":3,"args":{"name":"server \"A\""}}"#,
+            r#"{"name":"thread_name","ph":"M","pid":1,"tid":0,"args":{"name":"events"}}"#,
+            r#"{"name":"A","ph":"i","ts":2000,"pid":1,"tid":0,"s":"t","args":{"sequence":4,"event":"\"a\""}}"#,
+            r#"{"name":"service","ph":"B","ts":2000,"pid":1,"tid":3}"#,
+            r#"{"name":"queue","ph":"C","ts":2000,"pid":1,"tid":0,"args":{"value":1.5}}"#,
+            r#"{"name":"setup","ph":"X","ts":2000,"pid":1,"tid":3,"dur":3000}"#,
+            r#"{"name":"alarm","ph":"i","ts":2000,"pid":1,"tid":3,"s":"t"}"#,
+        ];
+        assert_eq!(expected.len(), exporter.records_written());
+        assert_eq!(
+            format!("[\n{}\n]\n", expected.join(",\n")),
+            finish_to_string(exporter),
+            "trace should hold each record in order"
+        );
+    }
+}
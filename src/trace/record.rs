@@ -0,0 +1,340 @@
+use crate::{ObservedEvent, SimTime};
+use std::fmt::Write;
+use std::io::BufRead;
+
+/// A description of one executed event, as stored in a trace.
+///
+/// Each record captures the [`Debug`] representation of the event's execution time, the insertion sequence of its
+/// [`EventHandle`], the name reported by the event's `name()` method, and the [`Debug`] representation of the event
+/// itself. Storing the time and event as text allows a record to describe any simulation regardless of the types it
+/// uses, at the cost of comparing those fields as text when replaying a trace.
+///
+/// Records are written to and read from traces as JSON objects occupying a single line each, with fields always in the
+/// order `time`, `sequence`, `name`, `event`:
+///
+/// ```text
+/// {"time":"5","sequence":3,"name":"my_model::ArrivalEvent","event":"ArrivalEvent { customer: 12 }"}
+/// ```
+///
+/// [`Debug`]: std::fmt::Debug
+/// [`EventHandle`]: crate::EventHandle
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceRecord {
+    /// The [`Debug`] representation of the event's execution time.
+    ///
+    /// [`Debug`]: std::fmt::Debug
+    pub execution_time: String,
+    /// The insertion sequence of the handle that the event was scheduled with.
+    pub insertion_sequence: usize,
+    /// The name reported by the event.
+    pub name: String,
+    /// The [`Debug`] representation of the event.
+    ///
+    /// [`Debug`]: std::fmt::Debug
+    pub event: String,
+}
+
+impl TraceRecord {
+    /// Format this record as a single line of JSON, without a trailing newline.
+    pub fn to_json_line(&self) -> String {
+        let mut line = String::with_capacity(48 + self.name.len() + self.event.len());
+        line.push_str("{\"time\":");
+        push_json_string(&mut line, &self.execution_time);
+        // writing to a String cannot fail
+        let _ = write!(line, ",\"sequence\":{}", self.insertion_sequence);
+        line.push_str(",\"name\":");
+        push_json_string(&mut line, &self.name);
+        line.push_str(",\"event\":");
+        push_json_string(&mut line, &self.event);
+        line.push('}');
+        line
+    }
+
+    /// Parse a single line of JSON, as produced by [`to_json_line()`], back into a record. Returns `None` if the line
+    /// is not a JSON object holding exactly the four expected fields.
+    ///
+    /// Fields may appear in any order and may be separated by whitespace, but no other fields are permitted.
+    ///
+    /// [`to_json_line()`]: TraceRecord::to_json_line
+    pub fn from_json_line(line: &str) -> Option<Self> {
+        let mut parser = Parser::new(line);
+        let mut execution_time = None;
+        let mut insertion_sequence = None;
+        let mut name = None;
+        let mut event = None;
+
+        parser.expect('{')?;
+        loop {
+            let key = parser.string()?;
+            parser.expect(':')?;
+            let duplicate = match key.as_str() {
+                "time" => execution_time.replace(parser.string()?).is_some(),
+                "sequence" => insertion_sequence.replace(parser.integer()?).is_some(),
+                "name" => name.replace(parser.string()?).is_some(),
+                "event" => event.replace(parser.string()?).is_some(),
+                _ => return None,
+            };
+            if duplicate {
+                return None;
+            }
+
+            match parser.next_token()? {
+                ',' => continue,
+                '}' => break,
+                _ => return None,
+            }
+        }
+        parser.end()?;
+
+        Some(Self {
+            execution_time: execution_time?,
+            insertion_sequence: insertion_sequence?,
+            name: name?,
+            event: event?,
+        })
+    }
+}
+
+impl<Time> From<&ObservedEvent<'_, Time>> for TraceRecord
+where
+    Time: SimTime,
+{
+    fn from(event: &ObservedEvent<'_, Time>) -> Self {
+        Self {
+            execution_time: format!("{:?}", event.execution_time()),
+            insertion_sequence: event.handle().insertion_sequence(),
+            name: event.name().to_owned(),
+            event: format!("{:?}", event.event()),
+        }
+    }
+}
+
+/// Read a trace written by a [`TraceRecorder`], one [`TraceRecord`] per line. Blank lines are skipped.
+///
+/// # Errors
+///
+/// Any error encountered while reading from `reader` is passed back to the caller. A line that cannot be parsed as a
+/// [`TraceRecord`] results in an error of kind [`InvalidData`] that reports the offending line number.
+///
+/// [`TraceRecorder`]: super::TraceRecorder
+/// [`InvalidData`]: std::io::ErrorKind::InvalidData
+pub fn read_trace<Reader>(reader: Reader) -> std::io::Result<Vec<TraceRecord>>
+where
+    Reader: BufRead,
+{
+    let mut records = Vec::new();
+    for (index, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let record = TraceRecord::from_json_line(&line).ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("line {} is not a valid trace record", index + 1),
+            )
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Append `value` to `line` as a quoted JSON string, escaping characters as necessary.
+pub(super) fn push_json_string(line: &mut String, value: &str) {
+    line.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => line.push_str("\\\""),
+            '\\' => line.push_str("\\\\"),
+            '\n' => line.push_str("\\n"),
+            '\r' => line.push_str("\\r"),
+            '\t' => line.push_str("\\t"),
+            c if c.is_control() => {
+                // writing to a String cannot fail
+                let _ = write!(line, "\\u{:04x}", c as u32);
+            },
+            c => line.push(c),
+        }
+    }
+    line.push('"');
+}
+
+/// Minimal reader for the flat JSON objects that make up a trace.
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(line: &'a str) -> Self {
+        Self {
+            chars: line.chars().peekable(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    /// Consume the next non-whitespace character.
+    fn next_token(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.chars.next()
+    }
+
+    fn expect(&mut self, expected: char) -> Option<()> {
+        (self.next_token()? == expected).then_some(())
+    }
+
+    /// Succeed only if nothing but whitespace remains.
+    fn end(&mut self) -> Option<()> {
+        self.skip_whitespace();
+        self.chars.peek().is_none().then_some(())
+    }
+
+    fn integer(&mut self) -> Option<usize> {
+        self.skip_whitespace();
+        let mut digits = String::new();
+        while let Some(digit) = self.chars.next_if(char::is_ascii_digit) {
+            digits.push(digit);
+        }
+        digits.parse().ok()
+    }
+
+    fn string(&mut self) -> Option<String> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            match self.chars.next()? {
+                '"' => return Some(value),
+                '\\' => value.push(self.escape()?),
+                c => value.push(c),
+            }
+        }
+    }
+
+    /// Decode the remainder of an escape sequence, the backslash having already been consumed.
+    fn escape(&mut self) -> Option<char> {
+        let c = match self.chars.next()? {
+            '"' => '"',
+            '\\' => '\\',
+            '/' => '/',
+            'b' => '\u{8}',
+            'f' => '\u{c}',
+            'n' => '\n',
+            'r' => '\r',
+            't' => '\t',
+            'u' => {
+                let high = self.code_unit()?;
+                if (0xd800..0xdc00).contains(&high) {
+                    // surrogate pair, which must be followed by its low half
+                    self.chars.next().filter(|c| *c == '\\')?;
+                    self.chars.next().filter(|c| *c == 'u')?;
+                    let low = self.code_unit()?;
+                    if !(0xdc00..0xe000).contains(&low) {
+                        return None;
+                    }
+                    char::from_u32(0x10000 + ((high - 0xd800) << 10) + (low - 0xdc00))?
+                } else {
+                    char::from_u32(high)?
+                }
+            },
+            _ => return None,
+        };
+        Some(c)
+    }
+
+    /// Read the four hexadecimal digits of a `\u` escape.
+    fn code_unit(&mut self) -> Option<u32> {
+        let mut value = 0;
+        for _ in 0..4 {
+            value = value * 16 + self.chars.next()?.to_digit(16)?;
+        }
+        Some(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record() -> TraceRecord {
+        TraceRecord {
+            execution_time: "F64Time(1.5)".into(),
+            insertion_sequence: 42,
+            name: "model::Event".into(),
+            event: "Event { label: \"a \\ b\", note: \"line\nbreak\u{1}\" }".into(),
+        }
+    }
+
+    #[test]
+    fn records_survive_round_trip() {
+        let record = record();
+        let line = record.to_json_line();
+        assert!(!line.contains('\n'), "record should occupy a single line");
+        assert_eq!(
+            Some(record),
+            TraceRecord::from_json_line(&line),
+            "record should parse back to the original"
+        );
+    }
+
+    #[test]
+    fn records_use_stable_format() {
+        let record = TraceRecord {
+            execution_time: "3".into(),
+            insertion_sequence: 7,
+            name: "Arrival".into(),
+            event: "Arrival { id: \"x\" }".into(),
+        };
+        assert_eq!(
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"Arrival { id: \"x\" }"}"#,
+            record.to_json_line(),
+            "record should be written with fields in fixed order"
+        );
+    }
+
+    #[test]
+    fn parsing_tolerates_whitespace_and_field_order() {
+        let line = r#" { "sequence" : 7, "event": "A\ud83d\ude00\n", "name":"Arrival","time":"3" } "#;
+        let record = TraceRecord::from_json_line(line).expect("line should parse");
+        assert_eq!(7, record.insertion_sequence);
+        assert_eq!("A\u{1f600}\n", record.event, "escapes should be decoded");
+    }
+
+    #[test]
+    fn parsing_rejects_malformed_lines() {
+        for line in [
+            "",
+            "{}",
+            r#"{"time":"3","sequence":7,"name":"Arrival"}"#,
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"e","extra":"x"}"#,
+            r#"{"time":"3","sequence":-7,"name":"Arrival","event":"e"}"#,
+            r#"{"time":"3","time":"3","sequence":7,"name":"Arrival","event":"e"}"#,
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"e"} trailing"#,
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"\q"}"#,
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"\ud83d"}"#,
+            r#"{"time":"3","sequence":7,"name":"Arrival","event":"\ud83dA"}"#,
+        ] {
+            assert_eq!(
+                None,
+                TraceRecord::from_json_line(line),
+                "line should be rejected: {}",
+                line
+            );
+        }
+    }
+
+    #[test]
+    fn read_trace_reports_bad_lines() {
+        let good = record().to_json_line();
+        let trace = format!("{}\n\n{}\n", good, good);
+        let records = read_trace(trace.as_bytes()).expect("trace should be readable");
+        assert_eq!(vec![record(), record()], records, "blank lines should be skipped");
+
+        let trace = format!("{}\nnot json\n", good);
+        let error = read_trace(trace.as_bytes()).expect_err("bad line should be rejected");
+        assert_eq!(std::io::ErrorKind::InvalidData, error.kind());
+        assert!(error.to_string().contains("line 2"), "error should report line number");
+    }
+}
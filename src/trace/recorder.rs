@@ -0,0 +1,140 @@
+use super::TraceRecord;
+use crate::{ObservedEvent, Observer, SimTime};
+use std::fmt::Formatter;
+use std::io::Write;
+
+/// An [`Observer`] that logs every executed event as a [`TraceRecord`].
+///
+/// Each record is written to the underlying writer as a single line of JSON before the event executes, so that a
+/// trace will include the event that caused a run to fail. Pass a [`File`] or other writer to [`new()`] for a
+/// file-backed log, or use [`in_memory()`] to collect the trace into a buffer that can be inspected after the run.
+///
+/// Writers are used as given, so wrapping a [`File`] in a [`BufWriter`] is advisable for long runs.
+///
+/// Because the [`Observer`] hooks cannot fail, the first error encountered while writing is stored rather than
+/// reported, and no further records are written once an error has occurred. Call [`finish()`] once the simulation
+/// completes to flush the writer and retrieve any such error.
+///
+/// ```
+/// # use desque::serial::{OkEvent, Simulation};
+/// # use desque::trace::{read_trace, TraceRecorder};
+/// # use desque::SimState;
+/// # #[derive(Debug)]
+/// # struct State;
+/// # impl SimState<u32> for State {
+/// #     fn is_complete(&self, _: &u32) -> bool {
+/// #         false
+/// #     }
+/// # }
+/// # #[derive(Debug)]
+/// # struct Arrival;
+/// # impl OkEvent<State, u32> for Arrival {
+/// #     fn execute(&mut self, _: &mut Simulation<State, u32>) {}
+/// # }
+/// let mut sim = Simulation::new(State, 0);
+/// sim.schedule(Arrival, 3)?;
+///
+/// let recorder = sim.add_observer(TraceRecorder::in_memory());
+/// sim.run()?;
+///
+/// let log = sim.remove_observer(recorder).unwrap().finish()?;
+/// let records = read_trace(log.as_slice())?;
+/// assert_eq!(1, records.len());
+/// assert_eq!("3", records[0].execution_time);
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`File`]: std::fs::File
+/// [`BufWriter`]: std::io::BufWriter
+/// [`new()`]: TraceRecorder::new
+/// [`in_memory()`]: TraceRecorder::in_memory
+/// [`finish()`]: TraceRecorder::finish
+pub struct TraceRecorder<Writer>
+where
+    Writer: Write,
+{
+    writer: Writer,
+    records_written: usize,
+    error: Option<std::io::Error>,
+}
+
+impl<Writer> TraceRecorder<Writer>
+where
+    Writer: Write,
+{
+    /// Construct a recorder that writes its trace to the provided writer.
+    pub fn new(writer: Writer) -> Self {
+        Self {
+            writer,
+            records_written: 0,
+            error: None,
+        }
+    }
+
+    /// Get the number of records successfully written so far.
+    pub fn records_written(&self) -> usize {
+        self.records_written
+    }
+
+    /// Get a shared reference to the first error encountered while writing, if any.
+    pub fn error(&self) -> Option<&std::io::Error> {
+        self.error.as_ref()
+    }
+
+    /// Get a shared reference to the underlying writer.
+    pub fn get_ref(&self) -> &Writer {
+        &self.writer
+    }
+
+    /// Flush the underlying writer and hand it back to the caller.
+    ///
+    /// # Errors
+    ///
+    /// If an error was encountered while writing records or while flushing the writer, it is returned instead of the
+    /// writer.
+    pub fn finish(mut self) -> std::io::Result<Writer> {
+        if let Some(error) = self.error.take() {
+            return Err(error);
+        }
+
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+impl TraceRecorder<Vec<u8>> {
+    /// Construct a recorder that collects its trace in memory.
+    pub fn in_memory() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<Time, Writer> Observer<Time> for TraceRecorder<Writer>
+where
+    Time: SimTime,
+    Writer: Write,
+{
+    fn before_event(&mut self, event: &ObservedEvent<'_, Time>) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let line = TraceRecord::from(event).to_json_line();
+        match writeln!(self.writer, "{}", line) {
+            Ok(()) => self.records_written += 1,
+            Err(error) => self.error = Some(error),
+        }
+    }
+}
+
+impl<Writer> std::fmt::Debug for TraceRecorder<Writer>
+where
+    Writer: Write,
+{
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        f.debug_struct("TraceRecorder")
+            .field("records_written", &self.records_written)
+            .field("error", &self.error)
+            .finish()
+    }
+}
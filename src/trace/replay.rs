@@ -0,0 +1,171 @@
+use super::TraceRecord;
+use crate::{ObservedEvent, Observer, SimTime};
+
+/// An [`Observer`] that checks a run against a previously recorded trace.
+///
+/// As each event executes, the replay compares it to the next expected [`TraceRecord`], remembering the first point at
+/// which the two runs disagree. Running a simulation under a [`TraceRecorder`], then again under a [`TraceReplay`]
+/// built from the recorded trace, thereby demonstrates whether the simulation behaves deterministically, e.g. after a
+/// refactor of its events or when reusing a seed for its random number generators.
+///
+/// The replay only observes the simulation; it does not stop the run upon finding a divergence. Check
+/// [`first_divergence()`] once the run completes.
+///
+/// ```
+/// # use desque::serial::{OkEvent, Simulation};
+/// # use desque::trace::{TraceRecorder, TraceReplay};
+/// # use desque::SimState;
+/// # #[derive(Debug)]
+/// # struct State;
+/// # impl SimState<u32> for State {
+/// #     fn is_complete(&self, _: &u32) -> bool {
+/// #         false
+/// #     }
+/// # }
+/// # #[derive(Debug)]
+/// # struct Arrival;
+/// # impl OkEvent<State, u32> for Arrival {
+/// #     fn execute(&mut self, _: &mut Simulation<State, u32>) {}
+/// # }
+/// # fn build_simulation() -> Simulation<State, u32> {
+/// #     let mut sim = Simulation::new(State, 0);
+/// #     sim.schedule(Arrival, 3).unwrap();
+/// #     sim
+/// # }
+/// let mut first = build_simulation();
+/// let recorder = first.add_observer(TraceRecorder::in_memory());
+/// first.run()?;
+/// let log = first.remove_observer(recorder).unwrap().finish()?;
+///
+/// let mut second = build_simulation();
+/// let replay = second.add_observer(TraceReplay::from_reader(log.as_slice())?);
+/// second.run()?;
+///
+/// let replay = second.remove_observer(replay).unwrap();
+/// assert!(replay.is_faithful());
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+///
+/// [`TraceRecorder`]: super::TraceRecorder
+/// [`first_divergence()`]: TraceReplay::first_divergence
+#[derive(Debug, Clone)]
+pub struct TraceReplay {
+    expected: Vec<TraceRecord>,
+    events_observed: usize,
+    first_divergence: Option<TraceDivergence>,
+}
+
+impl TraceReplay {
+    /// Construct a replay that expects events to match the provided records, in order.
+    pub fn new(expected: Vec<TraceRecord>) -> Self {
+        Self {
+            expected,
+            events_observed: 0,
+            first_divergence: None,
+        }
+    }
+
+    /// Construct a replay from a trace written by a [`TraceRecorder`].
+    ///
+    /// # Errors
+    ///
+    /// Errors are passed back from [`read_trace()`].
+    ///
+    /// [`TraceRecorder`]: super::TraceRecorder
+    /// [`read_trace()`]: super::read_trace
+    pub fn from_reader<Reader>(reader: Reader) -> std::io::Result<Self>
+    where
+        Reader: std::io::BufRead,
+    {
+        Ok(Self::new(super::read_trace(reader)?))
+    }
+
+    /// Get the number of events that have executed while this replay was observing.
+    pub fn events_observed(&self) -> usize {
+        self.events_observed
+    }
+
+    /// Get the first point at which the observed run disagreed with the expected trace, if any.
+    ///
+    /// A run that ends before executing every expected event is not considered to have diverged by this method, as
+    /// the observer cannot tell when a run has ended. Use [`is_faithful()`] to also check that the run was complete.
+    ///
+    /// [`is_faithful()`]: TraceReplay::is_faithful
+    pub fn first_divergence(&self) -> Option<&TraceDivergence> {
+        self.first_divergence.as_ref()
+    }
+
+    /// Get whether the observed run has matched the expected trace exactly, executing each of its events and no others.
+    pub fn is_faithful(&self) -> bool {
+        self.first_divergence.is_none() && self.events_observed == self.expected.len()
+    }
+}
+
+impl<Time> Observer<Time> for TraceReplay
+where
+    Time: SimTime,
+{
+    fn before_event(&mut self, event: &ObservedEvent<'_, Time>) {
+        let index = self.events_observed;
+        self.events_observed += 1;
+        if self.first_divergence.is_some() {
+            return;
+        }
+
+        let actual = TraceRecord::from(event);
+        let expected = self.expected.get(index);
+        if expected != Some(&actual) {
+            self.first_divergence = Some(TraceDivergence {
+                index,
+                expected: expected.cloned(),
+                actual,
+            });
+        }
+    }
+}
+
+/// Describes the first event at which a run disagreed with the trace it was replayed against.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TraceDivergence {
+    index: usize,
+    expected: Option<TraceRecord>,
+    actual: TraceRecord,
+}
+
+impl TraceDivergence {
+    /// Get the position of the diverging event within the run, counting from zero.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Get the record the trace expected at this position, or `None` if the run executed more events than the trace
+    /// contains.
+    pub fn expected(&self) -> Option<&TraceRecord> {
+        self.expected.as_ref()
+    }
+
+    /// Get the record describing the event that actually executed at this position.
+    pub fn actual(&self) -> &TraceRecord {
+        &self.actual
+    }
+}
+
+impl std::fmt::Display for TraceDivergence {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.expected {
+            Some(expected) => write!(
+                f,
+                "event {} diverged from trace: expected {}, found {}",
+                self.index,
+                expected.to_json_line(),
+                self.actual.to_json_line()
+            ),
+            None => write!(
+                f,
+                "event {} extends beyond end of trace: found {}",
+                self.index,
+                self.actual.to_json_line()
+            ),
+        }
+    }
+}
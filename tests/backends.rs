@@ -0,0 +1,119 @@
+use desque::queue::{BinaryHeapQueue, CalendarQueue, EventQueueBackend, LadderQueue, PairingHeapQueue, RadixHeapQueue};
+use desque::serial::*;
+use desque::{EventHandle, SimState};
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+/// Simulation state for a model that spawns jobs at random, occasionally cancelling or delaying one that has yet to run
+struct Workshop {
+    jobs_spawned: usize,
+    waiting: Vec<EventHandle>,
+    executed: Vec<(u64, usize)>,
+    rng: Pcg64,
+}
+
+impl SimState<u64> for Workshop {}
+
+/// A job that records its execution, then spawns further jobs
+#[derive(Debug)]
+struct Job {
+    id: usize,
+}
+
+impl Job {
+    fn spawn<Backend>(sim: &mut Simulation<Workshop, u64, Backend>)
+    where
+        Backend: EventQueueBackend<u64>,
+    {
+        let state = sim.state_mut();
+        let id = state.jobs_spawned;
+        state.jobs_spawned += 1;
+        let delay = state.rng.random_range(0..50);
+        let priority = state.rng.random_range(-2..=2);
+
+        let time = sim.current_time() + delay;
+        let handle = sim
+            .schedule_with_priority(Self { id }, time, priority)
+            .expect("delay should not be negative");
+        sim.state_mut().waiting.push(handle);
+    }
+}
+
+impl<Backend> OkEvent<Workshop, u64, Backend> for Job
+where
+    Backend: EventQueueBackend<u64>,
+{
+    fn execute(&mut self, sim: &mut Simulation<Workshop, u64, Backend>) {
+        let now = *sim.current_time();
+        sim.state_mut().executed.push((now, self.id));
+        if sim.state().jobs_spawned >= 5_000 {
+            return;
+        }
+
+        let spawned = sim.state_mut().rng.random_range(0..4);
+        for _ in 0..spawned {
+            Job::spawn(sim);
+        }
+
+        let waiting = sim.state().waiting.len();
+        if waiting == 0 {
+            return;
+        }
+        let target = sim.state_mut().rng.random_range(0..waiting);
+        let handle = sim.state_mut().waiting.swap_remove(target);
+        match sim.state_mut().rng.random_range(0..4) {
+            0 => {
+                sim.cancel(handle);
+            },
+            1 => {
+                let delay = sim.state_mut().rng.random_range(0..20);
+                if let Ok(handle) = sim.reschedule(handle, now + delay) {
+                    sim.state_mut().waiting.push(handle);
+                }
+            },
+            _ => sim.state_mut().waiting.push(handle),
+        }
+    }
+}
+
+fn run_with<Backend>(backend: Backend) -> Vec<(u64, usize)>
+where
+    Backend: EventQueueBackend<u64>,
+{
+    let workshop = Workshop {
+        jobs_spawned: 0,
+        waiting: Vec::new(),
+        executed: Vec::new(),
+        rng: Pcg64::seed_from_u64(9_004_173),
+    };
+    let mut sim = Simulation::with_backend(workshop, 0, backend);
+    for _ in 0..20 {
+        Job::spawn(&mut sim);
+    }
+
+    sim.run().expect("simulation should complete normally");
+    assert_eq!(
+        0,
+        sim.pending_event_count(),
+        "simulation should run until the queue is empty"
+    );
+    sim.state().executed.clone()
+}
+
+#[test]
+fn backends_execute_events_in_identical_order() {
+    let expected = run_with(BinaryHeapQueue::new());
+    assert!(
+        expected.len() > 1_000,
+        "model should execute a substantial number of events"
+    );
+    assert!(
+        expected.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+        "events should execute in order of time"
+    );
+
+    assert_eq!(expected, run_with(CalendarQueue::new()), "calendar queue diverged");
+    assert_eq!(expected, run_with(PairingHeapQueue::new()), "pairing heap diverged");
+    assert_eq!(expected, run_with(LadderQueue::new()), "ladder queue diverged");
+    assert_eq!(expected, run_with(RadixHeapQueue::new()), "radix heap diverged");
+}
@@ -0,0 +1,165 @@
+use desque::resource::{Container, Store};
+use desque::serial::*;
+use desque::{Recurrence, SimState};
+use std::cell::Cell;
+use std::rc::Rc;
+
+/// A warehouse with a fuel tank for its forklifts and a rack of pallets, each pallet labelled by its destination
+struct Warehouse {
+    fuel: Container<u32, Log>,
+    rack: Store<&'static str, Log>,
+    log: Vec<(u32, String)>,
+}
+
+impl SimState<u32> for Warehouse {}
+
+/// Records the completion of a request
+#[derive(Debug)]
+struct Log(String);
+
+impl OkEvent<Warehouse, u32> for Log {
+    fn execute(&mut self, sim: &mut Simulation<Warehouse, u32>) {
+        let now = *sim.current_time();
+        let entry = std::mem::take(&mut self.0);
+        sim.state_mut().log.push((now, entry));
+    }
+}
+
+fn warehouse() -> Simulation<Warehouse, u32> {
+    let warehouse = Warehouse {
+        fuel: Container::new(10, 0),
+        rack: Store::new(2),
+        log: Vec::new(),
+    };
+    Simulation::new(warehouse, 0)
+}
+
+#[test]
+fn refuelling_waits_for_deliveries() {
+    let mut sim = warehouse();
+    sim.schedule_fn(
+        |sim| sim.drain_container(|warehouse| &mut warehouse.fuel, 5, Log("refuelled".into())),
+        1,
+    )
+    .unwrap();
+    sim.schedule_periodic(
+        || {
+            FnEvent::new(|sim: &mut Simulation<Warehouse, u32>| {
+                sim.fill_container(|warehouse| &mut warehouse.fuel, 2, Log("delivered".into()))
+            })
+        },
+        2,
+        2,
+        Recurrence::Times(4),
+    )
+    .unwrap();
+
+    sim.run().unwrap();
+    let log: Vec<_> = sim
+        .state()
+        .log
+        .iter()
+        .map(|(time, entry)| (*time, entry.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (2, "delivered"),
+            (4, "delivered"),
+            (6, "delivered"),
+            (6, "refuelled"),
+            (8, "delivered"),
+        ],
+        log,
+        "refuelling should wait until enough fuel has been delivered"
+    );
+    assert_eq!(3, sim.state().fuel.level());
+}
+
+#[test]
+fn picking_waits_for_matching_pallets() {
+    let mut sim = warehouse();
+    sim.schedule_fn(
+        |sim| {
+            sim.get_item_where(
+                |warehouse| &mut warehouse.rack,
+                |destination| *destination == "north",
+                |destination| Log(format!("picked {}", destination)),
+            )
+        },
+        0,
+    )
+    .unwrap();
+    for (time, destination) in [(1, "south"), (2, "east"), (3, "north")] {
+        sim.schedule_fn(
+            move |sim| {
+                sim.put_item(
+                    |warehouse| &mut warehouse.rack,
+                    destination,
+                    Log(format!("racked {}", destination)),
+                )
+            },
+            time,
+        )
+        .unwrap();
+    }
+    sim.schedule_fn(
+        |sim| {
+            sim.get_item(
+                |warehouse| &mut warehouse.rack,
+                |destination| Log(format!("picked {}", destination)),
+            )
+        },
+        4,
+    )
+    .unwrap();
+
+    sim.run().unwrap();
+    let log: Vec<_> = sim
+        .state()
+        .log
+        .iter()
+        .map(|(time, entry)| (*time, entry.as_str()))
+        .collect();
+    assert_eq!(
+        vec![
+            (1, "racked south"),
+            (2, "racked east"),
+            (4, "picked south"),
+            (4, "racked north"),
+            (4, "picked north"),
+        ],
+        log,
+        "full rack should hold back the third pallet until a pallet is picked"
+    );
+    assert_eq!(vec![&"east"], sim.state().rack.items().collect::<Vec<_>>());
+}
+
+#[test]
+fn filters_can_share_data_without_send() {
+    let mut sim = warehouse();
+    let wanted = Rc::new(Cell::new("south"));
+    let filter_wanted = Rc::clone(&wanted);
+    sim.get_item_where(
+        |warehouse| &mut warehouse.rack,
+        move |destination| *destination == filter_wanted.get(),
+        |destination| Log(format!("picked {}", destination)),
+    )
+    .unwrap();
+    wanted.set("east");
+    for destination in ["south", "east"] {
+        sim.put_item(
+            |warehouse| &mut warehouse.rack,
+            destination,
+            Log(format!("racked {}", destination)),
+        )
+        .unwrap();
+    }
+
+    sim.run().unwrap();
+    let log: Vec<_> = sim.state().log.iter().map(|(_, entry)| entry.as_str()).collect();
+    assert_eq!(
+        vec!["racked south", "racked east", "picked east"],
+        log,
+        "filter should read the destination as updated after the request was made"
+    );
+}
@@ -9,14 +9,20 @@ use std::cmp::Ordering;
 use std::collections::VecDeque;
 
 /// Wrap f64 in a struct to implement Ord and SimTime
-#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 struct F64Time(f64);
 
 impl Eq for F64Time {}
 
+impl PartialOrd for F64Time {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl Ord for F64Time {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.partial_cmp(other).unwrap()
+        self.0.partial_cmp(&other.0).unwrap()
     }
 }
 
@@ -0,0 +1,167 @@
+use desque::serial::*;
+use desque::trace::{read_trace, TraceRecorder, TraceReplay};
+use desque::SimState;
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64;
+
+/// Simulation state for a single-server queue that counts time in whole ticks
+struct Shop {
+    waiting: usize,
+    server_busy: bool,
+    rng: Pcg64,
+}
+
+impl SimState<u64> for Shop {
+    fn is_complete(&self, current_time: &u64) -> bool {
+        *current_time >= 500
+    }
+}
+
+/// Customer enters the shop
+#[derive(Debug)]
+struct Arrival {
+    customer: usize,
+}
+
+impl Arrival {
+    fn schedule(customer: usize, sim: &mut Simulation<Shop, u64>) {
+        let delay = sim.state_mut().rng.random_range(1..10);
+        sim.schedule_with_delay(Self { customer }, delay)
+            .expect("arrival delay should be positive");
+    }
+}
+
+impl OkEvent<Shop, u64> for Arrival {
+    fn execute(&mut self, sim: &mut Simulation<Shop, u64>) {
+        if sim.state().server_busy {
+            sim.state_mut().waiting += 1;
+        } else {
+            sim.state_mut().server_busy = true;
+            Departure::schedule(sim);
+        }
+        Self::schedule(self.customer + 1, sim);
+    }
+}
+
+/// Customer leaves the counter
+#[derive(Debug)]
+struct Departure {}
+
+impl Departure {
+    fn schedule(sim: &mut Simulation<Shop, u64>) {
+        let delay = sim.state_mut().rng.random_range(1..12);
+        sim.schedule_with_delay(Self {}, delay)
+            .expect("service delay should be positive");
+    }
+}
+
+impl OkEvent<Shop, u64> for Departure {
+    fn execute(&mut self, sim: &mut Simulation<Shop, u64>) {
+        if sim.state().waiting > 0 {
+            sim.state_mut().waiting -= 1;
+            Self::schedule(sim);
+        } else {
+            sim.state_mut().server_busy = false;
+        }
+    }
+}
+
+fn build_sim(seed: u64) -> Simulation<Shop, u64> {
+    let shop = Shop {
+        waiting: 0,
+        server_busy: false,
+        rng: Pcg64::seed_from_u64(seed),
+    };
+    let mut sim = Simulation::new(shop, 0);
+    Arrival::schedule(0, &mut sim);
+    sim
+}
+
+fn record_trace(seed: u64) -> Vec<u8> {
+    let mut sim = build_sim(seed);
+    let recorder = sim.add_observer(TraceRecorder::in_memory());
+    sim.run().expect("simulation should complete normally");
+
+    sim.remove_observer(recorder)
+        .expect("recorder should still be attached")
+        .finish()
+        .expect("in-memory trace should not fail")
+}
+
+fn replay_trace(seed: u64, trace: &[u8]) -> TraceReplay {
+    let mut sim = build_sim(seed);
+    let replay = TraceReplay::from_reader(trace).expect("trace should be readable");
+    let replay = sim.add_observer(replay);
+    sim.run().expect("simulation should complete normally");
+
+    sim.remove_observer(replay).expect("replay should still be attached")
+}
+
+#[test]
+fn recorded_trace_describes_each_event() {
+    let trace = record_trace(5283011922716735907);
+    let records = read_trace(trace.as_slice()).expect("trace should be readable");
+
+    assert!(records.len() > 100, "trace should cover the whole run");
+    let first = &records[0];
+    assert_eq!(0, first.insertion_sequence, "first record should be the first arrival");
+    assert!(first.name.ends_with("Arrival"), "record should name the event type");
+    assert_eq!(
+        "Arrival { customer: 0 }", first.event,
+        "record should hold event payload"
+    );
+    assert!(
+        records.windows(2).all(|pair| {
+            pair[0].execution_time.parse::<u64>().unwrap() <= pair[1].execution_time.parse::<u64>().unwrap()
+        }),
+        "records should be in execution order"
+    );
+}
+
+#[test]
+fn same_seed_replays_faithfully() {
+    let seed = 5283011922716735907;
+    let trace = record_trace(seed);
+    assert_eq!(trace, record_trace(seed), "traces of the same seed should be identical");
+
+    let replay = replay_trace(seed, &trace);
+    assert!(replay.first_divergence().is_none(), "replay should not diverge");
+    assert!(replay.is_faithful(), "replay should execute every recorded event");
+}
+
+#[test]
+fn different_seed_diverges() {
+    let trace = record_trace(5283011922716735907);
+    let replay = replay_trace(9136027416201773451, &trace);
+
+    let divergence = replay.first_divergence().expect("replay should diverge");
+    assert!(!replay.is_faithful());
+    let expected = divergence.expected().expect("divergence should occur within trace");
+    assert_ne!(
+        expected,
+        divergence.actual(),
+        "divergence should report differing records"
+    );
+}
+
+#[test]
+fn truncated_trace_diverges_at_end() {
+    let seed = 5283011922716735907;
+    let trace = record_trace(seed);
+    let records = read_trace(trace.as_slice()).expect("trace should be readable");
+    let truncated: Vec<_> = records.iter().take(10).cloned().collect();
+
+    let mut sim = build_sim(seed);
+    let replay = sim.add_observer(TraceReplay::new(truncated));
+    sim.run().expect("simulation should complete normally");
+    let replay = sim.remove_observer(replay).expect("replay should still be attached");
+
+    let divergence = replay.first_divergence().expect("replay should diverge");
+    assert_eq!(10, divergence.index(), "divergence should occur after the last record");
+    assert_eq!(None, divergence.expected(), "trace should have no record at that index");
+    assert_eq!(
+        &records[10],
+        divergence.actual(),
+        "run should still match the full trace"
+    );
+}